@@ -1,9 +1,34 @@
-use std::process::Command;
+use std::{env, path::PathBuf, process::Command, sync::OnceLock};
 
 use cargo_metadata::camino::Utf8Path;
 
 const CARGO_TERM_QUIET: &str = "CARGO_TERM_QUIET";
 const FALSE: &str = "false";
+const CARGO: &str = "CARGO";
+
+/// Cargo executable set with [`set_cargo_bin`].
+static CARGO_BIN: OnceLock<PathBuf> = OnceLock::new();
+
+/// Override the cargo executable used for every cargo invocation of the process.
+/// Takes precedence over the `CARGO` environment variable.
+/// Can be called only once.
+pub fn set_cargo_bin(cargo_bin: PathBuf) -> anyhow::Result<()> {
+    CARGO_BIN
+        .set(cargo_bin)
+        .map_err(|cargo_bin| anyhow::anyhow!("cargo executable already set to {cargo_bin:?}"))
+}
+
+/// Cargo executable to run.
+/// In order of precedence:
+/// - the executable set with [`set_cargo_bin`].
+/// - the `CARGO` environment variable.
+/// - `cargo`, resolved from `PATH`.
+pub fn cargo_bin() -> PathBuf {
+    if let Some(cargo_bin) = CARGO_BIN.get() {
+        return cargo_bin.clone();
+    }
+    env::var_os(CARGO).map_or_else(|| PathBuf::from("cargo"), PathBuf::from)
+}
 
 /// Disable cargo's quiet mode, to improve the debugging experience when cargo fails.
 /// Plus, release-plz parses cargo's output to determine what happened.
@@ -13,6 +38,7 @@ pub fn disable_cargo_quiet(command: &mut Command) -> &mut Command {
 
 pub fn cargo_metadata_command() -> cargo_metadata::MetadataCommand {
     let mut command = cargo_metadata::MetadataCommand::new();
+    command.cargo_path(cargo_bin());
     disable_cargo_metadata_quiet(&mut command);
     command
 }
@@ -4,7 +4,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use url::Url;
 
-const CRATES_IO_INDEX: &str = "https://github.com/rust-lang/crates.io-index";
+pub const CRATES_IO_INDEX: &str = "https://github.com/rust-lang/crates.io-index";
+/// Url of the crates.io index when accessed with the sparse protocol.
+pub const CRATES_IO_SPARSE_INDEX: &str = "sparse+https://index.crates.io/";
 const CRATES_IO_REGISTRY: &str = "crates-io";
 
 /// Read index for a specific registry using environment variables.
@@ -19,6 +19,41 @@ pub struct Repo {
     original_branch: String,
     /// Remote name before running any git operation
     original_remote: String,
+    /// Remote where branches are pushed.
+    /// If [`Option::None`], branches are pushed to `original_remote`.
+    push_remote: Option<String>,
+    /// Identity used to create commits and tags.
+    identity: GitIdentity,
+}
+
+/// Name and email that git records as author and committer of commits,
+/// and as tagger of annotated tags.
+/// Fields set to [`Option::None`] fall back to the git configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitIdentity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl GitIdentity {
+    /// Returns `true` if at least one field overrides the git configuration.
+    pub fn is_set(&self) -> bool {
+        self.name.is_some() || self.email.is_some()
+    }
+
+    /// `-c` arguments that override the git configuration with this identity.
+    fn config_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(name) = &self.name {
+            args.push("-c".to_string());
+            args.push(format!("user.name={name}"));
+        }
+        if let Some(email) = &self.email {
+            args.push("-c".to_string());
+            args.push(format!("user.email={email}"));
+        }
+        args
+    }
 }
 
 impl Repo {
@@ -34,9 +69,24 @@ impl Repo {
             directory: directory.as_ref().to_path_buf(),
             original_branch: current_branch,
             original_remote: current_remote,
+            push_remote: None,
+            identity: GitIdentity::default(),
         })
     }
 
+    /// Push branches to the given remote instead of the original one.
+    pub fn with_push_remote(mut self, remote: impl Into<String>) -> Self {
+        self.push_remote = Some(remote.into());
+        self
+    }
+
+    /// Create commits and tags with the given identity
+    /// instead of the one of the git configuration.
+    pub fn with_identity(mut self, identity: GitIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+
     pub fn directory(&self) -> &Utf8Path {
         &self.directory
     }
@@ -66,6 +116,10 @@ impl Repo {
                     Ok(("origin".to_string(), branch))
                 } else if err.contains("fatal: ambiguous argument 'HEAD': unknown revision or path not in the working tree.") {
                     Err(anyhow!("git repository does not contain any commit."))
+                } else if err.contains("fatal: HEAD does not point to a branch")
+                    && is_jj_colocated(directory.as_ref())
+                {
+                    jj_current_remote_and_branch(directory.as_ref())
                 } else {
                     Err(e)
                 }
@@ -97,7 +151,7 @@ impl Repo {
 
     pub fn add_all_and_commit(&self, message: &str) -> anyhow::Result<()> {
         self.git(&["add", "."])?;
-        self.git(&["commit", "-m", message])?;
+        self.git_with_identity(&["commit", "-m", message])?;
         Ok(())
     }
 
@@ -142,17 +196,17 @@ impl Repo {
     }
 
     pub fn commit(&self, message: &str) -> anyhow::Result<()> {
-        self.git(&["commit", "-m", message])?;
+        self.git_with_identity(&["commit", "-m", message])?;
         Ok(())
     }
 
     pub fn commit_signed(&self, message: &str) -> anyhow::Result<()> {
-        self.git(&["commit", "-s", "-m", message])?;
+        self.git_with_identity(&["commit", "-s", "-m", message])?;
         Ok(())
     }
 
     pub fn push(&self, obj: &str) -> anyhow::Result<()> {
-        self.git(&["push", &self.original_remote, obj])?;
+        self.git(&["push", self.push_remote(), obj])?;
         Ok(())
     }
 
@@ -168,7 +222,7 @@ impl Repo {
         // In other words, it will only push if no one else has pushed changes to the remote
         // branch since you last pulled. If someone else has pushed changes, the command will fail,
         // preventing you from accidentally overwriting someone else's work.
-        self.git(&["push", &self.original_remote, obj, "--force-with-lease"])
+        self.git(&["push", self.push_remote(), obj, "--force-with-lease"])
             .with_context(|| format!("failed to force-push {obj}"))?;
         Ok(())
     }
@@ -179,6 +233,11 @@ impl Repo {
         Ok(())
     }
 
+    /// Identity used to create commits and tags.
+    pub fn identity(&self) -> &GitIdentity {
+        &self.identity
+    }
+
     /// Branch name before running any git operation.
     /// I.e. when the [`Repo`] was created.
     pub fn original_branch(&self) -> &str {
@@ -190,6 +249,18 @@ impl Repo {
         git_in_dir(&self.directory, args)
     }
 
+    /// Run a git command that records the identity of the user,
+    /// overriding the git configuration with [`Self::with_identity`].
+    fn git_with_identity(&self, args: &[&str]) -> anyhow::Result<String> {
+        let identity_args = self.identity.config_args();
+        let all_args: Vec<&str> = identity_args
+            .iter()
+            .map(String::as_str)
+            .chain(args.iter().copied())
+            .collect();
+        self.git(&all_args)
+    }
+
     pub fn stash_pop(&self) -> anyhow::Result<()> {
         self.git(&["stash", "pop"])?;
         Ok(())
@@ -269,6 +340,53 @@ impl Repo {
         Ok(last_commit.to_string())
     }
 
+    /// Commits reachable from `HEAD`, but not from `since`, that changed files matching
+    /// at least one of the given globs. Globs are relative to the repository root.
+    /// Returns `(commit hash, commit message)` pairs, from the newest to the oldest commit.
+    pub fn commits_at_globs(
+        &self,
+        since: Option<&str>,
+        max_count: u32,
+        globs: &[String],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let range = since.map_or_else(|| "HEAD".to_string(), |since| format!("{since}..HEAD"));
+        let max_count = format!("--max-count={max_count}");
+        self.log_commits_at_globs(&[&max_count, &range], globs)
+    }
+
+    /// Commits reachable from `to`, but not from `from`, that changed files matching
+    /// at least one of the given globs. Globs are relative to the repository root.
+    /// Returns `(commit hash, commit message)` pairs, from the newest to the oldest commit.
+    pub fn commits_between(
+        &self,
+        from: &str,
+        to: &str,
+        globs: &[String],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let range = format!("{from}..{to}");
+        self.log_commits_at_globs(&[&range], globs)
+            .with_context(|| format!("cannot list the commits between `{from}` and `{to}`"))
+    }
+
+    fn log_commits_at_globs(
+        &self,
+        log_args: &[&str],
+        globs: &[String],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let pathspecs: Vec<String> = globs.iter().map(|g| format!(":(top,glob){g}")).collect();
+        let mut args = vec!["log", "-z", "--pretty=format:%H%n%B"];
+        args.extend(log_args);
+        args.push("--");
+        args.extend(pathspecs.iter().map(String::as_str));
+        let output = self.git(&args)?;
+        let commits = output
+            .split('\0')
+            .filter_map(|commit| commit.trim().split_once('\n'))
+            .map(|(hash, message)| (hash.to_string(), message.trim().to_string()))
+            .collect();
+        Ok(commits)
+    }
+
     pub fn current_commit_message(&self) -> anyhow::Result<String> {
         self.git(&["log", "-1", "--pretty=format:%B"])
     }
@@ -301,7 +419,7 @@ impl Repo {
 
     /// Create a git tag (annotated)
     pub fn tag(&self, name: &str, message: &str) -> anyhow::Result<String> {
-        self.git(&["tag", "-m", message, name])
+        self.git_with_identity(&["tag", "-m", message, name])
     }
 
     /// Create a lightweight git tag (no message, just a reference to a commit)
@@ -327,6 +445,19 @@ impl Repo {
         }
     }
 
+    /// Tags of the original remote, fetched with a single `git ls-remote` call.
+    pub fn remote_tags(&self) -> anyhow::Result<HashSet<String>> {
+        let output = self
+            .git(&["ls-remote", "--tags", "--refs", &self.original_remote])
+            .context("cannot list the tags of the remote")?;
+        let tags = output
+            .lines()
+            .filter_map(|line| line.split_once("refs/tags/"))
+            .map(|(_, tag)| tag.to_string())
+            .collect();
+        Ok(tags)
+    }
+
     /// Check if a commit comes before another one.
     ///
     /// ## Example
@@ -348,11 +479,25 @@ impl Repo {
         .is_ok()
     }
 
+    /// Check if a commit has more than one parent.
+    pub fn is_merge_commit(&self, commit_hash: &str) -> anyhow::Result<bool> {
+        let output = self
+            .git(&["rev-list", "--parents", "-n", "1", commit_hash])
+            .with_context(|| format!("cannot determine parents of commit {commit_hash}"))?;
+        // The output contains the commit hash followed by the hashes of its parents.
+        Ok(output.split_whitespace().count() > 2)
+    }
+
     /// Name of the remote when the [`Repo`] was created.
     pub fn original_remote(&self) -> &str {
         &self.original_remote
     }
 
+    /// Name of the remote where branches are pushed.
+    pub fn push_remote(&self) -> &str {
+        self.push_remote.as_deref().unwrap_or(&self.original_remote)
+    }
+
     /// Url of the remote when the [`Repo`] was created.
     pub fn original_remote_url(&self) -> anyhow::Result<String> {
         let param = format!("remote.{}.url", self.original_remote);
@@ -388,6 +533,19 @@ pub fn is_file_committed(repo_path: &Utf8Path, file: &Utf8Path) -> bool {
     git_in_dir(repo_path, &["ls-files", "--error-unmatch", file]).is_ok()
 }
 
+/// Whether `dir` is inside the working tree of a git repository.
+pub fn is_inside_work_tree(dir: &Utf8Path) -> bool {
+    git_in_dir(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|output| output == "true")
+}
+
+/// Get the files with uncommitted changes, except typechanges, of the repository containing `dir`.
+/// Unlike [`Repo::changes_except_typechanges`], this doesn't require the repository to be on a branch.
+/// Paths are relative to the root of the repository.
+pub fn changes_except_typechanges_in_dir(dir: &Utf8Path) -> anyhow::Result<Vec<String>> {
+    let output = git_in_dir(dir, &["status", "--porcelain"])?;
+    Ok(changed_files(&output, |line| !line.starts_with("T ")))
+}
+
 fn changed_files(output: &str, filter: impl FnMut(&&str) -> bool) -> Vec<String> {
     output
         .lines()
@@ -433,6 +591,52 @@ pub fn git_in_dir(dir: &Utf8Path, args: &[&str]) -> anyhow::Result<String> {
     }
 }
 
+/// Whether the repository is a [Jujutsu](https://jj-vcs.github.io/jj/) repository
+/// colocated with git, i.e. whether the root of the repository contains a `.jj` directory.
+///
+/// In these repositories, `HEAD` is detached and points to the parent of the
+/// Jujutsu working-copy commit, whose changes look uncommitted to git.
+pub fn is_jj_colocated(directory: &Utf8Path) -> bool {
+    git_in_dir(directory, &["rev-parse", "--show-toplevel"])
+        .is_ok_and(|root| Utf8Path::new(&root).join(".jj").is_dir())
+}
+
+/// Jujutsu keeps `HEAD` detached, so the current branch is
+/// the branch (a bookmark in Jujutsu) pointing to `HEAD`.
+fn jj_current_remote_and_branch(directory: &Utf8Path) -> anyhow::Result<(String, String)> {
+    let branches = git_in_dir(
+        directory,
+        &[
+            "for-each-ref",
+            "--points-at",
+            "HEAD",
+            "--format=%(refname:short)",
+            "refs/heads",
+        ],
+    )?;
+    let branch = branches.lines().next().context(
+        "HEAD is detached and no branch points to it. \
+         Create a bookmark on the parent of the Jujutsu working-copy commit, e.g. `jj bookmark set main -r @-`",
+    )?;
+    debug!("jujutsu repository detected, using branch {branch} pointing to HEAD");
+    let upstream = git_in_dir(
+        directory,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            &format!("{branch}@{{upstream}}"),
+        ],
+    );
+    match upstream.ok().as_deref().and_then(|u| u.split_once('/')) {
+        Some((remote, upstream_branch)) => Ok((remote.to_string(), upstream_branch.to_string())),
+        None => {
+            warn!("no upstream configured for branch {branch}");
+            Ok(("origin".to_string(), branch.to_string()))
+        }
+    }
+}
+
 /// Get the name of the current branch.
 fn get_current_branch(directory: impl AsRef<Utf8Path>) -> anyhow::Result<String> {
     git_in_dir(directory.as_ref(), &["rev-parse", "--abbrev-ref", "HEAD"]).map_err(|e| {
@@ -452,6 +656,110 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn branch_of_detached_head_is_detected_in_jj_repository() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        let branch = repo.original_branch().to_string();
+        repo.git(&["checkout", "--detach"]).unwrap();
+        let directory = Utf8Path::from_path(repository_dir.path()).unwrap();
+        assert!(!is_jj_colocated(directory));
+        Repo::new(directory).unwrap_err();
+
+        fs_err::create_dir(directory.join(".jj")).unwrap();
+        assert!(is_jj_colocated(directory));
+        let repo = Repo::new(directory).unwrap();
+        assert_eq!(repo.original_branch(), branch);
+        assert_eq!(repo.original_remote(), "origin");
+    }
+
+    #[test]
+    fn work_tree_is_detected() {
+        let directory = tempdir().unwrap();
+        let directory = Utf8Path::from_path(directory.path()).unwrap();
+        assert!(!is_inside_work_tree(directory));
+        Repo::init(directory);
+        assert!(is_inside_work_tree(directory));
+        assert!(!is_inside_work_tree(&directory.join(".git")));
+    }
+
+    #[test]
+    fn remote_tags_are_listed() {
+        let repository_dir = tempdir().unwrap();
+        let remote_dir = tempdir().unwrap();
+        let remote_path = Utf8Path::from_path(remote_dir.path()).unwrap();
+        git_in_dir(remote_path, &["init", "--bare"]).unwrap();
+        let repo = Repo::init(&repository_dir);
+        repo.git(&["remote", "add", "origin", remote_path.as_str()])
+            .unwrap();
+        repo.tag_lightweight("v1.0.0").unwrap();
+        repo.push("v1.0.0").unwrap();
+        repo.tag_lightweight("v2.0.0").unwrap();
+
+        let remote_tags = repo.remote_tags().unwrap();
+        assert_eq!(remote_tags, HashSet::from(["v1.0.0".to_string()]));
+    }
+
+    #[test]
+    fn merge_commits_are_detected() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        let first_commit = repo.current_commit_hash().unwrap();
+        repo.checkout_new_branch("feature").unwrap();
+        fs_err::write(repository_dir.path().join("feature.txt"), "feature").unwrap();
+        repo.add_all_and_commit("feat: add feature").unwrap();
+        let feature_commit = repo.current_commit_hash().unwrap();
+        repo.checkout(&first_commit).unwrap();
+        repo.git(&[
+            "merge",
+            "--no-ff",
+            "-m",
+            "Merge branch 'feature'",
+            "feature",
+        ])
+        .unwrap();
+        let merge_commit = repo.current_commit_hash().unwrap();
+
+        assert!(repo.is_merge_commit(&merge_commit).unwrap());
+        assert!(!repo.is_merge_commit(&feature_commit).unwrap());
+        assert!(!repo.is_merge_commit(&first_commit).unwrap());
+    }
+
+    #[test]
+    fn commits_at_globs_are_found() {
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir);
+        let first_commit = repo.current_commit_hash().unwrap();
+        let vendor_dir = repository_dir.path().join("vendor").join("foo");
+        fs_err::create_dir_all(&vendor_dir).unwrap();
+        fs_err::write(vendor_dir.join("lib.rs"), "fn foo() {}").unwrap();
+        repo.add_all_and_commit("feat: update foo\n\nmore details")
+            .unwrap();
+        let vendor_commit = repo.current_commit_hash().unwrap();
+        fs_err::write(repository_dir.path().join("other.txt"), "other").unwrap();
+        repo.add_all_and_commit("docs: other").unwrap();
+
+        let commits = repo
+            .commits_at_globs(Some(&first_commit), 100, &["vendor/foo/**".to_string()])
+            .unwrap();
+        assert_eq!(
+            commits,
+            vec![(
+                vendor_commit.clone(),
+                "feat: update foo\n\nmore details".to_string()
+            )]
+        );
+        let commits = repo
+            .commits_at_globs(None, 100, &["vendor/bar/**".to_string()])
+            .unwrap();
+        assert!(commits.is_empty());
+        let commits = repo
+            .commits_between(&first_commit, &vendor_commit, &["**".to_string()])
+            .unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].0, vendor_commit);
+    }
+
     #[test]
     fn inexistent_previous_commit_detected() {
         let repository_dir = tempdir().unwrap();
@@ -531,6 +839,43 @@ D  crates/git_cmd/CHANGELOG.md
         assert_eq!(changed_files, expected_changed_files);
     }
 
+    #[test]
+    fn commit_and_tag_use_custom_identity() {
+        test_logs::init();
+        let repository_dir = tempdir().unwrap();
+        let repo = Repo::init(&repository_dir).with_identity(GitIdentity {
+            name: Some("release-bot".to_string()),
+            email: Some("bot@example.com".to_string()),
+        });
+        let file1 = repository_dir.as_ref().join("file1.txt");
+        fs_err::write(file1, b"Hello, file1!").unwrap();
+        repo.add_all_and_commit("file1").unwrap();
+        let commit_hash = repo.current_commit_hash().unwrap();
+        assert_eq!(repo.get_author_name(&commit_hash).unwrap(), "release-bot");
+        assert_eq!(
+            repo.get_author_email(&commit_hash).unwrap(),
+            "bot@example.com"
+        );
+        assert_eq!(
+            repo.get_committer_name(&commit_hash).unwrap(),
+            "release-bot"
+        );
+        assert_eq!(
+            repo.get_committer_email(&commit_hash).unwrap(),
+            "bot@example.com"
+        );
+
+        repo.tag("v1.0.0", "test").unwrap();
+        let tagger = repo
+            .git(&[
+                "for-each-ref",
+                "--format=%(taggername) %(taggeremail)",
+                "refs/tags/v1.0.0",
+            ])
+            .unwrap();
+        assert_eq!(tagger, "release-bot <bot@example.com>");
+    }
+
     #[test]
     fn existing_tag_is_recognized() {
         test_logs::init();
@@ -91,6 +91,9 @@
 //! assert_eq!(Version::new(1, 2, 4).next(&commits), Version::new(2, 0, 0));
 //! ```
 //!
+//! The footer marks the commit as breaking regardless of its type, so a `fix` commit
+//! with a `BREAKING CHANGE:` (or `BREAKING-CHANGE:`) footer increments the major version, too.
+//!
 //! ## Pre-release
 //!
 //! Pre-release versions are incremented in the same way, independently
@@ -69,6 +69,27 @@ fn commit_with_breaking_change_increments_major_version_when_major_is_zero() {
     );
 }
 
+#[test]
+fn fix_commit_with_breaking_change_footer_increments_major_version() {
+    let commits = ["fix: serious bug\n\nBREAKING CHANGE: user will be broken"];
+    let version = Version::new(1, 2, 3);
+    assert_eq!(version.next(commits), Version::new(2, 0, 0));
+}
+
+#[test]
+fn fix_commit_with_hyphenated_breaking_change_footer_increments_major_version() {
+    let commits = ["fix: serious bug\n\nBREAKING-CHANGE: user will be broken"];
+    let version = Version::new(1, 2, 3);
+    assert_eq!(version.next(commits), Version::new(2, 0, 0));
+}
+
+#[test]
+fn fix_commit_with_breaking_change_footer_increments_minor_version_when_major_is_zero() {
+    let commits = ["fix: serious bug\n\nBREAKING CHANGE: user will be broken"];
+    let version = Version::new(0, 2, 3);
+    assert_eq!(version.next(commits), Version::new(0, 3, 0));
+}
+
 #[test]
 fn commit_with_custom_major_increment_regex_increments_major_version() {
     let commits = ["major: some changes"];
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+
+use super::{
+    config_path::ConfigPath,
+    update::{GitForgeKind, Update},
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct Changelog {
+    /// Path to the Cargo.toml of the project.
+    /// If not provided, release-plz will use the Cargo.toml of the current directory.
+    #[arg(long, value_parser = PathBufValueParser::new(), alias = "project-manifest")]
+    manifest_path: Option<PathBuf>,
+
+    /// Path to the Cargo.toml contained in the released version of the project.
+    /// If not provided, the packages of your project will be compared with the
+    /// ones published in the cargo registry.
+    #[arg(long, value_parser = PathBufValueParser::new(), alias = "registry-project-manifest")]
+    registry_manifest_path: Option<PathBuf>,
+
+    /// Package to print the changelog entry of. Use it when you want the entry of a single
+    /// package rather than the ones of all the packages contained in the workspace.
+    #[arg(
+        short,
+        long,
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    package: Option<String>,
+
+    /// Date of the release. Format: %Y-%m-%d.
+    /// If unspecified, the date of the `SOURCE_DATE_EPOCH` environment variable is used.
+    /// If `SOURCE_DATE_EPOCH` isn't set, it defaults to current Utc date.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    release_date: Option<String>,
+
+    /// Registry where the packages are stored.
+    /// The registry name needs to be present in the Cargo config.
+    /// If unspecified, the `publish` field of the package manifest is used.
+    /// If the `publish` field is empty, crates.io is used.
+    #[arg(
+        long,
+        conflicts_with("registry_manifest_path"),
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    registry: Option<String>,
+
+    /// Path to the git-cliff configuration file.
+    /// If not provided, `dirs::config_dir()/git-cliff/cliff.toml` is used if present.
+    #[arg(
+        long,
+        env = "GIT_CLIFF_CONFIG",
+        value_name = "PATH",
+        value_parser = PathBufValueParser::new()
+    )]
+    changelog_config: Option<PathBuf>,
+
+    /// Allow dirty working directories.
+    /// The uncommitted changes are considered part of the release.
+    #[arg(long)]
+    allow_dirty: bool,
+
+    /// GitHub/Gitea repository url where your project is hosted.
+    /// It is used to generate the changelog release link.
+    /// It defaults to the url of the default remote.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    repo_url: Option<String>,
+
+    /// Path to the release-plz config file.
+    #[command(flatten)]
+    config: ConfigPath,
+
+    /// Git token used to fetch the usernames of the authors of the commits.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new(), visible_alias = "github-token", env, hide_env_values=true)]
+    git_token: Option<String>,
+
+    /// Kind of git host where your project is hosted.
+    #[arg(long, visible_alias = "backend", value_enum, default_value_t = GitForgeKind::Github)]
+    forge: GitForgeKind,
+
+    /// Base URL of the API of the git forge.
+    /// Only used with the GitHub forge.
+    /// If unspecified, it's derived from the repository url.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    forge_api_url: Option<String>,
+
+    /// Maximum number of commits to analyze when the package hasn't been published yet.
+    /// Default: 1000.
+    #[arg(long)]
+    max_analyze_commits: Option<u32>,
+}
+
+impl Changelog {
+    /// The changelog entries are the ones that `release-plz update` would add,
+    /// so they are computed from the equivalent `update` arguments.
+    pub fn into_update(self) -> Update {
+        Update {
+            manifest_path: self.manifest_path.into_iter().collect(),
+            registry_manifest_path: self.registry_manifest_path,
+            package: self.package,
+            release_date: self.release_date,
+            registry: self.registry,
+            changelog_config: self.changelog_config,
+            allow_dirty: self.allow_dirty,
+            repo_url: self.repo_url,
+            config: self.config,
+            git_token: self.git_token,
+            forge: self.forge,
+            forge_api_url: self.forge_api_url,
+            max_analyze_commits: self.max_analyze_commits,
+            ..Update::default()
+        }
+    }
+}
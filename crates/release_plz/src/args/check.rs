@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+
+use super::{
+    config_path::ConfigPath,
+    update::{GitForgeKind, Update},
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct Check {
+    /// Path to the Cargo.toml of the project you want to check.
+    /// If not provided, release-plz will use the Cargo.toml of the current directory.
+    #[arg(long, value_parser = PathBufValueParser::new())]
+    manifest_path: Option<PathBuf>,
+
+    /// Path to the git-cliff configuration file.
+    /// If not provided, `dirs::config_dir()/git-cliff/cliff.toml` is used if present.
+    #[arg(
+        long,
+        env = "GIT_CLIFF_CONFIG",
+        value_name = "PATH",
+        value_parser = PathBufValueParser::new()
+    )]
+    changelog_config: Option<PathBuf>,
+
+    /// GitHub/Gitea repository url where your project is hosted.
+    /// It defaults to the url of the default remote.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    repo_url: Option<String>,
+
+    /// Path to the release-plz config file.
+    #[command(flatten)]
+    config: ConfigPath,
+
+    /// Git token to check. If not provided, the git forge settings aren't checked.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new(), visible_alias = "github-token", env, hide_env_values=true)]
+    git_token: Option<String>,
+
+    /// Kind of git host where your project is hosted.
+    #[arg(long, visible_alias = "backend", value_enum, default_value_t = GitForgeKind::Github)]
+    forge: GitForgeKind,
+
+    /// Base URL of the API of the git forge.
+    /// Only used with the GitHub forge.
+    /// If unspecified, it's derived from the repository url.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    forge_api_url: Option<String>,
+}
+
+impl Check {
+    /// The checks validate the configuration used by `release-plz update`,
+    /// so they run on the equivalent `update` arguments.
+    pub fn into_update(self) -> Update {
+        Update {
+            manifest_path: self.manifest_path.into_iter().collect(),
+            changelog_config: self.changelog_config,
+            repo_url: self.repo_url,
+            config: self.config,
+            git_token: self.git_token,
+            forge: self.forge,
+            forge_api_url: self.forge_api_url,
+            ..Update::default()
+        }
+    }
+}
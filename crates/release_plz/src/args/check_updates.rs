@@ -0,0 +1,9 @@
+use super::OutputType;
+
+#[derive(clap::Parser, Debug)]
+pub struct CheckUpdates {
+    /// Output format. If specified, prints the current and latest release-plz version
+    /// and whether an update is available.
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputType>,
+}
@@ -0,0 +1,33 @@
+use std::path::{Path, PathBuf};
+
+use clap::builder::PathBufValueParser;
+
+use super::{OutputType, config_path::ConfigPath, manifest_command::ManifestCommand};
+
+#[derive(clap::Parser, Debug)]
+pub struct ConfigCommand {
+    /// Print the configuration of every package of the workspace, after merging
+    /// the `[[package]]` sections with the `[workspace]` section.
+    /// By default, the configuration is printed as TOML.
+    #[arg(long, required = true)]
+    pub print: bool,
+
+    /// Path to the Cargo.toml of the project.
+    /// If not provided, release-plz will use the Cargo.toml of the current directory.
+    #[arg(long, value_parser = PathBufValueParser::new())]
+    manifest_path: Option<PathBuf>,
+
+    /// Path to the release-plz config file.
+    #[command(flatten)]
+    pub config: ConfigPath,
+
+    /// Output format. If unspecified, the configuration is printed as TOML.
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputType>,
+}
+
+impl ManifestCommand for ConfigCommand {
+    fn optional_manifest(&self) -> Option<&Path> {
+        self.manifest_path.as_deref()
+    }
+}
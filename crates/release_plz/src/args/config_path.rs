@@ -4,6 +4,7 @@ use std::{
 };
 
 use anyhow::{Context as _, bail};
+use cargo_utils::CARGO_TOML;
 use clap::Args;
 use fs_err::read_to_string;
 use tracing::info;
@@ -18,7 +19,8 @@ pub struct ConfigPath {
     /// Path to the release-plz config file.
     ///
     /// If not specified, the following paths are checked in order: `./release-plz.toml`,
-    /// `./.release-plz.toml`
+    /// `./.release-plz.toml`.
+    /// If none of them exists, the `[workspace.metadata.release_plz]` table of `./Cargo.toml` is used.
     ///
     /// If a config file is not found, the default configuration is used.
     #[arg(long = "config", value_name = "PATH")]
@@ -31,8 +33,23 @@ impl ConfigPath {
     /// If a path is specified, it will attempt to load the configuration from that file. If the
     /// file does not exist, it will return an error. If no path is specified, it will check the
     /// default paths (`release-plz.toml` and `.release-plz.toml`) and load the first one that
-    /// exists.
+    /// exists. Otherwise, it will load the `[workspace.metadata.release_plz]` table of `Cargo.toml`.
     pub fn load(&self) -> anyhow::Result<Config> {
+        self.load_in(Path::new(""))
+    }
+
+    /// Like [`ConfigPath::load`], but look for the default config files in `dir`
+    /// instead of the current directory.
+    pub fn load_in(&self, dir: &Path) -> anyhow::Result<Config> {
+        let config = self.load_unchecked(dir)?;
+        config.check_package_name_patterns()?;
+        config.check_dependencies_update()?;
+        release_plz_core::check_template_context(&config.workspace.template_context)
+            .context("invalid `template_context`")?;
+        Ok(config)
+    }
+
+    fn load_unchecked(&self, dir: &Path) -> anyhow::Result<Config> {
         if let Some(path) = self.path.as_deref() {
             match load_config(path) {
                 Ok(Some(config)) => return Ok(config),
@@ -40,21 +57,77 @@ impl ConfigPath {
                 Err(err) => return Err(err.context("failed to read config file")),
             }
         }
+        load_default_config(dir)
+    }
+}
 
-        for path in DEFAULT_CONFIG_PATHS {
-            let path = Path::new(path);
-            match load_config(path) {
-                Ok(Some(config)) => return Ok(config),
-                Ok(None) => (),
-                Err(err) => return Err(err.context("invalid config file")),
+/// Load the configuration from the default paths in `dir` or from the metadata of
+/// the `Cargo.toml` in `dir`.
+fn load_default_config(dir: &Path) -> anyhow::Result<Config> {
+    let mut file_config = None;
+    for path in DEFAULT_CONFIG_PATHS {
+        let path = dir.join(path);
+        match load_config(&path) {
+            Ok(Some(config)) => {
+                file_config = Some((path, config));
+                break;
             }
+            Ok(None) => (),
+            Err(err) => return Err(err.context("invalid config file")),
         }
+    }
 
-        info!("release-plz config file not found, using default configuration");
-        Ok(Config::default())
+    // The manifest can contain the configuration in the `[workspace.metadata.release_plz]` table.
+    let manifest_path = dir.join(CARGO_TOML);
+    let metadata_config = load_metadata_config(&manifest_path)?;
+    match (file_config, metadata_config) {
+        (Some((path, _)), Some(_)) => bail!(
+            "release-plz configuration found both in {} and in the `[workspace.metadata.release_plz]` table of {}. Keep only one of them",
+            path.display(),
+            manifest_path.display()
+        ),
+        (Some((_, config)), None) => Ok(config),
+        (None, Some(config)) => {
+            info!(
+                "using release-plz config of the `[workspace.metadata.release_plz]` table of {}",
+                manifest_path.display()
+            );
+            Ok(config)
+        }
+        (None, None) => {
+            info!("release-plz config file not found, using default configuration");
+            Ok(Config::default())
+        }
     }
 }
 
+/// Try to load the configuration from the `[workspace.metadata.release_plz]` table of the manifest.
+///
+/// Returns `Ok(None)` if the manifest or the table don't exist.
+fn load_metadata_config(manifest_path: &Path) -> anyhow::Result<Option<Config>> {
+    let contents = match read_to_string(manifest_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let manifest: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("invalid manifest {}", manifest_path.display()))?;
+    let Some(metadata_config) = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("metadata"))
+        .and_then(|metadata| metadata.get("release_plz"))
+    else {
+        return Ok(None);
+    };
+    let config = metadata_config.clone().try_into().with_context(|| {
+        format!(
+            "invalid `[workspace.metadata.release_plz]` table in {}",
+            manifest_path.display()
+        )
+    })?;
+    Ok(Some(config))
+}
+
 /// Try to load the configuration from the specified path.
 ///
 /// Returns `Ok(Some(config))` if the file is found and valid, `Ok(None)` if the file does not exist,
@@ -118,6 +191,23 @@ mod tests {
         assert!(result.contains("invalid config file"));
     }
 
+    #[test]
+    fn template_context_cannot_override_release_plz_variables() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            "[workspace]\ntemplate_context = {{ version = \"1.0.0\" }}"
+        )
+        .unwrap();
+
+        let config_path = ConfigPath {
+            path: Some(temp_file.path().to_path_buf()),
+        };
+
+        let result = format!("{:?}", config_path.load().unwrap_err());
+        assert!(result.contains("`version` can't be used"), "{result}");
+    }
+
     #[test]
     fn load_config_default_path_success() {
         let temp_dir = tempdir().unwrap();
@@ -130,6 +220,45 @@ mod tests {
         assert_eq!(config_path.load().unwrap(), Config::default());
     }
 
+    #[test]
+    fn load_config_from_cargo_toml_metadata() {
+        let temp_dir = tempdir().unwrap();
+        fs_err::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\n\
+            members = []\n\
+            [workspace.metadata.release_plz.workspace]\n\
+            allow_dirty = true\n",
+        )
+        .unwrap();
+
+        let config = load_default_config(temp_dir.path()).unwrap();
+        assert_eq!(config.workspace.allow_dirty, Some(true));
+
+        // The config file and the metadata are ambiguous.
+        fs_err::write(temp_dir.path().join("release-plz.toml"), "").unwrap();
+        let error = load_default_config(temp_dir.path()).unwrap_err();
+        assert!(
+            error.to_string().contains("configuration found both in"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn cargo_toml_without_metadata_uses_default() {
+        let temp_dir = tempdir().unwrap();
+        fs_err::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = []\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            load_default_config(temp_dir.path()).unwrap(),
+            Config::default()
+        );
+    }
+
     #[test]
     fn load_config_no_config_file_uses_default() {
         let temp_dir = tempdir().unwrap();
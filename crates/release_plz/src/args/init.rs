@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use clap::builder::PathBufValueParser;
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+
+use crate::init::PrConcurrency;
 
 use super::manifest_command::ManifestCommand;
 
@@ -14,6 +16,33 @@ pub struct Init {
     /// If set, don't check if the toml files contain `description` and `license` fields, which are mandatory for crates.io.
     #[arg(long)]
     pub no_toml_check: bool,
+    /// Label of the runner of the jobs of the generated GitHub Actions workflow,
+    /// e.g. the label of a self-hosted runner.
+    /// Can be specified multiple times to require all the labels.
+    /// If unspecified, `ubuntu-latest` is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub runs_on: Vec<String>,
+    /// Concurrency group of the release PR job of the generated GitHub Actions workflow.
+    /// It can contain GitHub Actions expressions, e.g. `release-plz-${{ github.workflow }}`.
+    /// If unspecified, `release-plz-${{ github.ref }}` is used.
+    #[arg(long, value_name = "GROUP", value_parser = NonEmptyStringValueParser::new())]
+    pub pr_concurrency_group: Option<String>,
+    /// Whether a new run of the release PR job cancels the one in progress.
+    /// If unspecified, `false` is used, so that a release PR update isn't interrupted.
+    #[arg(long)]
+    pub cancel_in_progress: Option<bool>,
+}
+
+impl Init {
+    pub fn pr_concurrency(&self) -> PrConcurrency {
+        let default = PrConcurrency::default();
+        PrConcurrency {
+            group: self.pr_concurrency_group.clone().unwrap_or(default.group),
+            cancel_in_progress: self
+                .cancel_in_progress
+                .unwrap_or(default.cancel_in_progress),
+        }
+    }
 }
 
 impl ManifestCommand for Init {
@@ -1,9 +1,11 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context as _;
 use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
 use release_plz_core::fs_utils::to_utf8_path;
 
+use super::config_path::ConfigPath;
+
 /// Command that acts on a manifest.
 pub trait ManifestCommand {
     fn optional_manifest(&self) -> Option<&Path>;
@@ -29,3 +31,23 @@ pub trait ManifestCommand {
         }).with_context(|| format!("Failed to read metadata from manifest at {manifest}"))
     }
 }
+
+/// Command that can process several workspaces in turn, one per `--manifest-path` argument.
+pub trait Workspaces: ManifestCommand {
+    /// Manifests of the workspaces to process.
+    /// `None` stands for the `Cargo.toml` of the current directory.
+    fn workspace_manifests(&self) -> Vec<Option<PathBuf>>;
+
+    /// Restrict the command to the workspace of `manifest`.
+    fn select_workspace(&mut self, manifest: Option<PathBuf>);
+
+    fn config_path(&self) -> &ConfigPath;
+}
+
+pub fn workspace_manifests(manifest_paths: &[PathBuf]) -> Vec<Option<PathBuf>> {
+    if manifest_paths.is_empty() {
+        vec![None]
+    } else {
+        manifest_paths.iter().cloned().map(Some).collect()
+    }
+}
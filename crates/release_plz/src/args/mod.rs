@@ -1,28 +1,39 @@
-mod config_path;
+pub(crate) mod changelog;
+pub(crate) mod check;
+mod check_updates;
+mod config_command;
+pub(crate) mod config_path;
 mod generate_completions;
 mod init;
 pub(crate) mod manifest_command;
 mod release;
+mod release_notes;
 mod release_pr;
 pub(crate) mod repo_command;
 mod set_version;
-mod update;
+pub(crate) mod update;
+mod yank;
+
+use std::path::PathBuf;
 
 use anyhow::bail;
 use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
 use cargo_utils::CARGO_TOML;
 use clap::{
     ValueEnum,
-    builder::{Styles, styling::AnsiColor},
+    builder::{PathBufValueParser, Styles, styling::AnsiColor},
 };
 use init::Init;
 use release_plz_core::fs_utils::current_directory;
 use set_version::SetVersion;
 use tracing::level_filters::LevelFilter;
 
+use crate::log::LogFormat;
+
 use self::{
-    generate_completions::GenerateCompletions, release::Release, release_pr::ReleasePr,
-    update::Update,
+    changelog::Changelog, check::Check, check_updates::CheckUpdates, config_command::ConfigCommand,
+    generate_completions::GenerateCompletions, release::Release, release_notes::ReleaseNotes,
+    release_pr::ReleasePr, update::UpdateCommand, yank::Yank,
 };
 
 const MAIN_COLOR: AnsiColor = AnsiColor::Red;
@@ -54,8 +65,40 @@ pub struct CliArgs {
         long,
         global = true,
         action = clap::ArgAction::Count,
+        conflicts_with = "quiet",
     )]
     verbose: u8,
+    /// Only print errors in logs.
+    ///
+    /// The `RELEASE_PLZ_LOG` environment variable takes precedence over this option.
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Format of the logs.
+    ///
+    /// Use `json` to print one JSON object per line, e.g. to process logs with
+    /// a log aggregator.
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        env = "RELEASE_PLZ_LOG_FORMAT"
+    )]
+    pub log_format: LogFormat,
+    /// Path of the cargo executable used to run cargo commands,
+    /// e.g. a wrapper script or the cargo of a specific toolchain.
+    ///
+    /// If unspecified, the `CARGO` environment variable is used.
+    /// If `CARGO` isn't set, `cargo` is used.
+    #[arg(long, global = true, value_parser = PathBufValueParser::new())]
+    pub cargo_bin: Option<PathBuf>,
+    /// Don't make network requests that release-plz can do without,
+    /// such as checking for a newer version of release-plz.
+    ///
+    /// Network requests needed by the command, e.g. to publish packages
+    /// or to open a release PR, are still made.
+    #[arg(long, global = true, env = "RELEASE_PLZ_OFFLINE")]
+    pub offline: bool,
 }
 
 impl CliArgs {
@@ -69,12 +112,21 @@ impl CliArgs {
         };
         Ok(level)
     }
+
+    /// Log level used if verbosity isn't set.
+    pub fn default_log_level(&self) -> LevelFilter {
+        if self.quiet {
+            LevelFilter::ERROR
+        } else {
+            LevelFilter::INFO
+        }
+    }
 }
 
 #[derive(clap::Subcommand, Debug)]
 pub enum Command {
     /// Update packages version and changelogs based on commit messages.
-    Update(Update),
+    Update(UpdateCommand),
     /// Create a Pull Request representing the next release.
     ///
     /// The Pull request updates the package version and generates a changelog entry for the new
@@ -89,10 +141,25 @@ pub enum Command {
     ///
     /// You can run this command in the CI on every commit in the main branch.
     Release(Release),
+    /// Print the changelog entries of the next release, without editing any file.
+    ///
+    /// Use it to review the release notes before opening a release PR.
+    /// In a workspace, the entry of each updated package is preceded by a heading
+    /// with the package name. Use `--package` to print the entry of a single package.
+    Changelog(Changelog),
+    /// Validate the release-plz setup without making changes.
+    ///
+    /// Check that the configuration is valid, that the packages can be published,
+    /// that the templates render, and that the git token (if provided) can authenticate
+    /// to the git forge.
+    /// Print the outcome of every check and exit with an error if any check fails.
+    Check(Check),
     /// Generate command autocompletions for various shells.
     GenerateCompletions(GenerateCompletions),
     /// Check if a newer version of release-plz is available.
-    CheckUpdates,
+    ///
+    /// With `--offline`, the check is skipped.
+    CheckUpdates(CheckUpdates),
     /// Write the JSON schema of the release-plz.toml configuration
     /// to .schema/latest.json
     GenerateSchema,
@@ -115,6 +182,27 @@ pub enum Command {
     /// Note that this command is meant to edit the versions of the packages of your workspace, not the
     /// version of your dependencies.
     SetVersion(SetVersion),
+    /// Inspect the release-plz configuration.
+    ///
+    /// Use `release-plz config --print` to print the configuration that release-plz
+    /// uses for every package of the workspace.
+    /// Packages without a `[[package]]` section use the `[workspace]` configuration.
+    Config(ConfigCommand),
+    /// Yank a version of a package from the cargo registry.
+    ///
+    /// Specify the version with the syntax `<package_name>@<version>`.
+    /// E.g. `release-plz yank --package my-crate@1.2.3`.
+    /// Use `--undo` to un-yank the version.
+    ///
+    /// Git tags and git releases aren't modified.
+    Yank(Yank),
+    /// Print the release notes of a package for a range of commits, without editing any file.
+    ///
+    /// Only the commits between `--from` and `--to` that changed files of the package
+    /// are considered. The notes are rendered with the changelog configuration,
+    /// regardless of the next version of the package.
+    /// E.g. `release-plz release-notes --package my-crate --from v1.0.0 --to HEAD`.
+    ReleaseNotes(ReleaseNotes),
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
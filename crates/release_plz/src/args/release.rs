@@ -4,14 +4,17 @@ use clap::{
     ValueEnum,
     builder::{NonEmptyStringValueParser, PathBufValueParser},
 };
-use release_plz_core::{GitForge, GitHub, GitLab, Gitea, ReleaseRequest};
+use release_plz_core::{Bitbucket, CombinedGitRelease, GitForge, GitLab, Gitea, ReleaseRequest};
 use secrecy::SecretString;
 
 use crate::config::Config;
 
 use super::{
-    OutputType, config_path::ConfigPath, manifest_command::ManifestCommand,
-    repo_command::RepoCommand,
+    OutputType,
+    config_path::ConfigPath,
+    manifest_command::{ManifestCommand, Workspaces, workspace_manifests},
+    repo_command::{RepoCommand, github_client},
+    update::git_cliff_config,
 };
 
 #[derive(clap::Parser, Debug)]
@@ -19,8 +22,9 @@ pub struct Release {
     /// Path to the Cargo.toml of the project you want to release.
     /// If not provided, release-plz will use the Cargo.toml of the current directory.
     /// Both Cargo workspaces and single packages are supported.
+    /// Repeat the argument to process several workspaces of the same repository in turn.
     #[arg(long, value_parser = PathBufValueParser::new(), alias = "project-manifest")]
-    manifest_path: Option<PathBuf>,
+    manifest_path: Vec<PathBuf>,
 
     /// Registry where you want to publish the packages.
     /// The registry name needs to be present in the Cargo config.
@@ -35,10 +39,58 @@ pub struct Release {
     #[arg(long, value_parser = NonEmptyStringValueParser::new())]
     token: Option<String>,
 
+    /// Command that prints the token used to publish to the cargo registry.
+    /// Repeat the argument once per program argument, e.g.
+    /// `--registry-token-command vault --registry-token-command read --registry-token-command=-field=token`.
+    /// Overrides the `registry_token_command` field of the config file.
+    #[arg(
+        long,
+        conflicts_with = "token",
+        allow_hyphen_values = true,
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    registry_token_command: Vec<String>,
+
+    /// Protocol used to read the registry index when checking if a package is published.
+    /// With `sparse`, the git index of crates.io is replaced with its sparse index,
+    /// and release-plz fails if a registry only offers a git index.
+    /// With `auto`, the protocol of the index url is used.
+    #[arg(long, value_enum, default_value_t = IndexProtocol::Auto)]
+    index_protocol: IndexProtocol,
+
     /// Perform all checks without uploading.
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Run the `verify_command` of the packages even if `--dry-run` is set.
+    #[arg(long, requires = "dry_run")]
+    pub dry_run_verify: bool,
+
+    /// Update the name and the body of the git releases that already exist,
+    /// e.g. to regenerate them after fixing the changelog.
+    /// Release-plz edits the existing git releases: it doesn't delete them,
+    /// it doesn't edit git tags and it doesn't publish packages again.
+    #[arg(long)]
+    pub force_release_body: bool,
+
+    /// List the tags of the remote repository with a single request and skip the packages
+    /// whose git tag already exists there, before checking if they are published.
+    /// Useful to speed up re-runs in large workspaces where most packages are already released.
+    #[arg(long)]
+    pub skip_existing_tags: bool,
+
+    /// Keep releasing the other packages when the release of a package fails.
+    /// The packages that depend on a failed package are skipped.
+    /// At the end, release-plz fails listing the packages that weren't released.
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Don't create git tags, even if they are enabled in the configuration.
+    /// Git releases are created only for the tags that already exist.
+    /// Useful to re-publish packages to the registry without touching the tags.
+    #[arg(long)]
+    pub no_tag: bool,
+
     /// Don't verify the contents by building them.
     /// When you pass this flag, `release-plz` adds the `--no-verify` flag to `cargo publish`.
     #[arg(long)]
@@ -55,7 +107,7 @@ pub struct Release {
     #[arg(long, value_parser = NonEmptyStringValueParser::new())]
     pub repo_url: Option<String>,
 
-    /// Git token used to publish the GitHub/Gitea/GitLab release.
+    /// Git token used to publish the GitHub/Gitea/GitLab release or the Bitbucket tag.
     #[arg(long, value_parser = NonEmptyStringValueParser::new(), env, hide_env_values=true)]
     pub git_token: Option<String>,
 
@@ -63,6 +115,32 @@ pub struct Release {
     #[arg(long, visible_alias = "backend", value_enum, default_value_t = ReleaseGitForgeKind::Github)]
     forge: ReleaseGitForgeKind,
 
+    /// Base URL of the API of the git forge.
+    /// Only used with the GitHub forge.
+    /// If unspecified, it's derived from the repository url:
+    /// `https://api.github.com` for github.com, `https://<host>/api/v3` for GitHub Enterprise Server.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub forge_api_url: Option<String>,
+
+    /// Name of the tagger of the git tags.
+    /// Overrides the `git_author_name` field of the config file.
+    /// If unspecified, the `user.name` of the git configuration is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub git_author_name: Option<String>,
+
+    /// Email of the tagger of the git tags.
+    /// Overrides the `git_author_email` field of the config file.
+    /// If unspecified, the `user.email` of the git configuration is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub git_author_email: Option<String>,
+
+    /// Branch where release PRs are merged.
+    /// Used to check whether the last commit of the merged release PR is in the history.
+    /// Overrides the `base_branch` field of the config file.
+    /// If unspecified, the current branch is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub base_branch: Option<String>,
+
     /// Path to the release-plz config file.
     #[command(flatten)]
     pub config: ConfigPath,
@@ -71,6 +149,27 @@ pub struct Release {
     /// released packages.
     #[arg(short, long, value_enum)]
     pub output: Option<OutputType>,
+
+    /// Write the output to this file instead of stdout.
+    /// Parent directories are created if they don't exist.
+    /// Requires `--output`.
+    #[arg(long, requires = "output", value_parser = PathBufValueParser::new())]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexProtocol {
+    Auto,
+    Sparse,
+}
+
+impl From<IndexProtocol> for release_plz_core::IndexProtocol {
+    fn from(value: IndexProtocol) -> Self {
+        match value {
+            IndexProtocol::Auto => Self::Auto,
+            IndexProtocol::Sparse => Self::Sparse,
+        }
+    }
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
@@ -81,11 +180,13 @@ pub enum ReleaseGitForgeKind {
     Gitea,
     #[value(name = "gitlab")]
     Gitlab,
+    #[value(name = "bitbucket")]
+    Bitbucket,
 }
 
 impl Release {
     pub fn release_request(
-        self,
+        &self,
         config: &Config,
         metadata: cargo_metadata::Metadata,
     ) -> anyhow::Result<ReleaseRequest> {
@@ -95,27 +196,47 @@ impl Release {
             let release = release_plz_core::GitRelease {
                 forge: match self.forge {
                     ReleaseGitForgeKind::Gitea => GitForge::Gitea(Gitea::new(repo_url, git_token)?),
-                    ReleaseGitForgeKind::Github => {
-                        GitForge::Github(GitHub::new(repo_url.owner, repo_url.name, git_token))
-                    }
+                    ReleaseGitForgeKind::Github => GitForge::Github(github_client(
+                        repo_url,
+                        git_token,
+                        self.forge_api_url.as_deref(),
+                    )?),
                     ReleaseGitForgeKind::Gitlab => {
                         GitForge::Gitlab(GitLab::new(repo_url, git_token)?)
                     }
+                    ReleaseGitForgeKind::Bitbucket => {
+                        GitForge::Bitbucket(Bitbucket::new(repo_url, git_token)?)
+                    }
                 },
             };
             Some(release)
         } else {
             None
         };
-        let mut req = ReleaseRequest::new(metadata).with_dry_run(self.dry_run);
-
-        if let Some(registry) = self.registry {
+        let mut req = ReleaseRequest::new(metadata)
+            .with_dry_run(self.dry_run)
+            .with_dry_run_verify(self.dry_run_verify)
+            .with_force_release_body(self.force_release_body)
+            .with_skip_existing_tags(self.skip_existing_tags)
+            .with_keep_going(self.keep_going)
+            .with_no_tag(self.no_tag)
+            .with_index_protocol(self.index_protocol.into());
+
+        if let Some(registry) = self.registry.clone() {
             req = req.with_registry(registry);
         }
-        if let Some(token) = self.token {
+        if let Some(token) = self.token.clone() {
             req = req.with_token(SecretString::from(token));
         }
-        if let Some(repo_url) = self.repo_url {
+        let registry_token_command = if self.registry_token_command.is_empty() {
+            config.workspace.registry_token_command.clone()
+        } else {
+            Some(self.registry_token_command.clone())
+        };
+        if let Some(registry_token_command) = registry_token_command {
+            req = req.with_registry_token_command(registry_token_command);
+        }
+        if let Some(repo_url) = self.repo_url.clone() {
             req = req.with_repo_url(repo_url);
         }
         if let Some(git_release) = git_release {
@@ -124,12 +245,62 @@ impl Release {
         if let Some(release_always) = config.workspace.release_always {
             req = req.with_release_always(release_always);
         }
+        req = req.with_template_context(config.workspace.template_context.clone())?;
+        if let Some(comment_on_release) = config.workspace.comment_on_release {
+            req = req.with_comment_on_release(comment_on_release);
+        }
+        if let Some(single_tag) = config.workspace.single_tag {
+            req = req.with_single_tag(single_tag);
+        }
+        if config.workspace.combined_git_release == Some(true) {
+            let mut combined_git_release = CombinedGitRelease::default()
+                .with_only(config.workspace.combined_git_release_only == Some(true));
+            if let Some(tag_name) = &config.workspace.combined_git_release_tag_name {
+                combined_git_release = combined_git_release.with_tag_name_template(tag_name);
+            }
+            req = req.with_combined_git_release(combined_git_release);
+        }
+        if let Some(verify_published_checksum) = config.workspace.verify_published_checksum {
+            req = req.with_verify_published_checksum(verify_published_checksum);
+        }
+        if let Some(skip_publish_wait) = config.workspace.skip_publish_wait {
+            req = req.with_skip_publish_wait(skip_publish_wait);
+        }
+        if let Some(fetch_contributors) = config.workspace.fetch_contributors {
+            req = req.with_fetch_contributors(fetch_contributors);
+        }
+        if let Some(changelog_filenames) = &config.workspace.changelog_filenames {
+            req = req.with_changelog_filenames(changelog_filenames.clone());
+        }
+        if let Some(output_format) = config.workspace.changelog_output_format {
+            let output_format = output_format.into();
+            if output_format == release_plz_core::ChangelogOutputFormat::Json {
+                // The git release body is rendered from the JSON changelog.
+                req = req.with_changelog_config(git_cliff_config(None, config, None)?);
+            }
+            req = req.with_changelog_output_format(output_format);
+        }
 
         req = req.with_publish_timeout(config.workspace.publish_timeout()?);
 
         req = config.fill_release_config(self.allow_dirty, self.no_verify, req)?;
 
         req = req.with_branch_prefix(config.workspace.pr_branch_prefix.clone());
+        if let Some(regex) = &config.workspace.release_pr_branch_regex {
+            req = req.with_release_pr_branch_regex(regex)?;
+        }
+
+        req = req.with_base_branch(
+            self.base_branch
+                .clone()
+                .or_else(|| config.workspace.base_branch.clone()),
+        );
+
+        req = req.with_git_identity(
+            config
+                .workspace
+                .git_identity(self.git_author_name.clone(), self.git_author_email.clone()),
+        );
 
         req.check_publish_fields()?;
 
@@ -145,7 +316,21 @@ impl RepoCommand for Release {
 
 impl ManifestCommand for Release {
     fn optional_manifest(&self) -> Option<&Path> {
-        self.manifest_path.as_deref()
+        self.manifest_path.first().map(PathBuf::as_path)
+    }
+}
+
+impl Workspaces for Release {
+    fn workspace_manifests(&self) -> Vec<Option<PathBuf>> {
+        workspace_manifests(&self.manifest_path)
+    }
+
+    fn select_workspace(&mut self, manifest: Option<PathBuf>) {
+        self.manifest_path = manifest.into_iter().collect();
+    }
+
+    fn config_path(&self) -> &ConfigPath {
+        &self.config
     }
 }
 
@@ -185,7 +370,7 @@ mod tests {
             publish_no_verify = true
 
             [[package]]
-            name = "aaa"
+            name = "git_cmd"
             publish_allow_dirty = true
             publish_features = ["a", "b", "c"]
         "#;
@@ -195,27 +380,59 @@ mod tests {
         let actual_request = release_args
             .release_request(&config, fake_metadata())
             .unwrap();
-        assert!(actual_request.allow_dirty("aaa"));
-        assert!(actual_request.no_verify("aaa"));
-        assert_eq!(actual_request.features("aaa"), &["a", "b", "c"]);
+        assert!(actual_request.allow_dirty("git_cmd"));
+        assert!(actual_request.no_verify("git_cmd"));
+        assert_eq!(actual_request.features("git_cmd"), &["a", "b", "c"]);
     }
 
     fn default_args() -> Release {
         Release {
             allow_dirty: false,
             no_verify: false,
-            manifest_path: None,
+            manifest_path: vec![],
             registry: None,
             token: None,
+            registry_token_command: vec![],
+            index_protocol: IndexProtocol::Auto,
             dry_run: false,
+            dry_run_verify: false,
+            force_release_body: false,
+            skip_existing_tags: false,
+            keep_going: false,
+            no_tag: false,
             repo_url: None,
             git_token: None,
             forge: ReleaseGitForgeKind::Github,
+            forge_api_url: None,
+            git_author_name: None,
+            git_author_email: None,
+            base_branch: None,
             config: ConfigPath::default(),
             output: None,
+            output_file: None,
         }
     }
 
+    #[test]
+    fn registry_token_command_arguments_can_contain_spaces() {
+        use clap::Parser as _;
+
+        let release_args = Release::try_parse_from([
+            "release",
+            "--registry-token-command",
+            "sh",
+            "--registry-token-command",
+            "-c",
+            "--registry-token-command",
+            "vault read -field=token secret/cargo",
+        ])
+        .unwrap();
+        assert_eq!(
+            release_args.registry_token_command,
+            ["sh", "-c", "vault read -field=token secret/cargo"]
+        );
+    }
+
     #[test]
     fn default_config_is_converted_to_default_release_request() {
         let release_args = default_args();
@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+use release_plz_core::ReleaseNotesRequest;
+
+use crate::config::Config;
+
+use super::{config_path::ConfigPath, manifest_command::ManifestCommand, update::git_cliff_config};
+
+#[derive(clap::Parser, Debug)]
+pub struct ReleaseNotes {
+    /// Package to generate the release notes for.
+    #[arg(short, long, value_parser = NonEmptyStringValueParser::new())]
+    package: String,
+
+    /// Git ref where the range of commits starts, e.g. `v1.0.0`.
+    /// Commits reachable from this ref aren't part of the release notes.
+    #[arg(long, alias = "from-ref", value_parser = NonEmptyStringValueParser::new())]
+    from: String,
+
+    /// Git ref where the range of commits ends.
+    #[arg(long, alias = "to-ref", default_value = "HEAD", value_parser = NonEmptyStringValueParser::new())]
+    to: String,
+
+    /// Path to the Cargo.toml of the project containing the package.
+    /// If not provided, release-plz will use the Cargo.toml of the current directory.
+    #[arg(long, value_parser = PathBufValueParser::new())]
+    manifest_path: Option<PathBuf>,
+
+    /// Path to the git-cliff configuration file.
+    /// If not provided, `dirs::config_dir()/git-cliff/cliff.toml` is used if present.
+    #[arg(
+        long,
+        env = "GIT_CLIFF_CONFIG",
+        value_name = "PATH",
+        value_parser = PathBufValueParser::new()
+    )]
+    changelog_config: Option<PathBuf>,
+
+    /// Path to the release-plz config file.
+    #[command(flatten)]
+    pub config: ConfigPath,
+}
+
+impl ManifestCommand for ReleaseNotes {
+    fn optional_manifest(&self) -> Option<&Path> {
+        self.manifest_path.as_deref()
+    }
+}
+
+impl ReleaseNotes {
+    pub fn release_notes_request(
+        &self,
+        config: &Config,
+        metadata: cargo_metadata::Metadata,
+    ) -> anyhow::Result<ReleaseNotesRequest> {
+        let changelog_config = git_cliff_config(self.changelog_config.as_deref(), config, None)?;
+        let request = ReleaseNotesRequest::new(metadata, &self.package, &self.from, &self.to)
+            .with_changelog_config(changelog_config)
+            .with_template_context(config.workspace.template_context.clone());
+        Ok(request)
+    }
+}
@@ -1,6 +1,9 @@
-use release_plz_core::ReleasePrRequest;
+use std::path::PathBuf;
 
-use crate::config::Config;
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+use release_plz_core::{LabelStyle, ReleasePrRequest};
+
+use crate::config::{Config, PrLabel};
 
 use super::{OutputType, update::Update};
 
@@ -8,10 +11,30 @@ use super::{OutputType, update::Update};
 pub struct ReleasePr {
     #[command(flatten)]
     pub update: Update,
+    /// Name of the author and committer of the release commit.
+    /// Overrides the `git_author_name` field of the config file.
+    /// If unspecified, the `user.name` of the git configuration is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub git_author_name: Option<String>,
+    /// Email of the author and committer of the release commit.
+    /// Overrides the `git_author_email` field of the config file.
+    /// If unspecified, the `user.email` of the git configuration is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub git_author_email: Option<String>,
+    /// Branch the release PR is opened against.
+    /// Overrides the `base_branch` field of the config file.
+    /// If unspecified, the current branch is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub base_branch: Option<String>,
     /// Output format. If specified, prints the branch, URL and number of
     /// the release PR, if any.
     #[arg(short, long, value_enum)]
     pub output: Option<OutputType>,
+    /// Write the output to this file instead of stdout.
+    /// Parent directories are created if they don't exist.
+    /// Requires `--output`.
+    #[arg(long, requires = "output", value_parser = PathBufValueParser::new())]
+    pub output_file: Option<PathBuf>,
 }
 
 impl ReleasePr {
@@ -21,17 +44,57 @@ impl ReleasePr {
         cargo_metadata: cargo_metadata::Metadata,
     ) -> anyhow::Result<ReleasePrRequest> {
         let pr_branch_prefix = config.workspace.pr_branch_prefix.clone();
-        let pr_name = config.workspace.pr_name.clone();
-        let pr_body = config.workspace.pr_body.clone();
-        let pr_labels = config.workspace.pr_labels.clone();
+        let pr_name = config.workspace.pr_name_template()?;
+        let pr_body = config.workspace.pr_body_template()?;
+        let pr_body_footer = config
+            .workspace
+            .pr_body_footer
+            .clone()
+            .map(Into::into)
+            .unwrap_or_default();
+        let pr_labels = config
+            .workspace
+            .pr_labels
+            .iter()
+            .map(|l| l.name().to_string())
+            .collect();
+        let pr_label_styles = config
+            .workspace
+            .pr_labels
+            .iter()
+            .filter_map(|l| match l {
+                PrLabel::Name(_) => None,
+                PrLabel::Styled(label) => Some((
+                    label.name.clone(),
+                    LabelStyle {
+                        color: label.color.clone(),
+                        description: label.description.clone(),
+                    },
+                )),
+            })
+            .collect();
         let pr_draft = config.workspace.pr_draft;
+        let pr_head_repo = config.workspace.pr_head_repo.clone();
+        let base_branch = self
+            .base_branch
+            .clone()
+            .or_else(|| config.workspace.base_branch.clone());
+        let git_identity = config
+            .workspace
+            .git_identity(self.git_author_name.clone(), self.git_author_email.clone());
         let update_request = self.update.update_request(config, cargo_metadata)?;
         let request = ReleasePrRequest::new(update_request)
             .mark_as_draft(pr_draft)
             .with_labels(pr_labels)
+            .with_label_styles(pr_label_styles)
             .with_branch_prefix(pr_branch_prefix)
+            .with_base_branch(base_branch)
+            .with_head_repo(pr_head_repo)
             .with_pr_name_template(pr_name)
-            .with_pr_body_template(pr_body);
+            .with_pr_body_template(pr_body)
+            .with_pr_body_footer(pr_body_footer)
+            .with_git_identity(git_identity)
+            .with_template_context(config.workspace.template_context.clone())?;
         Ok(request)
     }
 }
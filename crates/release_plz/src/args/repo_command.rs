@@ -1,5 +1,7 @@
+use anyhow::Context as _;
 use git_cmd::Repo;
-use release_plz_core::RepoUrl;
+use release_plz_core::{GitHub, RepoUrl};
+use secrecy::SecretString;
 
 use crate::config::Config;
 
@@ -27,3 +29,21 @@ pub trait RepoCommand: ManifestCommand {
             .or_else(|| config.workspace.repo_url.as_ref().map(|u| u.as_str()))
     }
 }
+
+/// Create a GitHub client for `repo`.
+/// If `api_url` isn't specified, it's derived from the host of `repo`, so that
+/// both github.com and GitHub Enterprise Server are supported.
+pub fn github_client(
+    repo: RepoUrl,
+    token: SecretString,
+    api_url: Option<&str>,
+) -> anyhow::Result<GitHub> {
+    let api_url = match api_url {
+        Some(api_url) => api_url.to_string(),
+        None => repo.github_api_url(),
+    };
+    let api_url = api_url
+        .parse()
+        .with_context(|| format!("invalid forge API url {api_url}"))?;
+    Ok(GitHub::new(repo.owner, repo.name, token).with_base_url(api_url))
+}
@@ -9,12 +9,17 @@ use release_plz_core::set_version::{SetVersionRequest, SetVersionSpec, VersionCh
 
 use crate::config::Config;
 
-use super::{config_path::ConfigPath, manifest_command::ManifestCommand};
+use super::{OutputType, config_path::ConfigPath, manifest_command::ManifestCommand};
 
 #[derive(clap::Parser, Debug)]
 pub struct SetVersion {
     /// New version of the package you want to update. Format: `<package_name>@<version-req>`.
     pub versions: Vec<String>,
+    /// New version of the `[workspace.package]` table.
+    /// The packages that inherit the workspace version follow it.
+    /// The other packages are left untouched, unless you specify them with `<package_name>@<version>`.
+    #[arg(long, value_name = "VERSION")]
+    pub workspace: Option<Version>,
     /// Path to the Cargo.toml of the project you want to update.
     /// If not provided, release-plz will use the Cargo.toml of the current directory.
     /// Both Cargo workspaces and single packages are supported.
@@ -24,10 +29,27 @@ pub struct SetVersion {
     /// Path to the release-plz config file.
     #[command(flatten)]
     pub config: ConfigPath,
+
+    /// Output format. If specified, prints the old and new version and the
+    /// rewritten manifests of the updated packages.
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputType>,
+
+    /// Write the output to this file instead of stdout.
+    /// Parent directories are created if they don't exist.
+    /// Requires `--output`.
+    #[arg(long, requires = "output", value_parser = PathBufValueParser::new())]
+    pub output_file: Option<PathBuf>,
+
+    /// Allow editing files with uncommitted changes.
+    /// By default, release-plz refuses to edit manifests, changelogs and
+    /// `Cargo.lock` if they contain uncommitted changes.
+    #[arg(long)]
+    pub allow_dirty: bool,
 }
 
 impl SetVersion {
-    fn parse_versions(self) -> anyhow::Result<SetVersionSpec> {
+    fn parse_versions(&self) -> anyhow::Result<SetVersionSpec> {
         let is_single_package = self.versions.len() == 1 && !self.versions[0].contains('@');
         if is_single_package {
             let version = Version::parse(&self.versions[0])?;
@@ -38,7 +60,7 @@ impl SetVersion {
         }
     }
 
-    fn parse_workspace_versions(self) -> anyhow::Result<BTreeMap<String, VersionChange>> {
+    fn parse_workspace_versions(&self) -> anyhow::Result<BTreeMap<String, VersionChange>> {
         self
             .versions
             .iter()
@@ -57,8 +79,20 @@ impl SetVersion {
     /// Get [`SetVersionRequest`]
     pub fn set_version_request(self, config: &Config) -> anyhow::Result<SetVersionRequest> {
         let cargo_metadata = self.cargo_metadata()?;
+        let allow_dirty = self.allow_dirty || config.workspace.allow_dirty == Some(true);
         let version_changes = self.parse_versions()?;
-        let mut request = SetVersionRequest::new(version_changes, cargo_metadata)?;
+        let mut request = SetVersionRequest::new(version_changes, cargo_metadata)?
+            .with_allow_dirty(allow_dirty)
+            .with_update_cargo_lock(config.workspace.update_cargo_lock != Some(false));
+        if let Some(workspace_version) = self.workspace {
+            request = request.with_workspace_version(workspace_version)?;
+        }
+        if let Some(changelog_filenames) = &config.workspace.changelog_filenames {
+            request = request.with_changelog_filenames(changelog_filenames.clone());
+        }
+        if let Some(format_manifests_command) = &config.workspace.format_manifests_command {
+            request = request.with_format_manifests_command(format_manifests_command.clone());
+        }
         config.fill_set_version_config(&mut request)?;
         Ok(request)
     }
@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
 
 use anyhow::Context;
 use cargo_metadata::camino::Utf8Path;
@@ -9,27 +12,36 @@ use clap::{
 };
 use git_cliff_core::config::Config as GitCliffConfig;
 use release_plz_core::{
-    ChangelogRequest, GitForge, GitHub, GitLab, Gitea, RepoUrl, fs_utils::to_utf8_path,
-    update_request::UpdateRequest,
+    Bitbucket, ChangelogRequest, GitForge, GitLab, Gitea, RepoUrl,
+    fs_utils::to_utf8_path,
+    update_request::{DependenciesUpdate, UpdateRequest},
 };
 use secrecy::SecretString;
 
 use crate::{changelog_config, config::Config};
 
 use super::{
-    config_path::ConfigPath, manifest_command::ManifestCommand, repo_command::RepoCommand,
+    OutputType,
+    config_path::ConfigPath,
+    manifest_command::{ManifestCommand, Workspaces, workspace_manifests},
+    repo_command::{RepoCommand, github_client},
 };
 
+/// Environment variable used by reproducible builds to pin timestamps.
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>.
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
 /// Update your project locally, without opening a PR.
 /// If `repo_url` contains a GitHub URL, release-plz uses it to add a release
 /// link in the changelog.
-#[derive(clap::Parser, Debug)]
+#[derive(clap::Parser, Debug, Default)]
 pub struct Update {
     /// Path to the Cargo.toml of the project you want to update.
     /// If not provided, release-plz will use the Cargo.toml of the current directory.
     /// Both Cargo workspaces and single packages are supported.
+    /// Repeat the argument to process several workspaces of the same repository in turn.
     #[arg(long, value_parser = PathBufValueParser::new(), alias = "project-manifest")]
-    manifest_path: Option<PathBuf>,
+    pub(super) manifest_path: Vec<PathBuf>,
 
     /// Path to the Cargo.toml contained in the released version of the project you want to update.
     /// If not provided, the packages of your project will be compared with the
@@ -39,7 +51,7 @@ pub struct Update {
     /// For example, it could be the path to the project with a `git checkout` on its latest tag.
     /// The git history of this project should be behind the one of the project you want to update.
     #[arg(long, value_parser = PathBufValueParser::new(), alias = "registry-project-manifest")]
-    registry_manifest_path: Option<PathBuf>,
+    pub(super) registry_manifest_path: Option<PathBuf>,
 
     /// Package to update. Use it when you want to update a single package rather than all the
     /// packages contained in the workspace.
@@ -48,19 +60,21 @@ pub struct Update {
         long,
         value_parser = NonEmptyStringValueParser::new()
     )]
-    package: Option<String>,
+    pub(super) package: Option<String>,
 
     /// Don't create/update changelog.
     #[arg(long, conflicts_with("release_date"))]
-    no_changelog: bool,
+    pub(super) no_changelog: bool,
 
-    /// Date of the release. Format: %Y-%m-%d. It defaults to current Utc date.
+    /// Date of the release. Format: %Y-%m-%d.
+    /// If unspecified, the date of the `SOURCE_DATE_EPOCH` environment variable is used.
+    /// If `SOURCE_DATE_EPOCH` isn't set, it defaults to current Utc date.
     #[arg(
         long,
         conflicts_with("no_changelog"),
         value_parser = NonEmptyStringValueParser::new()
     )]
-    release_date: Option<String>,
+    pub(super) release_date: Option<String>,
 
     /// Registry where the packages are stored.
     /// The registry name needs to be present in the Cargo config.
@@ -71,12 +85,21 @@ pub struct Update {
         conflicts_with("registry_manifest_path"),
         value_parser = NonEmptyStringValueParser::new()
     )]
-    registry: Option<String>,
+    pub(super) registry: Option<String>,
 
     /// Update all the dependencies in the Cargo.lock file by running `cargo update`.
     /// If this flag is not specified, only update the workspace packages by running `cargo update --workspace`.
     #[arg(short, long)]
-    update_deps: bool,
+    pub(super) update_deps: bool,
+
+    /// Only update this dependency in the Cargo.lock file, by running `cargo update -p <PACKAGE>`.
+    /// Can be specified multiple times.
+    #[arg(
+        long,
+        conflicts_with("update_deps"),
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    pub(super) update_deps_package: Vec<String>,
 
     /// Path to the git-cliff configuration file.
     /// If not provided, `dirs::config_dir()/git-cliff/cliff.toml` is used if present.
@@ -87,18 +110,18 @@ pub struct Update {
         conflicts_with("no_changelog"),
         value_parser = PathBufValueParser::new()
     )]
-    changelog_config: Option<PathBuf>,
+    pub(super) changelog_config: Option<PathBuf>,
 
     /// Allow dirty working directories to be updated.
     /// The uncommitted changes will be part of the update.
     #[arg(long)]
-    allow_dirty: bool,
+    pub(super) allow_dirty: bool,
 
     /// GitHub/Gitea repository url where your project is hosted.
     /// It is used to generate the changelog release link.
     /// It defaults to the url of the default remote.
     #[arg(long, value_parser = NonEmptyStringValueParser::new())]
-    repo_url: Option<String>,
+    pub(super) repo_url: Option<String>,
 
     /// Path to the release-plz config file.
     #[command(flatten)]
@@ -110,21 +133,72 @@ pub struct Update {
 
     /// Kind of git host where your project is hosted.
     #[arg(long, visible_alias = "backend", value_enum, default_value_t = GitForgeKind::Github)]
-    forge: GitForgeKind,
+    pub(super) forge: GitForgeKind,
+
+    /// Base URL of the API of the git forge.
+    /// Only used with the GitHub forge.
+    /// If unspecified, it's derived from the repository url:
+    /// `https://api.github.com` for github.com, `https://<host>/api/v3` for GitHub Enterprise Server.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    pub(super) forge_api_url: Option<String>,
+
     /// Maximum number of commits to analyze when the package hasn't been published yet.
     /// Default: 1000.
     #[arg(long)]
-    max_analyze_commits: Option<u32>,
+    pub(super) max_analyze_commits: Option<u32>,
+
+    /// Maximum number of packages to process in parallel, e.g. when running cargo-semver-checks.
+    /// Use it to limit the resources used by release-plz on shared CI runners.
+    /// Default: number of CPUs.
+    #[arg(short, long)]
+    pub(super) jobs: Option<NonZeroUsize>,
 }
 
-#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+/// Arguments of the `update` command.
+#[derive(clap::Parser, Debug)]
+pub struct UpdateCommand {
+    #[command(flatten)]
+    pub update: Update,
+
+    /// Write the files that the update would change into this directory,
+    /// mirroring the layout of the repository, instead of modifying the project.
+    /// Use it to review the proposed manifests and changelogs.
+    #[arg(long, value_name = "PATH", value_parser = PathBufValueParser::new())]
+    output_dir: Option<PathBuf>,
+
+    /// Output format. If specified, prints the updated packages.
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputType>,
+
+    /// Write the output to this file instead of stdout.
+    /// Parent directories are created if they don't exist.
+    /// Requires `--output`.
+    #[arg(long, requires = "output", value_parser = PathBufValueParser::new())]
+    pub output_file: Option<PathBuf>,
+
+    /// Include the commits that justify the update of each package in the output.
+    /// Requires `--output`.
+    #[arg(long, requires = "output")]
+    pub verbose_output: bool,
+}
+
+impl UpdateCommand {
+    pub fn output_dir(&self) -> anyhow::Result<Option<&Utf8Path>> {
+        self.output_dir.as_deref().map(to_utf8_path).transpose()
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum GitForgeKind {
+    #[default]
     #[value(name = "github")]
     Github,
     #[value(name = "gitea")]
     Gitea,
     #[value(name = "gitlab")]
     Gitlab,
+    #[value(name = "bitbucket")]
+    Bitbucket,
 }
 
 impl RepoCommand for Update {
@@ -135,7 +209,21 @@ impl RepoCommand for Update {
 
 impl ManifestCommand for Update {
     fn optional_manifest(&self) -> Option<&Path> {
-        self.manifest_path.as_deref()
+        self.manifest_path.first().map(PathBuf::as_path)
+    }
+}
+
+impl Workspaces for Update {
+    fn workspace_manifests(&self) -> Vec<Option<PathBuf>> {
+        workspace_manifests(&self.manifest_path)
+    }
+
+    fn select_workspace(&mut self, manifest: Option<PathBuf>) {
+        self.manifest_path = manifest.into_iter().collect();
+    }
+
+    fn config_path(&self) -> &ConfigPath {
+        &self.config
     }
 }
 
@@ -148,18 +236,30 @@ impl Update {
         Ok(Some(match self.forge {
             GitForgeKind::Github => {
                 anyhow::ensure!(
-                    repo.is_on_github(),
+                    repo.is_on_github() || self.forge_api_url.is_some(),
                     "Can't create PR: the repository is not hosted in GitHub. Please select a different forge."
                 );
-                GitForge::Github(GitHub::new(repo.owner, repo.name, token))
+                GitForge::Github(github_client(repo, token, self.forge_api_url.as_deref())?)
             }
             GitForgeKind::Gitea => GitForge::Gitea(Gitea::new(repo, token)?),
             GitForgeKind::Gitlab => GitForge::Gitlab(GitLab::new(repo, token)?),
+            GitForgeKind::Bitbucket => GitForge::Bitbucket(Bitbucket::new(repo, token)?),
         }))
     }
 
-    fn dependencies_update(&self, config: &Config) -> bool {
-        self.update_deps || config.workspace.dependencies_update == Some(true)
+    fn dependencies_update(&self, config: &Config) -> DependenciesUpdate {
+        if self.update_deps {
+            DependenciesUpdate::All
+        } else if !self.update_deps_package.is_empty() {
+            DependenciesUpdate::Packages(self.update_deps_package.clone())
+        } else {
+            config
+                .workspace
+                .dependencies_update
+                .clone()
+                .map(Into::into)
+                .unwrap_or_default()
+        }
     }
 
     fn allow_dirty(&self, config: &Config) -> bool {
@@ -183,8 +283,23 @@ impl Update {
                 format!("Cannot find file {project_manifest:?}. Make sure you are inside a rust project or that --manifest-path points to a valid Cargo.toml file.")
             })?
             .with_dependencies_update(self.dependencies_update(config))
+            .with_update_cargo_lock(config.workspace.update_cargo_lock != Some(false))
             .with_max_analyze_commits(self.max_analyze_commits(config))
-            .with_allow_dirty(self.allow_dirty(config));
+            .with_infer_baseline_from_lock(config.workspace.infer_baseline_from_lock)
+            .with_changelog_exclude_merge_commits(config.workspace.changelog_exclude_merge_commits)
+            .with_fetch_contributors(config.workspace.fetch_contributors != Some(false))
+            .with_allow_dirty(self.allow_dirty(config))
+            .with_single_tag(config.workspace.single_tag == Some(true))
+            .with_dependency_update_message_template(
+                config.workspace.dependency_update_message_template.clone(),
+            )
+            .with_jobs(self.jobs);
+        if let Some(changelog_filenames) = &config.workspace.changelog_filenames {
+            update = update.with_changelog_filenames(changelog_filenames.clone());
+        }
+        if let Some(format_manifests_command) = &config.workspace.format_manifests_command {
+            update = update.with_format_manifests_command(format_manifests_command.clone());
+        }
         match self.get_repo_url(config) {
             Ok(repo_url) => {
                 update = update.with_repo_url(repo_url);
@@ -205,18 +320,18 @@ impl Update {
         }
         update = config.fill_update_config(self.no_changelog, update)?;
         {
-            let release_date = self
-                .release_date
-                .as_ref()
-                .map(|date| {
-                    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-                        .context("cannot parse release_date to y-m-d format")
-                })
-                .transpose()?;
+            let release_date = self.release_date()?;
             let pr_link = update.repo_url().map(|url| url.git_pr_link());
             let changelog_req = ChangelogRequest {
                 release_date,
                 changelog_config: Some(self.changelog_config(config, pr_link.as_deref())?),
+                output_format: config
+                    .workspace
+                    .changelog_output_format
+                    .map(Into::into)
+                    .unwrap_or_default(),
+                template_context: config.workspace.template_context.clone(),
+                ..Default::default()
             };
             update = update.with_changelog_req(changelog_req);
         }
@@ -238,48 +353,96 @@ impl Update {
         Ok(update)
     }
 
+    /// Date of the release, read from `--release-date` or from `SOURCE_DATE_EPOCH`.
+    fn release_date(&self) -> anyhow::Result<Option<NaiveDate>> {
+        if let Some(date) = &self.release_date {
+            return parse_release_date(date).map(Some);
+        }
+        match std::env::var(SOURCE_DATE_EPOCH) {
+            Ok(epoch) if !epoch.is_empty() => date_from_epoch(&epoch).map(Some),
+            _ => Ok(None),
+        }
+    }
+
     fn changelog_config(
         &self,
         config: &Config,
         pr_link: Option<&str>,
     ) -> anyhow::Result<GitCliffConfig> {
-        let default_config_path = dirs::config_dir()
-            .context("cannot get config dir")?
-            .join("git-cliff")
-            .join(git_cliff_core::DEFAULT_CONFIG);
-
-        let path = match self.user_changelog_config(config) {
-            Some(provided_path) => {
-                if provided_path.exists() {
-                    provided_path
-                } else {
-                    anyhow::bail!("cannot read {provided_path:?}")
-                }
-            }
-            None => &default_config_path,
-        };
-
-        // Parse the configuration file.
-        let changelog_config = if path.exists() {
-            anyhow::ensure!(
-                config.changelog.is_default(),
-                "specifying the `[changelog]` configuration has no effect if `changelog_config` path is specified"
-            );
-            GitCliffConfig::load(path).context("failed to parse git-cliff config file")?
-        } else {
-            changelog_config::to_git_cliff_config(config.changelog.clone(), pr_link)
-                .context("invalid `[changelog] config")?
-        };
-
-        Ok(changelog_config)
+        git_cliff_config(self.changelog_config.as_deref(), config, pr_link)
     }
+}
 
-    /// Changelog configuration specified by user
-    fn user_changelog_config<'a>(&'a self, config: &'a Config) -> Option<&'a Path> {
-        self.changelog_config
-            .as_deref()
-            .or(config.workspace.changelog_config.as_deref())
+/// git-cliff configuration used to render the changelog.
+/// `changelog_config_path` is the path passed via CLI, which takes precedence over the
+/// `changelog_config` field of the release-plz configuration.
+pub(crate) fn git_cliff_config(
+    changelog_config_path: Option<&Path>,
+    config: &Config,
+    pr_link: Option<&str>,
+) -> anyhow::Result<GitCliffConfig> {
+    let default_config_path = dirs::config_dir()
+        .context("cannot get config dir")?
+        .join("git-cliff")
+        .join(git_cliff_core::DEFAULT_CONFIG);
+
+    let path = match changelog_config_path.or(config.workspace.changelog_config.as_deref()) {
+        Some(provided_path) => {
+            if provided_path.exists() {
+                provided_path
+            } else {
+                anyhow::bail!("cannot read {provided_path:?}")
+            }
+        }
+        None => &default_config_path,
+    };
+
+    let show_authors = config.workspace.changelog_show_authors == Some(true);
+    let unreleased_heading = config.workspace.changelog_unreleased_heading.as_deref();
+    // Parse the configuration file.
+    let mut changelog_config = if path.exists() {
+        anyhow::ensure!(
+            !show_authors,
+            "`changelog_show_authors` has no effect if `changelog_config` path is specified"
+        );
+        anyhow::ensure!(
+            unreleased_heading.is_none(),
+            "`changelog_unreleased_heading` has no effect if `changelog_config` path is specified"
+        );
+        anyhow::ensure!(
+            config.changelog.is_default(),
+            "specifying the `[changelog]` configuration has no effect if `changelog_config` path is specified"
+        );
+        GitCliffConfig::load(path).context("failed to parse git-cliff config file")?
+    } else {
+        changelog_config::to_git_cliff_config(config.changelog.clone(), pr_link)
+            .context("invalid `[changelog] config")?
+    };
+    if show_authors {
+        anyhow::ensure!(
+            config.changelog.body.is_none(),
+            "`changelog_show_authors` has no effect if the `[changelog]` body is specified"
+        );
+        changelog_config.changelog.body =
+            release_plz_core::default_changelog_body_with_authors().to_string();
+    }
+    if config.workspace.changelog_strip_pr_suffix == Some(true) {
+        // Strip the PR number before the other preprocessors link it.
+        changelog_config
+            .git
+            .commit_preprocessors
+            .insert(0, release_plz_core::strip_pr_suffix_preprocessor());
     }
+    if let Some(unreleased_heading) = unreleased_heading {
+        anyhow::ensure!(
+            config.changelog.header.is_none(),
+            "`changelog_unreleased_heading` has no effect if the `[changelog]` header is specified"
+        );
+        changelog_config.changelog.header =
+            Some(release_plz_core::changelog_header(unreleased_heading));
+    }
+
+    Ok(changelog_config)
 }
 
 /// This function validates that the Cargo.lock file is not both ignored and committed,
@@ -298,6 +461,22 @@ fn check_if_cargo_lock_is_ignored_and_committed(local_manifest: &Utf8Path) -> an
     Ok(())
 }
 
+fn parse_release_date(date: &str) -> anyhow::Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .with_context(|| format!("invalid release date `{date}`: expected format YYYY-MM-DD"))
+}
+
+/// Convert the number of seconds since the Unix epoch to a UTC date.
+fn date_from_epoch(epoch: &str) -> anyhow::Result<NaiveDate> {
+    let seconds: i64 = epoch.trim().parse().with_context(|| {
+        format!("invalid {SOURCE_DATE_EPOCH} `{epoch}`: expected the number of seconds since the Unix epoch")
+    })?;
+    let date = chrono::DateTime::from_timestamp(seconds, 0)
+        .with_context(|| format!("{SOURCE_DATE_EPOCH} `{epoch}` is out of range"))?
+        .date_naive();
+    Ok(date)
+}
+
 #[cfg(test)]
 mod tests {
     use fake_package::metadata::fake_metadata;
@@ -307,20 +486,23 @@ mod tests {
     #[test]
     fn input_generates_correct_release_request() {
         let update_args = Update {
-            manifest_path: None,
+            manifest_path: vec![],
             registry_manifest_path: None,
             package: None,
             no_changelog: false,
             release_date: None,
             registry: None,
             update_deps: false,
+            update_deps_package: vec![],
             changelog_config: None,
             allow_dirty: false,
             repo_url: None,
             config: ConfigPath::default(),
             forge: GitForgeKind::Github,
+            forge_api_url: None,
             git_token: None,
             max_analyze_commits: None,
+            jobs: None,
         };
         let config = update_args.config.load().unwrap();
         let req = update_args
@@ -329,4 +511,23 @@ mod tests {
         let pkg_config = req.get_package_config("aaa");
         assert_eq!(pkg_config, release_plz_core::PackageUpdateConfig::default());
     }
+
+    #[test]
+    fn release_date_is_validated() {
+        assert_eq!(
+            parse_release_date("2024-02-29").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        let err = parse_release_date("29/02/2024").unwrap_err().to_string();
+        assert!(err.contains("expected format YYYY-MM-DD"), "{err}");
+    }
+
+    #[test]
+    fn release_date_is_read_from_source_date_epoch() {
+        assert_eq!(
+            date_from_epoch("1709251199").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert!(date_from_epoch("yesterday").is_err());
+    }
 }
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use cargo_metadata::semver::Version;
+use clap::builder::{NonEmptyStringValueParser, PathBufValueParser};
+use release_plz_core::YankRequest;
+use secrecy::SecretString;
+
+use super::manifest_command::ManifestCommand;
+
+#[derive(clap::Parser, Debug)]
+pub struct Yank {
+    /// Package version to yank. Format: `<package_name>@<version>`.
+    /// E.g. `my-crate@1.2.3`.
+    #[arg(short, long, value_parser = NonEmptyStringValueParser::new())]
+    package: String,
+
+    /// Un-yank the version, making it available again.
+    #[arg(long)]
+    undo: bool,
+
+    /// Path to the Cargo.toml of the project containing the package.
+    /// If not provided, release-plz will use the Cargo.toml of the current directory.
+    #[arg(long, value_parser = PathBufValueParser::new())]
+    manifest_path: Option<PathBuf>,
+
+    /// Registry where the package is published.
+    /// The registry name needs to be present in the Cargo config.
+    /// If unspecified, crates.io is used.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    registry: Option<String>,
+
+    /// Token used to authenticate to the cargo registry.
+    /// Override the `CARGO_REGISTRY_TOKEN` environment variable, or the `CARGO_REGISTRIES_<NAME>_TOKEN`
+    /// environment variable, used for registry specified in the `registry` input variable.
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    token: Option<String>,
+}
+
+impl ManifestCommand for Yank {
+    fn optional_manifest(&self) -> Option<&Path> {
+        self.manifest_path.as_deref()
+    }
+}
+
+impl Yank {
+    pub fn yank_request(&self, metadata: cargo_metadata::Metadata) -> anyhow::Result<YankRequest> {
+        let (package, version) = parse_package_version(&self.package)?;
+        let mut request = YankRequest::new(metadata, package, version).with_undo(self.undo);
+        if let Some(registry) = &self.registry {
+            request = request.with_registry(registry.clone());
+        }
+        if let Some(token) = &self.token {
+            request = request.with_token(SecretString::from(token.clone()));
+        }
+        Ok(request)
+    }
+}
+
+fn parse_package_version(package_version: &str) -> anyhow::Result<(&str, Version)> {
+    let (package, version) = package_version.split_once('@').with_context(|| {
+        format!("invalid package `{package_version}`: expected format `<package_name>@<version>`")
+    })?;
+    let version = Version::parse(version)
+        .with_context(|| format!("invalid version `{version}` of package `{package}`"))?;
+    Ok((package, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_version_is_parsed() {
+        let (package, version) = parse_package_version("my-crate@1.2.3").unwrap();
+        assert_eq!(package, "my-crate");
+        assert_eq!(version, Version::new(1, 2, 3));
+    }
+
+    #[test]
+    fn package_without_version_is_rejected() {
+        let error = parse_package_version("my-crate").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid package `my-crate`: expected format `<package_name>@<version>`"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use release_plz_core::update_request::UpdateRequest;
+
+/// Changelog entries that `release-plz update` would add, without editing any file.
+pub async fn changelog_entries(update_request: &UpdateRequest) -> anyhow::Result<String> {
+    // `next_versions` works on a copy of the repository, so no file is edited.
+    let (packages_update, _temp_repo) = release_plz_core::next_versions(update_request).await?;
+    Ok(packages_update.changelog_entries())
+}
+
+#[cfg(test)]
+mod tests {
+    use cargo_metadata::camino::Utf8Path;
+    use clap::Parser as _;
+    use git_cmd::Repo;
+
+    use crate::args::{changelog::Changelog, manifest_command::ManifestCommand as _};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn changelog_entries_of_unreleased_commits_are_printed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(temp_dir.path()).unwrap();
+        let repo = Repo::init(dir);
+        fs_err::create_dir_all(dir.join("src")).unwrap();
+        fs_err::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"my_crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "").unwrap();
+        // Read the version from the git tags, so that the registry isn't queried.
+        fs_err::write(
+            dir.join("release-plz.toml"),
+            "[workspace]\ngit_only = true\n",
+        )
+        .unwrap();
+        repo.add_all_and_commit("feat: add my_crate").unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "pub fn f() {}").unwrap();
+        repo.add_all_and_commit("fix: add f").unwrap();
+
+        let args = Changelog::try_parse_from([
+            "changelog",
+            "--manifest-path",
+            dir.join("Cargo.toml").as_str(),
+            "--config",
+            dir.join("release-plz.toml").as_str(),
+            "--release-date",
+            "2024-01-31",
+        ])
+        .unwrap()
+        .into_update();
+        let config = args.config.load().unwrap();
+        let update_request = args
+            .update_request(&config, args.cargo_metadata().unwrap())
+            .unwrap();
+
+        let entries = changelog_entries(&update_request).await.unwrap();
+        expect_test::expect![[r#"
+            ## [0.1.0] - 2024-01-31
+
+            ### Added
+
+            - add my_crate
+
+            ### Fixed
+
+            - add f
+
+            ### Other
+
+            - add README"#]]
+        .assert_eq(&entries);
+    }
+}
@@ -27,6 +27,8 @@ pub struct ChangelogCfg {
     pub commit_parsers: Option<Vec<CommitParser>>,
     /// Whether to protect all breaking changes from being skipped by a commit parser.
     pub protect_breaking_commits: Option<bool>,
+    /// Whether to fail the update if a commit doesn't follow the conventional commits specification.
+    pub require_conventional: Option<bool>,
     /// A regular expression for matching the git tags to add to the changelog.
     pub tag_pattern: Option<String>,
 }
@@ -40,6 +42,7 @@ impl ChangelogCfg {
 
 /// Used for modifying commit messages.
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct TextProcessor {
     /// Regex for matching a text to replace.
     pub pattern: String,
@@ -78,6 +81,7 @@ impl std::fmt::Display for Sorting {
 }
 
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct LinkParser {
     /// Regex for finding links in the commit message.
     pub pattern: String,
@@ -101,6 +105,7 @@ impl TryFrom<LinkParser> for git_cliff_core::config::LinkParser {
 
 /// Parser for grouping commits.
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct CommitParser {
     /// Regex for matching the commit message.
     pub message: Option<String>,
@@ -201,6 +206,9 @@ pub fn to_git_cliff_config(
         },
         git: git_cliff_core::config::GitConfig {
             conventional_commits: default_git_config.conventional_commits,
+            require_conventional: cfg
+                .require_conventional
+                .unwrap_or(default_git_config.require_conventional),
             filter_unconventional: default_git_config.filter_unconventional,
             split_commits: default_git_config.split_commits,
             commit_preprocessors,
@@ -237,6 +245,7 @@ mod tests {
             body = "Body"
             trim = true
             protect_breaking_commits = true
+            require_conventional = true
 
             commit_preprocessors = [
                 { pattern = "pattern", replace = "replace", replace_command = "replace_command" },
@@ -310,6 +319,7 @@ mod tests {
                 sort_commits: "newest".to_string(),
                 limit_commits: None,
                 conventional_commits: true,
+                require_conventional: true,
                 filter_unconventional: false,
                 split_commits: false,
                 ..Default::default()
@@ -0,0 +1,206 @@
+use anyhow::Context as _;
+use cargo_metadata::Metadata;
+use git_cliff_core::commit::Commit;
+use git_cmd::Repo;
+use release_plz_core::{
+    ChangelogBuilder, DEFAULT_BRANCH_PREFIX, Project, ReleaseRequest, update_request::UpdateRequest,
+};
+
+use crate::{
+    args::{manifest_command::ManifestCommand as _, update::Update},
+    config::Config,
+};
+
+/// Outcome of the checks run by `release-plz check`.
+#[derive(Default)]
+struct Report {
+    results: Vec<(&'static str, anyhow::Result<()>)>,
+}
+
+impl Report {
+    /// Record the outcome of the check and return its output, if successful.
+    fn record<T>(&mut self, check: &'static str, result: anyhow::Result<T>) -> Option<T> {
+        match result {
+            Ok(output) => {
+                self.results.push((check, Ok(())));
+                Some(output)
+            }
+            Err(e) => {
+                self.results.push((check, Err(e)));
+                None
+            }
+        }
+    }
+
+    fn failed_checks(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_err()).count()
+    }
+
+    fn print(&self) {
+        for (check, result) in &self.results {
+            match result {
+                Ok(()) => println!("✅ {check}"),
+                Err(e) => println!("❌ {check}: {e:#}"),
+            }
+        }
+    }
+}
+
+/// Validate the release-plz setup without editing files or calling write APIs.
+/// Print a report with the outcome of every check and fail if any check fails.
+pub async fn check(args: &Update) -> anyhow::Result<()> {
+    let report = run_checks(args).await;
+    report.print();
+    let failed_checks = report.failed_checks();
+    anyhow::ensure!(failed_checks == 0, "{failed_checks} check(s) failed");
+    Ok(())
+}
+
+async fn run_checks(args: &Update) -> Report {
+    let mut report = Report::default();
+    let metadata = report.record("cargo metadata is readable", args.cargo_metadata());
+    let config = report.record("release-plz config is valid", args.config.load());
+    if let (Some(metadata), Some(config)) = (metadata, config) {
+        check_project(&mut report, args, &config, metadata).await;
+    }
+    report
+}
+
+async fn check_project(report: &mut Report, args: &Update, config: &Config, metadata: Metadata) {
+    // Filling the release configuration also checks that the `[[package]]` names
+    // match workspace packages.
+    let release_request = config
+        .fill_release_config(false, false, ReleaseRequest::new(metadata.clone()))
+        .and_then(|req| req.check_publish_fields());
+    if report
+        .record("release configuration is valid", release_request)
+        .is_none()
+    {
+        // The update configuration would fail for the same reason.
+        return;
+    }
+    let Some(update_request) = report.record(
+        "update configuration is valid",
+        args.update_request(config, metadata),
+    ) else {
+        return;
+    };
+    let overrides = update_request.packages_config().overridden_packages();
+    let Some(project) = report.record(
+        "workspace is readable",
+        Project::new(
+            update_request.local_manifest(),
+            update_request.single_package(),
+            &overrides,
+            update_request.cargo_metadata(),
+            &update_request,
+        ),
+    ) else {
+        return;
+    };
+    report.record(
+        "mandatory Cargo.toml fields are present",
+        project.check_mandatory_fields(),
+    );
+    report.record(
+        "git tag names are valid",
+        check_tag_names(&project, update_request.cargo_metadata()),
+    );
+    report.record(
+        "changelog template renders",
+        check_changelog_template(&update_request),
+    );
+    // The git token is optional, so check it only if it's provided.
+    if let Some(git_client) = report
+        .record("git forge settings are valid", update_request.git_client())
+        .flatten()
+    {
+        let opened_prs = git_client.opened_prs(DEFAULT_BRANCH_PREFIX).await;
+        report.record(
+            "git token can authenticate to the git forge",
+            opened_prs.map(|_| ()),
+        );
+    }
+}
+
+fn check_tag_names(project: &Project, metadata: &Metadata) -> anyhow::Result<()> {
+    let repo = Repo::new(&metadata.workspace_root)?;
+    for package in project.publishable_packages() {
+        let tag = project.git_tag(&package.name, &package.version.to_string())?;
+        repo.git(&["check-ref-format", &format!("refs/tags/{tag}")])
+            .with_context(|| format!("invalid git tag `{tag}` for package `{}`", package.name))?;
+    }
+    Ok(())
+}
+
+fn check_changelog_template(update_request: &UpdateRequest) -> anyhow::Result<()> {
+    let commits = vec![Commit::new(
+        "0000000".to_string(),
+        "feat: check the changelog template".to_string(),
+    )];
+    let mut builder = ChangelogBuilder::new(commits, "0.1.0", "my_package");
+    if let Some(config) = &update_request.changelog_req().changelog_config {
+        builder = builder.with_config(config.clone());
+    }
+    builder.build().generate()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cargo_metadata::camino::Utf8Path;
+    use clap::Parser as _;
+
+    use crate::args::check::Check;
+
+    use super::*;
+
+    /// Package in a git repository, with the given release-plz configuration.
+    fn write_package(dir: &Utf8Path, config: &str) {
+        Repo::init(dir);
+        fs_err::create_dir_all(dir.join("src")).unwrap();
+        fs_err::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"my_crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\ndescription = \"my crate\"\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "").unwrap();
+        fs_err::write(dir.join("release-plz.toml"), config).unwrap();
+    }
+
+    async fn failed_checks(dir: &Utf8Path) -> Vec<&'static str> {
+        let args = Check::try_parse_from([
+            "check",
+            "--manifest-path",
+            dir.join("Cargo.toml").as_str(),
+            "--config",
+            dir.join("release-plz.toml").as_str(),
+        ])
+        .unwrap()
+        .into_update();
+        let report = run_checks(&args).await;
+        assert!(!report.results.is_empty());
+        report
+            .results
+            .iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(check, _)| *check)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn valid_setup_passes_every_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(temp_dir.path()).unwrap();
+        write_package(dir, "[workspace]\n");
+        assert!(failed_checks(dir).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_package_fails_the_release_configuration_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(temp_dir.path()).unwrap();
+        write_package(dir, "[[package]]\nname = \"unknown\"\n");
+        assert_eq!(failed_checks(dir).await, ["release configuration is valid"]);
+    }
+}
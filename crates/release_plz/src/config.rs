@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use cargo_metadata::camino::Utf8Path;
+use cargo_metadata::{Metadata, camino::Utf8Path, semver::Version};
 use cargo_utils::to_utf8_pathbuf;
 use release_plz_core::{
     GitReleaseConfig, ReleaseRequest,
@@ -9,7 +9,12 @@ use release_plz_core::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf, time::Duration};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use url::Url;
 
 use crate::changelog_config::ChangelogCfg;
@@ -29,25 +34,128 @@ pub struct Config {
     /// # Package
     /// Package-specific configuration. This overrides `workspace`.
     /// Not all settings of `workspace` can be overridden.
+    /// The `name` can be a glob pattern, e.g. `acme-*`.
     #[serde(default)]
     package: Vec<PackageSpecificConfigWithName>,
 }
 
 impl Config {
-    /// Package-specific configurations.
+    /// Package-specific configurations of the workspace packages.
     /// Returns `<package name, package config>`.
-    fn packages(&self) -> HashMap<&str, &PackageSpecificConfig> {
+    fn packages(&self, metadata: &Metadata) -> Vec<(String, &PackageSpecificConfig)> {
+        metadata
+            .workspace_packages()
+            .iter()
+            .filter_map(|p| {
+                self.package_config(&p.name)
+                    .map(|config| (p.name.to_string(), config))
+            })
+            .collect()
+    }
+
+    /// `[[package]]` section that applies to the given package.
+    ///
+    /// A section with the exact package name takes precedence over glob patterns.
+    /// If multiple glob patterns match, the most specific one wins, i.e. the one with
+    /// the most literal characters. Ties are resolved in favor of the first section of the config.
+    fn package_config(&self, package_name: &str) -> Option<&PackageSpecificConfig> {
+        if let Some(p) = self.package.iter().find(|p| p.name == package_name) {
+            return Some(&p.config);
+        }
         self.package
             .iter()
-            .map(|p| (p.name.as_str(), &p.config))
+            .filter(|p| is_glob_pattern(&p.name) && glob_matches(&p.name, package_name))
+            .min_by_key(|p| Reverse(glob_specificity(&p.name)))
+            .map(|p| &p.config)
+    }
+
+    /// Check that the glob patterns of the `[[package]]` sections are valid.
+    pub fn check_package_name_patterns(&self) -> anyhow::Result<()> {
+        for p in self.package.iter().filter(|p| is_glob_pattern(&p.name)) {
+            globset::Glob::new(&p.name).with_context(|| {
+                format!("invalid glob pattern `{}` in `[[package]]` name", p.name)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Check that the list of packages of `dependencies_update` isn't empty.
+    /// `cargo update` without packages would update all the dependencies instead.
+    pub fn check_dependencies_update(&self) -> anyhow::Result<()> {
+        if let Some(DependenciesUpdate::Packages(packages)) = &self.workspace.dependencies_update {
+            anyhow::ensure!(
+                !packages.is_empty(),
+                "the list of packages of `dependencies_update` is empty. Use `dependencies_update = \"workspace\"` to only update the workspace packages"
+            );
+        }
+        Ok(())
+    }
+
+    /// Configuration of the given packages, after merging the `[[package]]` sections
+    /// with the `[workspace]` section.
+    /// Packages without a `[[package]]` section use the `[workspace]` configuration.
+    pub fn resolved_packages_config(
+        &self,
+        package_names: &[&str],
+    ) -> BTreeMap<String, PackageSpecificConfig> {
+        let defaults = &self.workspace.packages_defaults;
+        package_names
+            .iter()
+            .map(|&name| {
+                let config = match self.package_config(name) {
+                    Some(config) => config.clone().merge(defaults.clone()),
+                    None => defaults.clone().into(),
+                };
+                (name.to_string(), config)
+            })
             .collect()
     }
 
+    /// Check that every `[[package]]` section refers to a package of the workspace.
+    /// Otherwise, a typo in the package name would silently be ignored.
+    fn check_package_names(&self, workspace_packages: &[&str]) -> anyhow::Result<()> {
+        let workspace_packages: BTreeSet<&str> = workspace_packages.iter().copied().collect();
+        let unknown_packages: Vec<&str> = self
+            .package
+            .iter()
+            .map(|p| p.name.as_str())
+            .filter(|name| {
+                if is_glob_pattern(name) {
+                    !workspace_packages
+                        .iter()
+                        .any(|package| glob_matches(name, package))
+                } else {
+                    !workspace_packages.contains(name)
+                }
+            })
+            .collect();
+        anyhow::ensure!(
+            unknown_packages.is_empty(),
+            "the `[[package]]` sections of the release-plz config refer to packages that aren't part of the workspace: {}. Packages of the workspace: {}",
+            unknown_packages.join(", "),
+            workspace_packages
+                .into_iter()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(())
+    }
+
+    fn check_package_names_in_metadata(&self, metadata: &Metadata) -> anyhow::Result<()> {
+        let workspace_packages: Vec<&str> = metadata
+            .workspace_packages()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        self.check_package_names(&workspace_packages)
+    }
+
     pub fn fill_update_config(
         &self,
         is_changelog_update_disabled: bool,
         update_request: UpdateRequest,
     ) -> anyhow::Result<UpdateRequest> {
+        self.check_package_names_in_metadata(update_request.cargo_metadata())?;
         // Validate workspace defaults (applies to all packages without specific config)
         validate_git_only_settings(
             self.workspace.packages_defaults.git_only,
@@ -59,9 +167,11 @@ impl Config {
         if is_changelog_update_disabled {
             default_update_config.changelog_update = false.into();
         }
-        let mut update_request =
-            update_request.with_default_package_config(default_update_config.into());
-        for (package, config) in self.packages() {
+        let default_update_config = release_plz_core::UpdateConfig::from(default_update_config)
+            .with_tag_prefix(self.tag_prefix(None));
+        let mut update_request = update_request.with_default_package_config(default_update_config);
+        for (package, config) in self.packages(update_request.cargo_metadata()) {
+            let tag_prefix = self.tag_prefix(Some(config));
             let mut update_config = config.clone();
             update_config = update_config.merge(self.workspace.packages_defaults.clone());
 
@@ -77,19 +187,39 @@ impl Config {
             if is_changelog_update_disabled {
                 update_config.common.changelog_update = false.into();
             }
-            update_request = update_request.with_package_config(package, update_config.into());
+            let min_version = update_config
+                .min_version
+                .as_deref()
+                .map(Version::parse)
+                .transpose()
+                .with_context(|| format!("invalid `min_version` of package {package}"))?;
+            let mut update_config = release_plz_core::PackageUpdateConfig::from(update_config);
+            update_config.generic = update_config.generic.with_tag_prefix(tag_prefix);
+            update_config.min_version = min_version;
+            update_request = update_request.with_package_config(package, update_config);
         }
         Ok(update_request)
     }
 
+    /// Prefix of the git tags of a package.
+    /// Packages that override `git_tag_name` don't use the workspace `tag_prefix`.
+    fn tag_prefix(&self, package_config: Option<&PackageSpecificConfig>) -> String {
+        let overrides_tag_name = package_config.is_some_and(|c| c.common.git_tag_name.is_some());
+        if overrides_tag_name {
+            String::new()
+        } else {
+            self.workspace.tag_prefix.clone().unwrap_or_default()
+        }
+    }
+
     pub fn fill_set_version_config(
         &self,
         set_version_request: &mut SetVersionRequest,
     ) -> anyhow::Result<()> {
-        for (package, config) in self.packages() {
+        for (package, config) in self.packages(set_version_request.cargo_metadata()) {
             if let Some(changelog_path) = config.common.changelog_path.clone() {
                 let changelog_path = to_utf8_pathbuf(changelog_path)?;
-                set_version_request.set_changelog_path(package, changelog_path);
+                set_version_request.set_changelog_path(&package, changelog_path);
             }
         }
         Ok(())
@@ -101,6 +231,7 @@ impl Config {
         no_verify: bool,
         release_request: ReleaseRequest,
     ) -> anyhow::Result<ReleaseRequest> {
+        self.check_package_names_in_metadata(release_request.cargo_metadata())?;
         // Validate workspace defaults (applies to all packages without specific config)
         validate_git_only_settings(
             self.workspace.packages_defaults.git_only,
@@ -115,10 +246,13 @@ impl Config {
         if allow_dirty {
             default_config.publish_allow_dirty = Some(true);
         }
-        let mut release_request =
-            release_request.with_default_package_config(default_config.into());
+        validate_publish_target(&default_config).context("Wrong workspace context")?;
+        let default_config = release_plz_core::ReleaseConfig::from(default_config)
+            .with_git_tag_prefix(self.tag_prefix(None));
+        let mut release_request = release_request.with_default_package_config(default_config);
 
-        for (package, config) in self.packages() {
+        for (package, config) in self.packages(release_request.cargo_metadata()) {
+            let tag_prefix = self.tag_prefix(Some(config));
             let mut release_config = config.clone();
             release_config = release_config.merge(self.workspace.packages_defaults.clone());
 
@@ -137,13 +271,34 @@ impl Config {
             if allow_dirty {
                 release_config.common.publish_allow_dirty = Some(true);
             }
-            release_request =
-                release_request.with_package_config(package, release_config.common.into());
+            validate_publish_target(&release_config.common)
+                .with_context(|| format!("Wrong configuration of package {package}"))?;
+            let release_config = release_plz_core::ReleaseConfig::from(release_config.common)
+                .with_git_tag_prefix(tag_prefix);
+            release_request = release_request.with_package_config(package, release_config);
         }
         Ok(release_request)
     }
 }
 
+/// Package names can't contain glob metacharacters,
+/// so a `[[package]]` name containing them is a glob pattern.
+fn is_glob_pattern(name: &str) -> bool {
+    name.contains(['*', '?', '[', '{'])
+}
+
+fn glob_matches(pattern: &str, package_name: &str) -> bool {
+    globset::Glob::new(pattern).is_ok_and(|glob| glob.compile_matcher().is_match(package_name))
+}
+
+/// Number of literal characters of the glob pattern.
+fn glob_specificity(pattern: &str) -> usize {
+    pattern
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?' | '[' | ']' | '{' | '}' | ','))
+        .count()
+}
+
 fn validate_git_only_settings(git_only: Option<bool>, publish: Option<bool>) -> anyhow::Result<()> {
     if git_only == Some(true) && publish == Some(true) {
         anyhow::bail!(
@@ -154,6 +309,16 @@ fn validate_git_only_settings(git_only: Option<bool>, publish: Option<bool>) ->
     Ok(())
 }
 
+fn validate_publish_target(config: &PackageConfig) -> anyhow::Result<()> {
+    if config.publish_target.is_some() && config.publish_no_verify == Some(true) {
+        anyhow::bail!(
+            "Config options 'publish_target' and 'publish_no_verify' are mutually exclusive: \
+            the target is only used to verify the package."
+        );
+    }
+    Ok(())
+}
+
 /// Config at the `[workspace]` level.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -168,16 +333,54 @@ pub struct Workspace {
     /// # Changelog Config
     /// Path to the git cliff configuration file. Defaults to the `keep a changelog` configuration.
     pub changelog_config: Option<PathBuf>,
+    /// # Changelog Show Authors
+    /// If `true`, use a default changelog body that shows the username of the author
+    /// of every commit on the git forge.
+    /// Can't be used together with a custom changelog body.
+    pub changelog_show_authors: Option<bool>,
+    /// # Changelog Strip PR Suffix
+    /// If `true`, remove the trailing PR number, e.g. ` (#123)`, from the commit messages
+    /// before rendering the changelog.
+    pub changelog_strip_pr_suffix: Option<bool>,
+    /// # Changelog Output Format
+    /// Format of the changelog file.
+    /// If unspecified, the changelog is rendered as markdown.
+    pub changelog_output_format: Option<ChangelogOutputFormat>,
     /// # Dependencies Update
-    /// - If `true`, update all the dependencies in the Cargo.lock file by running `cargo update`.
-    /// - If `false` or [`Option::None`], only update the workspace packages by running `cargo update --workspace`.
-    pub dependencies_update: Option<bool>,
+    /// - If `true` or `"all"`, update all the dependencies in the Cargo.lock file by running `cargo update`.
+    /// - If `false`, `"workspace"` or [`Option::None`], only update the workspace packages by running `cargo update --workspace`.
+    /// - If a list of packages, only update these packages by running `cargo update -p <package>...`.
+    ///   The list can't be empty.
+    ///
+    /// `update_dependencies` is accepted as an alias of this field.
+    #[serde(alias = "update_dependencies")]
+    pub dependencies_update: Option<DependenciesUpdate>,
+    /// # Update Cargo Lock
+    /// - If `true` or [`Option::None`], run `cargo update` after updating the manifests.
+    /// - If `false`, don't run `cargo update`. Useful for offline builds.
+    ///   The `Cargo.lock` file might be out of date until the next build.
+    pub update_cargo_lock: Option<bool>,
     /// # PR Name
     /// Tera template of the pull request's name created by release-plz.
     pub pr_name: Option<String>,
+    /// # PR Name Path
+    /// Path to a file containing the Tera template of the pull request's name.
+    /// Can't be used together with `pr_name`.
+    pub pr_name_path: Option<PathBuf>,
     /// # PR Body
     /// Tera template of the pull request's body created by release-plz.
     pub pr_body: Option<String>,
+    /// # PR Body Path
+    /// Path to a file containing the Tera template of the pull request's body.
+    /// Can't be used together with `pr_body`.
+    pub pr_body_path: Option<PathBuf>,
+    /// # PR Body Footer
+    /// Tera template of the footer appended to the pull request's body.
+    /// Set it to `true` to add the default footer even to custom bodies,
+    /// or to `false` to omit the footer.
+    /// If unspecified, the default body ends with a footer crediting release-plz,
+    /// while custom bodies have no footer.
+    pub pr_body_footer: Option<PrBodyFooter>,
     /// # PR Draft
     /// If `true`, the created release PR will be marked as a draft.
     #[serde(default)]
@@ -185,13 +388,46 @@ pub struct Workspace {
     /// # PR Labels
     /// Labels to add to the release PR.
     #[serde(default)]
-    pub pr_labels: Vec<String>,
+    pub pr_labels: Vec<PrLabel>,
+    /// # Template Context
+    /// Variables available in the templates of the release PR name and body,
+    /// of the changelog and of the git release body, e.g. `{ team = "core" }`.
+    /// They can't override the variables set by release-plz, such as `package` or `version`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub template_context: BTreeMap<String, String>,
     /// # PR Branch Prefix
     /// Prefix for the PR Branch
     pub pr_branch_prefix: Option<String>,
+    /// # PR Head Repo
+    /// Fork where release-plz pushes the release PR branch, in the `owner/repo` format.
+    /// The release PR is opened from the fork into the repository of release-plz.
+    /// If unspecified, the branch is pushed to the repository where the PR is opened.
+    pub pr_head_repo: Option<String>,
+    /// # Release PR Branch Regex
+    /// Regex used by `release-plz release` to identify release PRs by their branch name.
+    /// If unspecified, release PRs are the PRs whose branch starts with `pr_branch_prefix`.
+    pub release_pr_branch_regex: Option<String>,
+    /// # Base Branch
+    /// Branch the release PR is opened against, and where release PRs are merged.
+    /// If unspecified, the branch where release-plz runs is used.
+    pub base_branch: Option<String>,
+    /// # Git Author Name
+    /// Name of the author and committer of the release commit, and of the tagger of git tags.
+    /// If unspecified, the `user.name` of the git configuration is used.
+    pub git_author_name: Option<String>,
+    /// # Git Author Email
+    /// Email of the author and committer of the release commit, and of the tagger of git tags.
+    /// If unspecified, the `user.email` of the git configuration is used.
+    pub git_author_email: Option<String>,
     /// # Publish Timeout
     /// Timeout for the publishing process
     pub publish_timeout: Option<String>,
+    /// # Registry Token Command
+    /// Command that prints the token used to publish to the cargo registry,
+    /// e.g. `["vault", "read", "-field=token", "secret/cargo"]`.
+    /// Release-plz runs it once per release, when the token is needed.
+    /// Ignored if the token is passed via the `--token` cli argument.
+    pub registry_token_command: Option<Vec<String>>,
     /// # Repo URL
     /// GitHub/Gitea/GitLab repository url where your project is hosted.
     /// It is used to generate the changelog release link.
@@ -210,6 +446,86 @@ pub struct Workspace {
     ///   `release-plz-`. So if you want to create a PR that should trigger a release
     ///   (e.g. when you fix the CI), use this branch name format (e.g. `release-plz-fix-ci`).
     pub release_always: Option<bool>,
+    /// # Comment On Release
+    /// If `true`, after releasing the packages of a merged release PR, `release-plz release`
+    /// comments on the PR with the released packages, their git tags and crates.io pages.
+    /// Default: `false`.
+    pub comment_on_release: Option<bool>,
+    /// # Single Tag
+    /// - If `true`, create a single git tag and git release (e.g. `v1.4.0`) for all the packages
+    ///   of the workspace, instead of one per package.
+    ///   All the released packages must have the same version.
+    /// - If `false` or [`Option::None`], create a git tag and git release for every package.
+    pub single_tag: Option<bool>,
+    /// # Combined Git Release
+    /// - If `true`, after releasing the packages, create an additional git release that links
+    ///   the git tags of all the released packages and contains their changelogs.
+    /// - If `false` or [`Option::None`], don't create the combined git release.
+    pub combined_git_release: Option<bool>,
+    /// # Combined Git Release Only
+    /// - If `true`, create the combined git release instead of the git releases of the packages.
+    ///   The git tags of the packages are still created.
+    /// - If `false` or [`Option::None`], create the combined git release in addition to the
+    ///   git releases of the packages.
+    pub combined_git_release_only: Option<bool>,
+    /// # Combined Git Release Tag Name
+    /// Tera template of the git tag of the combined git release.
+    /// Default: `release-{{ date }}-{{ time }}`.
+    pub combined_git_release_tag_name: Option<String>,
+    /// # Tag Prefix
+    /// Prefix prepended to the git tag name of every package, e.g. `myproduct-`.
+    /// Packages that set their own `git_tag_name` don't use this prefix.
+    /// Default: no prefix.
+    pub tag_prefix: Option<String>,
+    /// # Verify Published Checksum
+    /// - If `true`, after publishing a package, download its `.crate` file from the registry
+    ///   and check that its checksum matches the `.crate` file built by `cargo publish`.
+    /// - If `false` or [`Option::None`], don't verify the published package.
+    pub verify_published_checksum: Option<bool>,
+    /// # Skip Publish Wait
+    /// - If `true`, after `cargo publish` uploads a package, don't wait for the package
+    ///   to be available in the registry index.
+    /// - If `false` or [`Option::None`], wait until the package is available in the registry index.
+    pub skip_publish_wait: Option<bool>,
+    /// # Fetch Contributors
+    /// - If `true` or [`Option::None`], query the git forge to find the usernames of the
+    ///   contributors shown in changelogs and git releases.
+    /// - If `false`, don't query the git forge for contributors.
+    pub fetch_contributors: Option<bool>,
+    /// # Dependency Update Message Template
+    /// Tera template of the changelog entry added to a package that is released only because
+    /// its local dependencies changed.
+    /// The `{{ packages }}` variable contains the comma-separated list of updated dependencies.
+    /// Default: `chore: updated the following local packages: {{ packages }}`.
+    pub dependency_update_message_template: Option<String>,
+    /// # Changelog Filenames
+    /// File names recognized as changelogs when a package doesn't set `changelog_path`,
+    /// in order of preference. File names are compared case-insensitively.
+    /// If none of them exists in the package directory, `CHANGELOG.md` is created.
+    /// Default: `["CHANGELOG.md", "CHANGES.md", "HISTORY.md", "RELEASES.md"]`.
+    pub changelog_filenames: Option<Vec<String>>,
+    /// # Changelog Unreleased Heading
+    /// Heading of the section of unreleased changes in the default changelog header,
+    /// e.g. `## [En desarrollo]`.
+    /// Default: `## [Unreleased]`.
+    pub changelog_unreleased_heading: Option<String>,
+    /// # Changelog Exclude Merge Commits
+    /// If `true`, commits with more than one parent don't appear in the changelog
+    /// and don't affect the next version.
+    #[serde(default)]
+    pub changelog_exclude_merge_commits: bool,
+    /// # Format Manifests Command
+    /// Command run in the workspace root on the `Cargo.toml` files edited by release-plz,
+    /// e.g. `["taplo", "fmt"]`.
+    /// The paths of the edited manifests are appended to the command.
+    /// If the command fails, release-plz fails.
+    pub format_manifests_command: Option<Vec<String>>,
+    /// # Infer Baseline From Lock
+    /// If `true`, packages that aren't in the registry and don't have a git tag
+    /// use the commit that added their current version to the `Cargo.lock` as starting point
+    /// of the changelog, instead of their whole git history.
+    #[serde(default)]
+    pub infer_baseline_from_lock: bool,
     /// Maximum number of commits to analyze when the package hasn't been published yet.
     /// Default: 1000.
     #[serde(default = "default_max_analyze_commits")]
@@ -223,22 +539,82 @@ impl Default for Workspace {
             packages_defaults: PackageConfig::default(),
             allow_dirty: None,
             changelog_config: None,
+            changelog_show_authors: None,
+            changelog_strip_pr_suffix: None,
+            changelog_output_format: None,
             dependencies_update: None,
+            update_cargo_lock: None,
             repo_url: None,
             pr_name: None,
+            pr_name_path: None,
             pr_body: None,
+            pr_body_path: None,
+            pr_body_footer: None,
             pr_draft: false,
             pr_labels: Vec::new(),
+            template_context: BTreeMap::new(),
             pr_branch_prefix: None,
+            pr_head_repo: None,
+            release_pr_branch_regex: None,
+            base_branch: None,
+            git_author_name: None,
+            git_author_email: None,
             publish_timeout: None,
+            registry_token_command: None,
             release_commits: None,
             release_always: None,
+            comment_on_release: None,
+            single_tag: None,
+            combined_git_release: None,
+            combined_git_release_only: None,
+            combined_git_release_tag_name: None,
+            tag_prefix: None,
+            verify_published_checksum: None,
+            skip_publish_wait: None,
+            fetch_contributors: None,
+            dependency_update_message_template: None,
+            changelog_filenames: None,
+            changelog_unreleased_heading: None,
+            changelog_exclude_merge_commits: false,
+            format_manifests_command: None,
+            infer_baseline_from_lock: false,
             max_analyze_commits: default_max_analyze_commits(),
         }
     }
 }
 
 impl Workspace {
+    /// Identity used by git to create the release commit and the git tags.
+    /// The values passed via CLI take precedence over the config file.
+    pub fn git_identity(
+        &self,
+        cli_name: Option<String>,
+        cli_email: Option<String>,
+    ) -> git_cmd::GitIdentity {
+        git_cmd::GitIdentity {
+            name: cli_name.or_else(|| self.git_author_name.clone()),
+            email: cli_email.or_else(|| self.git_author_email.clone()),
+        }
+    }
+
+    /// Tera template of the release PR name, read from `pr_name_path` if set.
+    pub fn pr_name_template(&self) -> anyhow::Result<Option<String>> {
+        inline_or_file_template(
+            self.pr_name.as_deref(),
+            self.pr_name_path.as_deref(),
+            "pr_name",
+        )
+    }
+
+    /// Tera template of the release PR body, read from `pr_body_path` if set.
+    pub fn pr_body_template(&self) -> anyhow::Result<Option<String>> {
+        inline_or_file_template(
+            self.pr_body.as_deref(),
+            self.pr_body_path.as_deref(),
+            "pr_body",
+        )
+    }
+
     /// Get the publish timeout. Defaults to 30 minutes.
     pub fn publish_timeout(&self) -> anyhow::Result<Duration> {
         let publish_timeout = self.publish_timeout.as_deref().unwrap_or("30m");
@@ -247,6 +623,24 @@ impl Workspace {
     }
 }
 
+/// Template specified either inline, in the `field` field, or in a file, in the `{field}_path` field.
+fn inline_or_file_template(
+    inline: Option<&str>,
+    path: Option<&Path>,
+    field: &str,
+) -> anyhow::Result<Option<String>> {
+    match (inline, path) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("`{field}` and `{field}_path` can't be set at the same time")
+        }
+        (Some(template), None) => Ok(Some(template.to_string())),
+        (None, Some(path)) => fs_err::read_to_string(path)
+            .map(Some)
+            .with_context(|| format!("cannot read the `{field}_path` template")),
+        (None, None) => Ok(None),
+    }
+}
+
 fn default_max_analyze_commits() -> Option<u32> {
     Some(DEFAULT_MAX_ANALYZE_COMMITS)
 }
@@ -303,9 +697,27 @@ pub struct PackageSpecificConfig {
     /// List of package names.
     /// Include the changelogs of these packages in the changelog of the current package.
     changelog_include: Option<Vec<String>>,
+    /// # Changelog Include Paths
+    /// Globs of paths, relative to the repository root, e.g. `["vendor/foo/**"]`.
+    /// Include the commits that changed these paths in the changelog of the current package.
+    changelog_include_paths: Option<Vec<String>>,
     /// # Version group
     /// The name of a group of packages that needs to have the same version.
     version_group: Option<String>,
+    /// # Force Bump
+    /// Part of the version to increment when the package is released,
+    /// regardless of the commits.
+    /// If the package is in a `version_group`, the group uses the highest
+    /// version among its packages.
+    force_bump: Option<ForceBump>,
+    /// # Min Version
+    /// Lowest version that release-plz can propose for the package, e.g. `"1.2.0"`.
+    /// If the version derived from the commits is lower, this version is used instead.
+    min_version: Option<String>,
+    /// # Release On Dependency Change
+    /// - If `true` or [`Option::None`], release the package when one of its local dependencies is released.
+    /// - If `false`, release the package only if it contains new commits.
+    release_on_dependency_change: Option<bool>,
 }
 
 impl PackageSpecificConfig {
@@ -314,7 +726,25 @@ impl PackageSpecificConfig {
         Self {
             common: self.common.merge(default),
             changelog_include: self.changelog_include,
+            changelog_include_paths: self.changelog_include_paths,
             version_group: self.version_group,
+            force_bump: self.force_bump,
+            min_version: self.min_version,
+            release_on_dependency_change: self.release_on_dependency_change,
+        }
+    }
+}
+
+impl From<PackageConfig> for PackageSpecificConfig {
+    fn from(common: PackageConfig) -> Self {
+        Self {
+            common,
+            changelog_include: None,
+            changelog_include_paths: None,
+            version_group: None,
+            force_bump: None,
+            min_version: None,
+            release_on_dependency_change: None,
         }
     }
 }
@@ -332,33 +762,49 @@ impl From<PackageConfig> for release_plz_core::ReleaseConfig {
         let is_git_tag_enabled = value.git_tag_enable != Some(false);
         let git_tag_name = value.git_tag_name.clone();
         let release = value.release != Some(false);
+        let publish = release_plz_core::PublishConfig::enabled(is_publish_enabled)
+            .with_registries(value.publish_registries.clone().unwrap_or_default())
+            .with_excluded_registries(value.publish_exclude_registries.clone().unwrap_or_default());
         let mut cfg = Self::default()
-            .with_publish(release_plz_core::PublishConfig::enabled(is_publish_enabled))
+            .with_publish(publish)
             .with_git_release(git_release(&value))
             .with_git_tag(
                 release_plz_core::GitTagConfig::enabled(is_git_tag_enabled)
-                    .set_name_template(git_tag_name),
+                    .set_name_template(git_tag_name)
+                    .with_annotated(value.git_tag_annotated != Some(false)),
             )
             .with_release(release);
 
         if let Some(changelog_update) = value.changelog_update {
             cfg = cfg.with_changelog_update(changelog_update);
         }
+        if let Some(changelog_body_from) = value.changelog_body_from {
+            cfg = cfg.with_changelog_body_from(changelog_body_from.into());
+        }
         if let Some(changelog_path) = value.changelog_path {
             cfg = cfg.with_changelog_path(to_utf8_pathbuf(changelog_path).unwrap());
         }
         if let Some(no_verify) = value.publish_no_verify {
             cfg = cfg.with_no_verify(no_verify);
         }
+        if let Some(locked) = value.publish_locked {
+            cfg = cfg.with_locked(locked);
+        }
         if let Some(features) = value.publish_features {
             cfg = cfg.with_features(features);
         }
         if let Some(all_features) = value.publish_all_features {
             cfg = cfg.with_all_features(all_features);
         }
+        if let Some(target) = value.publish_target {
+            cfg = cfg.with_target(target);
+        }
         if let Some(allow_dirty) = value.publish_allow_dirty {
             cfg = cfg.with_allow_dirty(allow_dirty);
         }
+        if let Some(verify_command) = value.verify_command {
+            cfg = cfg.with_verify_command(verify_command);
+        }
         cfg
     }
 }
@@ -372,14 +818,24 @@ fn git_release(config: &PackageConfig) -> GitReleaseConfig {
     let is_git_release_draft = config.git_release_draft == Some(true);
     let git_release_name = config.git_release_name.clone();
     let git_release_body = config.git_release_body.clone();
+    let require_changelog_body = config.git_release_require_changelog_body == Some(true);
     let mut git_release = release_plz_core::GitReleaseConfig::enabled(is_git_release_enabled)
         .set_draft(is_git_release_draft)
         .set_release_type(git_release_type)
         .set_name_template(git_release_name)
-        .set_body_template(git_release_body);
-
-    if config.git_release_latest == Some(false) {
-        git_release = git_release.set_latest(false);
+        .set_body_template(git_release_body)
+        .set_require_changelog_body(require_changelog_body)
+        .set_sbom(config.git_release_sbom == Some(true));
+
+    match config.git_release_latest {
+        Some(GitReleaseLatest::Enabled(false)) => {
+            git_release =
+                git_release.set_latest(release_plz_core::GitReleaseLatest::Enabled(false));
+        }
+        Some(GitReleaseLatest::Mode(LatestMode::Auto)) => {
+            git_release = git_release.set_latest(release_plz_core::GitReleaseLatest::Auto);
+        }
+        Some(GitReleaseLatest::Enabled(true)) | None => {}
     }
 
     git_release
@@ -398,6 +854,30 @@ pub struct PackageConfig {
     /// Whether to create/update changelog or not.
     /// If unspecified, the changelog is updated.
     pub changelog_update: Option<bool>,
+    /// # Changelog Body From
+    /// Source of the changes used in the git release body.
+    /// - `file` (default): the last entry of the changelog file.
+    /// - `commits`: the commits since the previous release, even if `changelog_update` is `false`.
+    pub changelog_body_from: Option<ChangelogBodyFrom>,
+    /// # Changelog Link Type
+    /// What the link of the version heading of the changelog points to.
+    /// If unspecified, the link compares the previous tag with the new one.
+    pub changelog_link_type: Option<ChangelogLinkType>,
+    /// # Changelog Protect Manual Edits
+    /// If true, fail when the changelog already contains an entry for the next version
+    /// that misses some of the new changes, because the entry was probably edited manually.
+    /// If false (default), the new changes aren't added to the existing entry.
+    pub changelog_protect_manual_edits: Option<bool>,
+    /// # Changelog Max Entries
+    /// Maximum number of releases to keep in the changelog.
+    /// Older releases are removed from the changelog when adding a new one.
+    /// If unspecified, all releases are kept.
+    pub changelog_max_entries: Option<usize>,
+    /// # Changelog Archive
+    /// - If `true`, the releases removed because of `changelog_max_entries` are moved to
+    ///   an archive next to the changelog, e.g. `CHANGELOG-archive.md`.
+    /// - If `false` (default), the releases are deleted.
+    pub changelog_archive: Option<bool>,
     /// # Features Always Increment Minor Version
     /// - If `true`, feature commits will always bump the minor version, even in 0.x releases.
     /// - If `false` (default), feature commits will only bump the minor version starting with 1.x releases.
@@ -408,6 +888,23 @@ pub struct PackageConfig {
     /// is (i.e newest version is v0.1.3 and is associated with commit ac83762).
     /// If false (default), release-plz will use the cargo registry (e.g. crates.io) to get the latest version.
     pub git_only: Option<bool>,
+    /// # Previous Tag Patterns
+    /// Tera templates of git tags created before adopting release-plz,
+    /// e.g. `["v{{ version }}"]`.
+    /// Used in `git_only` mode, together with [`Self::git_tag_name`], to find the
+    /// latest released version. The highest version across all the templates wins.
+    pub previous_tag_patterns: Option<Vec<String>>,
+    /// # Git Only Reachable Tags Only
+    /// - If `true`, in `git_only` mode, the latest release is the highest tag reachable
+    ///   from the current commit, e.g. to release patches from a maintenance branch.
+    /// - If `false` (default), the latest release is the highest tag of the repository.
+    pub git_only_reachable_tags_only: Option<bool>,
+    /// # Compare Ignore Files
+    /// Globs of the files to ignore when comparing the local package with the released one,
+    /// e.g. `["schema.json", "generated/**"]`.
+    /// Paths are relative to the package root.
+    /// Changes to these files don't trigger a new release.
+    pub compare_ignore_files: Option<Vec<String>>,
     /// # Git Release Enable
     /// Publish the GitHub/Gitea/GitLab release for the created git tag.
     /// Enabled by default.
@@ -415,6 +912,11 @@ pub struct PackageConfig {
     /// # Git Release Body
     /// Tera template of the git release body created by release-plz.
     pub git_release_body: Option<String>,
+    /// # Git Release Require Changelog Body
+    /// If true, fail the release instead of creating a git release with an empty body,
+    /// e.g. because the changelog can't be parsed.
+    /// If false (default), the git release is created with an empty body.
+    pub git_release_require_changelog_body: Option<bool>,
     /// # Git Release Type
     /// Whether to mark the created release as not ready for production.
     pub git_release_type: Option<ReleaseType>,
@@ -423,7 +925,12 @@ pub struct PackageConfig {
     pub git_release_draft: Option<bool>,
     /// # Git Release Latest
     /// If true, will set the git release as latest.
-    pub git_release_latest: Option<bool>,
+    /// If `"auto"`, will set the git release as latest only if it has the highest version.
+    pub git_release_latest: Option<GitReleaseLatest>,
+    /// # Git Release SBOM
+    /// If true, attach a `CycloneDX` SBOM of the package to the git release.
+    /// Disabled by default.
+    pub git_release_sbom: Option<bool>,
     /// # Git Release Name
     /// Tera template of the git release name created by release-plz.
     pub git_release_name: Option<String>,
@@ -434,6 +941,10 @@ pub struct PackageConfig {
     /// # Git Tag Name
     /// Tera template of the git tag name created by release-plz.
     pub git_tag_name: Option<String>,
+    /// # Git Tag Annotated
+    /// - If `true` or [`Option::None`], create annotated git tags, which contain a message.
+    /// - If `false`, create lightweight git tags, without message.
+    pub git_tag_annotated: Option<bool>,
     /// # Publish
     /// If `false`, don't run `cargo publish`.
     pub publish: Option<bool>,
@@ -443,16 +954,56 @@ pub struct PackageConfig {
     /// # Publish No Verify
     /// If `true`, add the `--no-verify` flag to the `cargo publish` command.
     pub publish_no_verify: Option<bool>,
+    /// # Publish Locked
+    /// If `true`, add the `--locked` flag to the `cargo publish` command and to the
+    /// cargo commands of `verify_command`, so that the release fails if the
+    /// `Cargo.lock` file is out of date.
+    pub publish_locked: Option<bool>,
     /// # Publish Features
     /// If `["a", "b", "c"]`, add the `--features=a,b,c` flag to the `cargo publish` command.
+    /// The features are also enabled in `cargo package` and cargo-semver-checks.
     pub publish_features: Option<Vec<String>>,
     /// # Publish All Features
     /// If `true`, add the `--all-features` flag to the `cargo publish` command.
+    /// The flag is also passed to `cargo package` and cargo-semver-checks.
     pub publish_all_features: Option<bool>,
+    /// # Publish Target
+    /// If set, add the `--target <triple>` flag to the `cargo publish` command,
+    /// so that the package is verified for that target, e.g. `"wasm32-unknown-unknown"`.
+    /// It can't be used together with `publish_no_verify`.
+    pub publish_target: Option<String>,
+    /// # Verify Command
+    /// Command run in the package directory before publishing the package,
+    /// e.g. `["cargo", "check", "--all-targets"]`.
+    /// If the command fails, the release fails.
+    pub verify_command: Option<Vec<String>>,
+    /// # Publish Registries
+    /// Names of the registries where the package can be published, e.g. `["crates-io"]`.
+    /// Registries of the `publish` field of the package manifest that aren't listed are skipped.
+    /// If unspecified, publish to all the registries of the package.
+    pub publish_registries: Option<Vec<String>>,
+    /// # Publish Exclude Registries
+    /// Names of the registries where the package must not be published.
+    pub publish_exclude_registries: Option<Vec<String>>,
     /// # Semver Check
     /// Controls when to run cargo-semver-checks.
     /// If unspecified, run cargo-semver-checks if the package is a library.
     pub semver_check: Option<bool>,
+    /// # Semver Check Skip If Only
+    /// Skip cargo-semver-checks if all the new commits of the package are conventional commits
+    /// of these types, e.g. `["docs", "chore", "ci"]`.
+    /// Breaking changes are never skipped.
+    /// If unspecified, cargo-semver-checks always runs.
+    pub semver_check_skip_if_only: Option<Vec<String>>,
+    /// # Semver Check Skip Pre 1.0
+    /// Skip cargo-semver-checks if the current version of the package is lower than `1.0.0`.
+    /// If unspecified, cargo-semver-checks runs for `0.x` versions, too.
+    pub semver_check_skip_pre_1_0: Option<bool>,
+    /// # Semver Check Enforce
+    /// Pass the planned release type to cargo-semver-checks and act when the API changes
+    /// require a bigger version bump.
+    /// If unspecified, only breaking changes are detected and they bump the version.
+    pub semver_check_enforce: Option<SemverCheckEnforce>,
     /// # Release
     /// Used to toggle off the update/release process for a workspace or package.
     pub release: Option<bool>,
@@ -464,21 +1015,44 @@ pub struct PackageConfig {
     /// Custom regex to match commit types that should trigger a major version increment.
     /// Useful when using non-conventional commit prefixes.
     pub custom_major_increment_regex: Option<String>,
+    /// # Version Metadata Template
+    /// Tera template of the build metadata appended to the new version of the package,
+    /// e.g. `{{ date }}` turns `1.2.3` into `1.2.3+20240520`.
+    /// crates.io rejects versions with build metadata, so use it only with private registries.
+    pub version_metadata_template: Option<String>,
 }
 
 impl From<PackageConfig> for release_plz_core::UpdateConfig {
     fn from(config: PackageConfig) -> Self {
         Self {
             semver_check: config.semver_check != Some(false),
+            semver_check_skip_if_only: config.semver_check_skip_if_only.unwrap_or_default(),
+            semver_check_skip_pre_1_0: config.semver_check_skip_pre_1_0 == Some(true),
+            semver_check_enforce: config.semver_check_enforce.map(Into::into),
             changelog_update: config.changelog_update != Some(false),
+            changelog_link_type: config
+                .changelog_link_type
+                .map(Into::into)
+                .unwrap_or_default(),
+            changelog_protect_manual_edits: config.changelog_protect_manual_edits == Some(true),
+            changelog_max_entries: config.changelog_max_entries,
+            changelog_archive: config.changelog_archive == Some(true),
             release: config.release != Some(false),
             publish: config.publish != Some(false),
             tag_name_template: config.git_tag_name,
+            // The prefix is a workspace setting. See `Config::tag_prefix`.
+            tag_prefix: String::new(),
             features_always_increment_minor: config.features_always_increment_minor == Some(true),
             changelog_path: config.changelog_path.map(|p| to_utf8_pathbuf(p).unwrap()),
             custom_minor_increment_regex: config.custom_minor_increment_regex,
             custom_major_increment_regex: config.custom_major_increment_regex,
+            version_metadata_template: config.version_metadata_template,
             git_only: config.git_only,
+            previous_tag_patterns: config.previous_tag_patterns.unwrap_or_default(),
+            git_only_reachable_tags_only: config.git_only_reachable_tags_only == Some(true),
+            compare_ignore_files: config.compare_ignore_files.unwrap_or_default(),
+            features: config.publish_features.unwrap_or_default(),
+            all_features: config.publish_all_features == Some(true),
         }
     }
 }
@@ -488,7 +1062,11 @@ impl From<PackageSpecificConfig> for release_plz_core::PackageUpdateConfig {
         Self {
             generic: config.common.into(),
             changelog_include: config.changelog_include.unwrap_or_default(),
+            changelog_include_paths: config.changelog_include_paths.unwrap_or_default(),
             version_group: config.version_group,
+            force_bump: config.force_bump.map(Into::into),
+            min_version: None,
+            release_on_dependency_change: config.release_on_dependency_change != Some(false),
         }
     }
 }
@@ -498,8 +1076,22 @@ impl PackageConfig {
     pub fn merge(self, default: Self) -> Self {
         Self {
             semver_check: self.semver_check.or(default.semver_check),
+            semver_check_skip_if_only: self
+                .semver_check_skip_if_only
+                .or(default.semver_check_skip_if_only),
+            semver_check_skip_pre_1_0: self
+                .semver_check_skip_pre_1_0
+                .or(default.semver_check_skip_pre_1_0),
+            semver_check_enforce: self.semver_check_enforce.or(default.semver_check_enforce),
             changelog_path: self.changelog_path.or(default.changelog_path),
             changelog_update: self.changelog_update.or(default.changelog_update),
+            changelog_body_from: self.changelog_body_from.or(default.changelog_body_from),
+            changelog_link_type: self.changelog_link_type.or(default.changelog_link_type),
+            changelog_protect_manual_edits: self
+                .changelog_protect_manual_edits
+                .or(default.changelog_protect_manual_edits),
+            changelog_max_entries: self.changelog_max_entries.or(default.changelog_max_entries),
+            changelog_archive: self.changelog_archive.or(default.changelog_archive),
             features_always_increment_minor: self
                 .features_always_increment_minor
                 .or(default.features_always_increment_minor),
@@ -507,16 +1099,28 @@ impl PackageConfig {
             git_release_type: self.git_release_type.or(default.git_release_type),
             git_release_draft: self.git_release_draft.or(default.git_release_draft),
             git_release_latest: self.git_release_latest.or(default.git_release_latest),
+            git_release_sbom: self.git_release_sbom.or(default.git_release_sbom),
             git_release_name: self.git_release_name.or(default.git_release_name),
             git_release_body: self.git_release_body.or(default.git_release_body),
+            git_release_require_changelog_body: self
+                .git_release_require_changelog_body
+                .or(default.git_release_require_changelog_body),
 
             publish: self.publish.or(default.publish),
             publish_allow_dirty: self.publish_allow_dirty.or(default.publish_allow_dirty),
             publish_no_verify: self.publish_no_verify.or(default.publish_no_verify),
+            publish_locked: self.publish_locked.or(default.publish_locked),
+            verify_command: self.verify_command.or(default.verify_command),
             publish_features: self.publish_features.or(default.publish_features),
             publish_all_features: self.publish_all_features.or(default.publish_all_features),
+            publish_target: self.publish_target.or(default.publish_target),
+            publish_registries: self.publish_registries.or(default.publish_registries),
+            publish_exclude_registries: self
+                .publish_exclude_registries
+                .or(default.publish_exclude_registries),
             git_tag_enable: self.git_tag_enable.or(default.git_tag_enable),
             git_tag_name: self.git_tag_name.or(default.git_tag_name),
+            git_tag_annotated: self.git_tag_annotated.or(default.git_tag_annotated),
             release: self.release.or(default.release),
             custom_minor_increment_regex: self
                 .custom_minor_increment_regex
@@ -524,7 +1128,15 @@ impl PackageConfig {
             custom_major_increment_regex: self
                 .custom_major_increment_regex
                 .or(default.custom_major_increment_regex),
+            version_metadata_template: self
+                .version_metadata_template
+                .or(default.version_metadata_template),
             git_only: self.git_only.or(default.git_only),
+            previous_tag_patterns: self.previous_tag_patterns.or(default.previous_tag_patterns),
+            git_only_reachable_tags_only: self
+                .git_only_reachable_tags_only
+                .or(default.git_only_reachable_tags_only),
+            compare_ignore_files: self.compare_ignore_files.or(default.compare_ignore_files),
         }
     }
 
@@ -563,6 +1175,228 @@ impl From<ReleaseType> for release_plz_core::ReleaseType {
     }
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ForceBump {
+    /// # Major
+    /// Increment the major version, e.g. `0.3.1` -> `1.0.0`.
+    Major,
+    /// # Minor
+    /// Increment the minor version, e.g. `0.3.1` -> `0.4.0`.
+    Minor,
+    /// # Patch
+    /// Increment the patch version, e.g. `0.3.1` -> `0.3.2`.
+    Patch,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogBodyFrom {
+    /// # File
+    /// Last entry of the changelog file.
+    File,
+    /// # Commits
+    /// Commits since the previous release of the package.
+    Commits,
+}
+
+impl From<ChangelogBodyFrom> for release_plz_core::ChangelogBodyFrom {
+    fn from(value: ChangelogBodyFrom) -> Self {
+        match value {
+            ChangelogBodyFrom::File => Self::File,
+            ChangelogBodyFrom::Commits => Self::Commits,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogLinkType {
+    /// # Compare
+    /// Compare the previous tag with the new one.
+    /// For the first release, link to the page of the new tag.
+    Compare,
+    /// # Tag
+    /// Link to the page of the new tag.
+    Tag,
+    /// # None
+    /// Don't link the version heading.
+    None,
+}
+
+impl From<ChangelogLinkType> for release_plz_core::ChangelogLinkType {
+    fn from(value: ChangelogLinkType) -> Self {
+        match value {
+            ChangelogLinkType::Compare => Self::Compare,
+            ChangelogLinkType::Tag => Self::Tag,
+            ChangelogLinkType::None => Self::None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogOutputFormat {
+    /// # Markdown
+    /// Render the changelog with the changelog template.
+    Markdown,
+    /// # Json
+    /// Write the releases as JSON to the changelog path with the `.json` extension.
+    Json,
+}
+
+impl From<ChangelogOutputFormat> for release_plz_core::ChangelogOutputFormat {
+    fn from(value: ChangelogOutputFormat) -> Self {
+        match value {
+            ChangelogOutputFormat::Markdown => Self::Markdown,
+            ChangelogOutputFormat::Json => Self::Json,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SemverCheckEnforce {
+    /// # Fail
+    /// Fail if the API changes require a bigger version bump than the planned one.
+    Fail,
+    /// # Bump
+    /// Increment the version as required by the API changes.
+    Bump,
+}
+
+impl From<SemverCheckEnforce> for release_plz_core::SemverCheckEnforce {
+    fn from(value: SemverCheckEnforce) -> Self {
+        match value {
+            SemverCheckEnforce::Fail => Self::Fail,
+            SemverCheckEnforce::Bump => Self::Bump,
+        }
+    }
+}
+
+impl From<ForceBump> for release_plz_core::ForceBump {
+    fn from(value: ForceBump) -> Self {
+        match value {
+            ForceBump::Major => Self::Major,
+            ForceBump::Minor => Self::Minor,
+            ForceBump::Patch => Self::Patch,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum PrBodyFooter {
+    /// # Enabled
+    /// `true` uses the default footer, `false` omits the footer.
+    Enabled(bool),
+    /// # Template
+    /// Tera template of the footer.
+    Template(String),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum PrLabel {
+    /// # Name
+    /// Name of the label.
+    Name(String),
+    /// # Styled
+    /// Label with the color and description used when release-plz creates it.
+    Styled(StyledPrLabel),
+}
+
+impl PrLabel {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) => name,
+            Self::Styled(label) => &label.name,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StyledPrLabel {
+    /// # Name
+    /// Name of the label.
+    pub name: String,
+    /// # Color
+    /// Color of the label as 6 hex digits, without `#`, e.g. `5319e7`.
+    pub color: Option<String>,
+    /// # Description
+    /// Description of the label.
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(untagged)]
+pub enum GitReleaseLatest {
+    /// # Enabled
+    /// `true` sets the git release as latest, `false` doesn't.
+    Enabled(bool),
+    /// # Mode
+    /// Strategy used to decide whether the git release is the latest one.
+    Mode(LatestMode),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LatestMode {
+    /// # Auto
+    /// Will set the git release as latest only if its version is the highest
+    /// among the released packages and the existing git tags.
+    /// Pre-releases are never set as latest.
+    Auto,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, JsonSchema)]
+#[serde(untagged)]
+pub enum DependenciesUpdate {
+    /// # Enabled
+    /// `true` updates all the dependencies, `false` only updates the workspace packages.
+    Enabled(bool),
+    /// # Scope
+    /// Dependencies to update.
+    Scope(DependenciesUpdateScope),
+    /// # Packages
+    /// Names of the packages to update, e.g. `["serde", "tokio"]`.
+    Packages(#[schemars(length(min = 1))] Vec<String>),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependenciesUpdateScope {
+    /// # Workspace
+    /// Only update the workspace packages.
+    Workspace,
+    /// # All
+    /// Update all the dependencies.
+    All,
+}
+
+impl From<DependenciesUpdate> for release_plz_core::update_request::DependenciesUpdate {
+    fn from(value: DependenciesUpdate) -> Self {
+        match value {
+            DependenciesUpdate::Enabled(false)
+            | DependenciesUpdate::Scope(DependenciesUpdateScope::Workspace) => Self::Workspace,
+            DependenciesUpdate::Enabled(true)
+            | DependenciesUpdate::Scope(DependenciesUpdateScope::All) => Self::All,
+            DependenciesUpdate::Packages(packages) => Self::Packages(packages),
+        }
+    }
+}
+
+impl From<PrBodyFooter> for release_plz_core::PrBodyFooter {
+    fn from(value: PrBodyFooter) -> Self {
+        match value {
+            PrBodyFooter::Enabled(true) => Self::Enabled,
+            PrBodyFooter::Enabled(false) => Self::Disabled,
+            PrBodyFooter::Template(template) => Self::Custom(template),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -590,8 +1424,12 @@ mod tests {
         Config {
             changelog: ChangelogCfg::default(),
             workspace: Workspace {
-                dependencies_update: Some(false),
+                dependencies_update: Some(DependenciesUpdate::Enabled(false)),
+                update_cargo_lock: None,
                 changelog_config: Some("../git-cliff.toml".into()),
+                changelog_show_authors: None,
+                changelog_strip_pr_suffix: None,
+                changelog_output_format: None,
                 allow_dirty: Some(false),
                 repo_url: Some(
                     "https://github.com/release-plz/release-plz"
@@ -607,13 +1445,38 @@ mod tests {
                     ..Default::default()
                 },
                 pr_name: None,
+                pr_name_path: None,
                 pr_body: None,
+                pr_body_path: None,
+                pr_body_footer: None,
                 pr_draft: false,
                 pr_labels: vec![],
+                template_context: BTreeMap::new(),
                 pr_branch_prefix: Some("f-".to_string()),
+                pr_head_repo: None,
+                release_pr_branch_regex: None,
+                base_branch: None,
+                git_author_name: None,
+                git_author_email: None,
                 publish_timeout: Some("10m".to_string()),
+                registry_token_command: None,
                 release_commits: Some("^feat:".to_string()),
                 release_always: None,
+                comment_on_release: None,
+                single_tag: None,
+                combined_git_release: None,
+                combined_git_release_only: None,
+                combined_git_release_tag_name: None,
+                tag_prefix: None,
+                verify_published_checksum: None,
+                skip_publish_wait: None,
+                fetch_contributors: None,
+                dependency_update_message_template: None,
+                changelog_filenames: None,
+                changelog_unreleased_heading: None,
+                changelog_exclude_merge_commits: false,
+                format_manifests_command: None,
+                infer_baseline_from_lock: false,
                 max_analyze_commits: default_max_analyze_commits(),
             },
             package: [].into(),
@@ -633,11 +1496,112 @@ mod tests {
                     ..Default::default()
                 },
                 changelog_include: None,
+                changelog_include_paths: None,
                 version_group: None,
+                force_bump: None,
+                min_version: None,
+                release_on_dependency_change: None,
             },
         }
     }
 
+    #[test]
+    fn tag_prefix_is_not_used_by_packages_with_custom_tag_name() {
+        let mut config = create_base_workspace_config();
+        config.workspace.tag_prefix = Some("myproduct-".to_string());
+        let mut package_config = create_base_package_config().config;
+        assert_eq!(config.tag_prefix(None), "myproduct-");
+        assert_eq!(config.tag_prefix(Some(&package_config)), "myproduct-");
+        package_config.common.git_tag_name = Some("v{{ version }}".to_string());
+        assert_eq!(config.tag_prefix(Some(&package_config)), "");
+    }
+
+    #[test]
+    fn packages_config_is_resolved() {
+        let mut config = create_base_workspace_config();
+        let mut package_config = create_base_package_config();
+        package_config.config.common.git_release_draft = Some(true);
+        package_config.config.version_group = Some("group".to_string());
+        config.package = vec![package_config];
+
+        let resolved = config.resolved_packages_config(&["crate1", "crate2"]);
+
+        let crate1 = &resolved["crate1"];
+        assert_eq!(crate1.common.git_release_draft, Some(true));
+        assert_eq!(crate1.common.git_release_enable, Some(true));
+        assert_eq!(crate1.version_group.as_deref(), Some("group"));
+        let crate2 = &resolved["crate2"];
+        assert_eq!(crate2.common, config.workspace.packages_defaults);
+        assert_eq!(crate2.version_group, None);
+    }
+
+    #[test]
+    fn glob_package_config_is_resolved() {
+        let mut config = create_base_workspace_config();
+        let package_config = |name: &str, version_group: &str| {
+            let mut package_config = create_base_package_config();
+            package_config.name = name.to_string();
+            package_config.config.version_group = Some(version_group.to_string());
+            package_config
+        };
+        config.package = vec![
+            package_config("acme-*", "acme"),
+            package_config("acme-cli-*", "acme-cli"),
+            package_config("acme-*-*", "acme-2"),
+            package_config("acme-cli-core", "exact"),
+        ];
+
+        let version_group = |name: &str| {
+            config
+                .package_config(name)
+                .and_then(|c| c.version_group.as_deref())
+        };
+        assert_eq!(version_group("acme-core"), Some("acme"));
+        assert_eq!(version_group("acme-cli-utils"), Some("acme-cli"));
+        assert_eq!(version_group("acme-cli-core"), Some("exact"));
+        assert_eq!(version_group("acme-web-core"), Some("acme-2"));
+        assert_eq!(version_group("other"), None);
+        config
+            .check_package_names(&["acme-core", "acme-cli-utils", "acme-cli-core"])
+            .unwrap();
+        config.package.truncate(1);
+        expect_test::expect!["the `[[package]]` sections of the release-plz config refer to packages that aren't part of the workspace: acme-*. Packages of the workspace: other"]
+            .assert_eq(&config.check_package_names(&["other"]).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn invalid_package_glob_is_rejected() {
+        let mut config = create_base_workspace_config();
+        let mut package_config = create_base_package_config();
+        package_config.name = "acme-[*".to_string();
+        config.package = vec![package_config];
+
+        expect_test::expect!["invalid glob pattern `acme-[*` in `[[package]]` name"].assert_eq(
+            &config
+                .check_package_name_patterns()
+                .unwrap_err()
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn unknown_package_names_are_reported() {
+        let mut config = create_base_workspace_config();
+        let mut typo = create_base_package_config();
+        typo.name = "crat1".to_string();
+        config.package = vec![create_base_package_config(), typo];
+
+        expect_test::expect!["the `[[package]]` sections of the release-plz config refer to packages that aren't part of the workspace: crat1. Packages of the workspace: crate1, crate2"]
+            .assert_eq(
+                &config
+                    .check_package_names(&["crate2", "crate1"])
+                    .unwrap_err()
+                    .to_string(),
+            );
+        config.package.pop();
+        config.check_package_names(&["crate1", "crate2"]).unwrap();
+    }
+
     #[test]
     fn config_without_update_config_is_deserialized() {
         let expected_config = create_base_workspace_config();
@@ -660,6 +1624,150 @@ mod tests {
         assert_eq!(config, expected_config);
     }
 
+    #[test]
+    fn pr_body_footer_is_deserialized() {
+        for (config_value, expected) in [
+            ("false", PrBodyFooter::Enabled(false)),
+            (
+                r#""released with {{ releases | length }} packages""#,
+                PrBodyFooter::Template("released with {{ releases | length }} packages".into()),
+            ),
+        ] {
+            let config = &format!(
+                "{BASE_WORKSPACE_CONFIG}\
+                pr_body_footer = {config_value}"
+            );
+            let config: Config = toml::from_str(config).unwrap();
+            assert_eq!(config.workspace.pr_body_footer, Some(expected));
+        }
+    }
+
+    #[test]
+    fn git_release_latest_is_deserialized() {
+        for (config_value, expected) in [
+            ("false", GitReleaseLatest::Enabled(false)),
+            (r#""auto""#, GitReleaseLatest::Mode(LatestMode::Auto)),
+        ] {
+            let config = &format!(
+                "{BASE_WORKSPACE_CONFIG}\
+                git_release_latest = {config_value}"
+            );
+            let config: Config = toml::from_str(config).unwrap();
+            assert_eq!(
+                config.workspace.packages_defaults.git_release_latest,
+                Some(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn dependencies_update_is_deserialized() {
+        for (config_value, expected) in [
+            ("true", DependenciesUpdate::Enabled(true)),
+            (
+                r#""workspace""#,
+                DependenciesUpdate::Scope(DependenciesUpdateScope::Workspace),
+            ),
+            (
+                r#"["serde", "tokio"]"#,
+                DependenciesUpdate::Packages(vec!["serde".to_string(), "tokio".to_string()]),
+            ),
+        ] {
+            let config = &format!(
+                "[workspace]\n\
+                dependencies_update = {config_value}"
+            );
+            let config: Config = toml::from_str(config).unwrap();
+            assert_eq!(config.workspace.dependencies_update, Some(expected));
+        }
+    }
+
+    #[test]
+    fn update_dependencies_is_an_alias_of_dependencies_update() {
+        let config = "[workspace]\n\
+            update_dependencies = \"all\"";
+        let config: Config = toml::from_str(config).unwrap();
+        assert_eq!(
+            config.workspace.dependencies_update,
+            Some(DependenciesUpdate::Scope(DependenciesUpdateScope::All))
+        );
+    }
+
+    #[test]
+    fn empty_dependencies_update_list_is_rejected() {
+        let config = "[workspace]\n\
+            dependencies_update = []";
+        let config: Config = toml::from_str(config).unwrap();
+        expect_test::expect![[r#"the list of packages of `dependencies_update` is empty. Use `dependencies_update = "workspace"` to only update the workspace packages"#]]
+            .assert_eq(&config.check_dependencies_update().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn force_bump_is_deserialized() {
+        let config = "[[package]]\n\
+            name = \"crate1\"\n\
+            force_bump = \"minor\"";
+        let config: Config = toml::from_str(config).unwrap();
+        assert_eq!(config.package[0].config.force_bump, Some(ForceBump::Minor));
+    }
+
+    #[test]
+    fn publish_target_conflicts_with_no_verify() {
+        let config = "[[package]]\n\
+            name = \"release-plz\"\n\
+            publish_target = \"wasm32-unknown-unknown\"";
+        let config: Config = toml::from_str(config).unwrap();
+        let release_request = || ReleaseRequest::new(fake_package::metadata::fake_metadata());
+        config
+            .fill_release_config(false, false, release_request())
+            .unwrap();
+        let error = config
+            .fill_release_config(false, true, release_request())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Wrong configuration of package release-plz"
+        );
+    }
+
+    #[test]
+    fn workspace_publish_false_disables_publish_of_all_packages() {
+        let config = "[workspace]\n\
+            publish = false\n\
+            [[package]]\n\
+            name = \"release-plz\"\n\
+            changelog_update = true";
+        let config: Config = toml::from_str(config).unwrap();
+        let request = config
+            .fill_release_config(
+                false,
+                false,
+                ReleaseRequest::new(fake_package::metadata::fake_metadata()),
+            )
+            .unwrap();
+        for package in ["release-plz", "package-without-config"] {
+            let package_config = request.get_package_config(package);
+            assert!(!package_config.publish().is_enabled(), "{package}");
+            assert!(package_config.git_release().is_enabled(), "{package}");
+        }
+    }
+
+    #[test]
+    fn invalid_min_version_is_rejected() {
+        let config = "[[package]]\n\
+            name = \"release-plz\"\n\
+            min_version = \"1.x\"";
+        let config: Config = toml::from_str(config).unwrap();
+        let update_request = UpdateRequest::new(fake_package::metadata::fake_metadata()).unwrap();
+        let error = config
+            .fill_update_config(false, update_request)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid `min_version` of package release-plz"
+        );
+    }
+
     fn config_package_release_is_deserialized(config_flag: &str, expected_value: bool) {
         let config = &format!(
             "{BASE_WORKSPACE_CONFIG}\n{BASE_PACKAGE_CONFIG}\
@@ -714,7 +1822,11 @@ mod tests {
             changelog: ChangelogCfg::default(),
             workspace: Workspace {
                 dependencies_update: None,
+                update_cargo_lock: None,
                 changelog_config: Some("../git-cliff.toml".into()),
+                changelog_show_authors: None,
+                changelog_strip_pr_suffix: None,
+                changelog_output_format: None,
                 allow_dirty: None,
                 repo_url: Some(
                     "https://github.com/release-plz/release-plz"
@@ -722,10 +1834,19 @@ mod tests {
                         .unwrap(),
                 ),
                 pr_name: None,
+                pr_name_path: None,
                 pr_body: None,
+                pr_body_path: None,
+                pr_body_footer: None,
                 pr_draft: false,
-                pr_labels: vec!["label1".to_string()],
+                pr_labels: vec![PrLabel::Name("label1".to_string())],
+                template_context: BTreeMap::new(),
                 pr_branch_prefix: Some("f-".to_string()),
+                pr_head_repo: None,
+                release_pr_branch_regex: None,
+                base_branch: None,
+                git_author_name: None,
+                git_author_email: None,
                 packages_defaults: PackageConfig {
                     semver_check: None,
                     changelog_update: true.into(),
@@ -737,8 +1858,24 @@ mod tests {
                     ..Default::default()
                 },
                 publish_timeout: Some("10m".to_string()),
+                registry_token_command: None,
                 release_commits: Some("^feat:".to_string()),
                 release_always: None,
+                comment_on_release: None,
+                single_tag: None,
+                combined_git_release: None,
+                combined_git_release_only: None,
+                combined_git_release_tag_name: None,
+                tag_prefix: None,
+                verify_published_checksum: None,
+                skip_publish_wait: None,
+                fetch_contributors: None,
+                dependency_update_message_template: None,
+                changelog_filenames: None,
+                changelog_unreleased_heading: None,
+                changelog_exclude_merge_commits: false,
+                format_manifests_command: None,
+                infer_baseline_from_lock: false,
                 max_analyze_commits: default_max_analyze_commits(),
             },
             package: [PackageSpecificConfigWithName {
@@ -754,7 +1891,11 @@ mod tests {
                         ..Default::default()
                     },
                     changelog_include: Some(vec!["pkg1".to_string()]),
+                    changelog_include_paths: None,
                     version_group: None,
+                    force_bump: None,
+                    min_version: None,
+                    release_on_dependency_change: None,
                 },
             }]
             .into(),
@@ -775,6 +1916,8 @@ mod tests {
             publish_timeout = "10m"
             repo_url = "https://github.com/release-plz/release-plz"
             release_commits = "^feat:"
+            changelog_exclude_merge_commits = false
+            infer_baseline_from_lock = false
             max_analyze_commits = 1000
 
             [changelog]
@@ -792,6 +1935,26 @@ mod tests {
         .assert_eq(&toml::to_string(&config).unwrap());
     }
 
+    #[test]
+    fn pr_labels_with_style_are_deserialized() {
+        let config = r#"
+            [workspace]
+            pr_labels = ["bot", { name = "release", color = "5319e7", description = "Release PR" }]
+        "#;
+        let config: Config = toml::from_str(config).unwrap();
+        assert_eq!(
+            config.workspace.pr_labels,
+            [
+                PrLabel::Name("bot".to_string()),
+                PrLabel::Styled(StyledPrLabel {
+                    name: "release".to_string(),
+                    color: Some("5319e7".to_string()),
+                    description: Some("Release PR".to_string()),
+                }),
+            ]
+        );
+    }
+
     #[test]
     fn wrong_config_section_is_not_deserialized() {
         let config = "[unknown]";
@@ -825,6 +1988,22 @@ allow_dirty = true";
         .assert_eq(&error);
     }
 
+    #[test]
+    fn wrong_commit_parser_is_not_deserialized() {
+        let config = r#"
+[changelog]
+commit_parsers = [{ mesage = "^feat", group = "added" }]"#;
+
+        let error = toml::from_str::<Config>(config).unwrap_err().to_string();
+        expect_test::expect![[r#"
+            TOML parse error at line 3, column 21
+              |
+            3 | commit_parsers = [{ mesage = "^feat", group = "added" }]
+              |                     ^^^^^^
+            unknown field `mesage`, expected one of `message`, `body`, `group`, `default_scope`, `scope`, `skip`, `field`, `pattern`, `sha`
+        "#]].assert_eq(&error);
+    }
+
     #[test]
     fn wrong_changelog_section_is_not_deserialized() {
         let config = r"
@@ -838,7 +2017,7 @@ unknown = false";
               |
             4 | unknown = false
               | ^^^^^^^
-            unknown field `unknown`, expected one of `header`, `body`, `trim`, `commit_preprocessors`, `postprocessors`, `sort_commits`, `link_parsers`, `commit_parsers`, `protect_breaking_commits`, `tag_pattern`
+            unknown field `unknown`, expected one of `header`, `body`, `trim`, `commit_preprocessors`, `postprocessors`, `sort_commits`, `link_parsers`, `commit_parsers`, `protect_breaking_commits`, `require_conventional`, `tag_pattern`
         "]]
         .assert_eq(&error);
     }
@@ -861,6 +2040,40 @@ unknown = false"#;
         .assert_eq(&error);
     }
 
+    #[test]
+    fn pr_body_template_is_read_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let body_path = dir.path().join("release-pr-body.tera");
+        fs_err::write(&body_path, "{{ releases }}").unwrap();
+        let workspace = Workspace {
+            pr_body_path: Some(body_path),
+            ..Default::default()
+        };
+        assert_eq!(
+            workspace.pr_body_template().unwrap().as_deref(),
+            Some("{{ releases }}")
+        );
+
+        let workspace = Workspace {
+            pr_body: Some("inline".to_string()),
+            ..workspace
+        };
+        let error = workspace.pr_body_template().unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "`pr_body` and `pr_body_path` can't be set at the same time"
+        );
+    }
+
+    #[test]
+    fn missing_pr_name_template_file_is_reported() {
+        let workspace = Workspace {
+            pr_name_path: Some(PathBuf::from("non-existent-file.tera")),
+            ..Default::default()
+        };
+        assert!(workspace.pr_name_template().is_err());
+    }
+
     #[test]
     fn test_parse_duration() {
         assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
@@ -881,6 +2094,27 @@ unknown = false"#;
         );
     }
 
+    #[test]
+    fn git_identity_of_cli_overrides_config() {
+        let config = &format!(
+            "{BASE_WORKSPACE_CONFIG}\
+            git_author_name = \"config-bot\"\n\
+            git_author_email = \"config-bot@example.com\""
+        );
+        let config: Config = toml::from_str(config).unwrap();
+
+        let identity = config
+            .workspace
+            .git_identity(Some("cli-bot".to_string()), None);
+        assert_eq!(
+            identity,
+            git_cmd::GitIdentity {
+                name: Some("cli-bot".to_string()),
+                email: Some("config-bot@example.com".to_string()),
+            }
+        );
+    }
+
     #[test]
     fn custom_minor_increment_regex_is_deserialized() {
         let config = &format!(
@@ -0,0 +1,113 @@
+use std::process::ExitCode;
+
+/// Category of a failure of the CLI.
+/// Each category is mapped to a different process exit code,
+/// so that CI scripts can react to the different failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Any failure that doesn't fall into the other categories.
+    Other,
+    /// Invalid configuration file or cli arguments.
+    Config,
+    /// Error while communicating with the git forge or another remote service.
+    Forge,
+    /// Error while publishing the packages.
+    Publish,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+
+    fn code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::Config => 2,
+            Self::Forge => 3,
+            Self::Publish => 4,
+        }
+    }
+}
+
+/// Error returned by the CLI commands.
+#[derive(Debug)]
+pub struct CliError {
+    kind: ErrorKind,
+    error: anyhow::Error,
+}
+
+impl CliError {
+    /// Categorize the error.
+    /// Network errors are always reported as [`ErrorKind::Forge`], because they
+    /// come from the communication with a remote service.
+    pub fn new(error: anyhow::Error, kind: ErrorKind) -> Self {
+        let kind = if is_network_error(&error) {
+            ErrorKind::Forge
+        } else {
+            kind
+        };
+        Self { kind, error }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn error(&self) -> &anyhow::Error {
+        &self.error
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(error, ErrorKind::Other)
+    }
+}
+
+fn is_network_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some())
+}
+
+pub trait ResultExt<T> {
+    /// Assign the given [`ErrorKind`] to the error.
+    fn err_kind(self, kind: ErrorKind) -> Result<T, CliError>;
+}
+
+impl<T> ResultExt<T> for anyhow::Result<T> {
+    fn err_kind(self, kind: ErrorKind) -> Result<T, CliError> {
+        self.map_err(|e| CliError::new(e, kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context as _;
+
+    use super::*;
+
+    #[test]
+    fn error_kind_is_assigned() {
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("invalid config"));
+        let error = result.err_kind(ErrorKind::Config).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Config);
+    }
+
+    #[test]
+    fn network_errors_are_forge_errors() {
+        let reqwest_error = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let result: anyhow::Result<()> = Err(reqwest_error).context("can't create release");
+        let error = result.err_kind(ErrorKind::Publish).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Forge);
+    }
+
+    #[test]
+    fn exit_codes_are_stable() {
+        assert_eq!(ErrorKind::Other.code(), 1);
+        assert_eq!(ErrorKind::Config.code(), 2);
+        assert_eq!(ErrorKind::Forge.code(), 3);
+        assert_eq!(ErrorKind::Publish.code(), 4);
+    }
+}
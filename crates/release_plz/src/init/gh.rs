@@ -1,6 +1,11 @@
-use std::{process::Command, vec};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    vec,
+};
 
 use anyhow::Context;
+use secrecy::{ExposeSecret, SecretString};
 
 fn gh() -> Command {
     Command::new("gh")
@@ -39,6 +44,29 @@ pub fn store_secret(token_name: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Store secret passing its value to the stdin of gh.
+pub fn store_secret_value(token_name: &str, value: &SecretString) -> anyhow::Result<()> {
+    let mut child = gh()
+        .arg("secret")
+        .arg("set")
+        .arg(token_name)
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("error while spawning gh to set repository secret")?;
+    child
+        .stdin
+        .take()
+        .context("error while opening gh stdin")?
+        .write_all(value.expose_secret().as_bytes())
+        .context("error while writing repository secret to gh")?;
+    let output = child
+        .wait_with_output()
+        .context("error while waiting gh to set repository secret")?;
+    get_stdout_if_success(output).context("error while setting repository secret")?;
+    println!();
+    Ok(())
+}
+
 pub fn is_gh_installed() -> bool {
     gh().arg("version")
         .output()
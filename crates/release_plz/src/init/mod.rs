@@ -5,13 +5,36 @@ use std::io::Write;
 use anyhow::Context;
 use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
 use release_plz_core::{Project, ReleaseMetadata, ReleaseMetadataBuilder};
+use secrecy::SecretString;
 use std::collections::HashSet;
 
 const CARGO_REGISTRY_TOKEN: &str = "CARGO_REGISTRY_TOKEN";
 const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
 const CUSTOM_GITHUB_TOKEN: &str = "RELEASE_PLZ_TOKEN";
+const DEFAULT_PR_CONCURRENCY_GROUP: &str = "release-plz-${{ github.ref }}";
 
-pub fn init(manifest_path: &Utf8Path, toml_check: bool) -> anyhow::Result<()> {
+/// `concurrency` settings of the release PR job of the generated workflow.
+#[derive(Debug)]
+pub struct PrConcurrency {
+    pub group: String,
+    pub cancel_in_progress: bool,
+}
+
+impl Default for PrConcurrency {
+    fn default() -> Self {
+        Self {
+            group: DEFAULT_PR_CONCURRENCY_GROUP.to_string(),
+            cancel_in_progress: false,
+        }
+    }
+}
+
+pub fn init(
+    manifest_path: &Utf8Path,
+    toml_check: bool,
+    runs_on: &[String],
+    pr_concurrency: &PrConcurrency,
+) -> anyhow::Result<()> {
     ensure_gh_is_installed()?;
 
     // Create a Project instance to check mandatory fields
@@ -43,7 +66,13 @@ pub fn init(manifest_path: &Utf8Path, toml_check: bool) -> anyhow::Result<()> {
 
     enable_pr_permissions(&repo_url)?;
     let github_token = store_github_token()?;
-    write_actions_yaml(github_token, trusted_publishing, persist_credentials)?;
+    write_actions_yaml(
+        github_token,
+        trusted_publishing,
+        persist_credentials,
+        runs_on,
+        pr_concurrency,
+    )?;
 
     let secrets_stored = !trusted_publishing || github_token != GITHUB_TOKEN;
     print_recap(&repo_url, secrets_stored);
@@ -102,12 +131,27 @@ fn greet() {
 }
 
 fn store_cargo_token() -> anyhow::Result<()> {
+    if let Some(token) = local_cargo_token()
+        && ask_confirmation("👉 Found a crates.io token in your cargo credentials — use it?")?
+    {
+        gh::store_secret_value(CARGO_REGISTRY_TOKEN, &token)?;
+        return Ok(());
+    }
     println!("👉 Paste your cargo registry token to store it in the GitHub actions repository secrets.
 💡 You can create a crates.io token on https://crates.io/settings/tokens/new, specifying the following scopes: \"publish-new\" and \"publish-update\".");
     gh::store_secret(CARGO_REGISTRY_TOKEN)?;
     Ok(())
 }
 
+/// crates.io token of the `CARGO_REGISTRY_TOKEN` environment variable or of
+/// the cargo credentials file, if any.
+fn local_cargo_token() -> Option<SecretString> {
+    cargo_utils::registry_token(None)
+        .inspect_err(|e| tracing::debug!("can't read the local cargo token: {e:?}"))
+        .ok()
+        .flatten()
+}
+
 fn enable_pr_permissions(repo_url: &str) -> anyhow::Result<()> {
     println!("
 👉 Go to {} and enable the option \"Allow GitHub Actions to create and approve pull requests\". Type Enter when done.", actions_settings_url(repo_url));
@@ -179,6 +223,8 @@ fn write_actions_yaml(
     github_token: &str,
     trusted_publishing: bool,
     persist_credentials: bool,
+    runs_on: &[String],
+    pr_concurrency: &PrConcurrency,
 ) -> anyhow::Result<()> {
     let branch = gh::default_branch()?;
     let owner = gh::repo_owner()?;
@@ -188,6 +234,8 @@ fn write_actions_yaml(
         &owner,
         trusted_publishing,
         persist_credentials,
+        runs_on,
+        pr_concurrency,
     );
     fs_err::create_dir_all(actions_file_parent())
         .context("failed to create GitHub actions workflows directory")?;
@@ -201,7 +249,14 @@ fn action_yaml(
     owner: &str,
     trusted_publishing: bool,
     persist_credentials: bool,
+    runs_on: &[String],
+    pr_concurrency: &PrConcurrency,
 ) -> String {
+    let runs_on = runs_on_yaml(runs_on);
+    let PrConcurrency {
+        group: concurrency_group,
+        cancel_in_progress,
+    } = pr_concurrency;
     let github_token_secret = format!("${{{{ secrets.{github_token} }}}}");
     let is_default_token = github_token == GITHUB_TOKEN;
     let checkout_token_line = if !persist_credentials || is_default_token {
@@ -263,7 +318,7 @@ on:
 jobs:
   release-plz-release:
     name: Release-plz release
-    runs-on: ubuntu-latest
+    runs-on: {runs_on}
     if: ${{{{ github.repository_owner == '{owner}' }}}}
     permissions:
       contents: write{id_token_permissions}
@@ -286,14 +341,14 @@ jobs:
 
   release-plz-pr:
     name: Release-plz PR
-    runs-on: ubuntu-latest
+    runs-on: {runs_on}
     if: ${{{{ github.repository_owner == '{owner}' }}}}
     permissions:
       pull-requests: write
       contents: write
     concurrency:
-      group: release-plz-${{{{ github.ref }}}}
-      cancel-in-progress: false
+      group: {concurrency_group}
+      cancel-in-progress: {cancel_in_progress}
     steps:
       - *checkout
       - *install-rust
@@ -307,6 +362,16 @@ jobs:
     )
 }
 
+/// Value of the `runs-on` field of the jobs.
+/// Multiple runner labels are written as a YAML list.
+fn runs_on_yaml(labels: &[String]) -> String {
+    match labels {
+        [] => "ubuntu-latest".to_string(),
+        [label] => label.clone(),
+        labels => format!("[{}]", labels.join(", ")),
+    }
+}
+
 fn ensure_gh_is_installed() -> anyhow::Result<()> {
     anyhow::ensure!(
         gh::is_gh_installed(),
@@ -335,6 +400,7 @@ impl ReleaseMetadataBuilder for NoopReleaseMetadataBuilder {
         Some(ReleaseMetadata {
             release_name_template: None,
             tag_name_template: None,
+            tag_prefix: String::new(),
         })
     }
 }
@@ -399,7 +465,15 @@ mod tests {
                       GITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}
                       CARGO_REGISTRY_TOKEN: ${{ secrets.CARGO_REGISTRY_TOKEN }}
         "]]
-        .assert_eq(&action_yaml("main", GITHUB_TOKEN, "owner", false, false));
+        .assert_eq(&action_yaml(
+            "main",
+            GITHUB_TOKEN,
+            "owner",
+            false,
+            false,
+            &[],
+            &PrConcurrency::default(),
+        ));
     }
 
     #[test]
@@ -464,6 +538,46 @@ mod tests {
             "owner",
             false,
             false,
+            &[],
+            &PrConcurrency::default(),
+        ));
+    }
+
+    #[test]
+    fn runs_on_labels_are_used_in_both_jobs() {
+        assert_eq!(runs_on_yaml(&[]), "ubuntu-latest");
+        assert_eq!(runs_on_yaml(&["my-runner".to_string()]), "my-runner");
+        let labels = ["self-hosted".to_string(), "linux".to_string()];
+        let yaml = action_yaml(
+            "main",
+            GITHUB_TOKEN,
+            "owner",
+            false,
+            false,
+            &labels,
+            &PrConcurrency::default(),
+        );
+        assert_eq!(yaml.matches("runs-on: [self-hosted, linux]\n").count(), 2);
+        assert!(!yaml.contains("ubuntu-latest"));
+    }
+
+    #[test]
+    fn pr_concurrency_is_configurable() {
+        let pr_concurrency = PrConcurrency {
+            group: "release-plz-${{ github.workflow }}".to_string(),
+            cancel_in_progress: true,
+        };
+        let yaml = action_yaml(
+            "main",
+            GITHUB_TOKEN,
+            "owner",
+            false,
+            false,
+            &[],
+            &pr_concurrency,
+        );
+        assert!(yaml.contains(
+            "    concurrency:\n      group: release-plz-${{ github.workflow }}\n      cancel-in-progress: true\n"
         ));
     }
 }
@@ -529,6 +643,8 @@ fn actions_yaml_string_with_trusted_publishing_is_correct() {
         "owner",
         true,
         false,
+        &[],
+        &PrConcurrency::default(),
     ));
 }
 
@@ -595,5 +711,7 @@ fn actions_yaml_string_with_persist_credentials_is_correct() {
         "owner",
         false,
         true,
+        &[],
+        &PrConcurrency::default(),
     ));
 }
@@ -3,6 +3,16 @@ use tracing_subscriber::{
     EnvFilter, filter::filter_fn, fmt, layer::SubscriberExt, util::SubscriberInitExt,
 };
 
+/// Format of the logs.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LogFormat {
+    /// Human-readable logs.
+    #[default]
+    Text,
+    /// One JSON object per line, including the fields of the current spans.
+    Json,
+}
+
 /// Intialize the logging using the tracing crate.
 ///
 /// You can customize the log level with the `RELEASE_PLZ_LOG` environment
@@ -10,35 +20,50 @@ use tracing_subscriber::{
 /// environment variable.
 ///
 /// If verbosity is set, the logs will show more information.
+/// Otherwise, logs are printed at the `default_level`.
 ///
 /// To maximize logs readability in CI, logs are written in one line
 /// (we don't split them in multiple lines).
-pub fn init(verbosity: Option<LevelFilter>) {
+///
+/// With the [`LogFormat::Json`] format, spans are always kept, so that every event
+/// contains the fields recorded by release-plz, such as the package name.
+pub fn init(verbosity: Option<LevelFilter>, default_level: LevelFilter, format: LogFormat) {
     let env_filter = EnvFilter::try_from_env("RELEASE_PLZ_LOG").unwrap_or_else(|_| {
         EnvFilter::builder()
-            .with_default_directive(verbosity.unwrap_or(LevelFilter::INFO).into())
+            .with_default_directive(verbosity.unwrap_or(default_level).into())
             .from_env_lossy()
     });
 
     let verbose = verbosity.is_some();
+    let is_json = format == LogFormat::Json;
 
     let ignore_info_spans = filter_fn(move |metadata| {
         let is_trace_or_debug = || metadata.level() < &Level::INFO;
         // If it's not a span, it's an event. We keep events.
-        verbose || !metadata.is_span() || is_trace_or_debug()
+        verbose || is_json || !metadata.is_span() || is_trace_or_debug()
     });
 
     let ansi =
         std::env::var_os("RELEASE_PLZ_NO_ANSI").is_none() && std::env::var_os("NO_COLOR").is_none();
 
-    fmt()
+    let builder = fmt()
         .with_env_filter(env_filter)
         .with_writer(std::io::stderr)
-        .with_ansi(ansi)
         .with_target(verbose)
         .with_file(verbose)
-        .with_line_number(verbose)
-        .finish()
-        .with(ignore_info_spans)
-        .init();
+        .with_line_number(verbose);
+    match format {
+        LogFormat::Text => builder
+            .with_ansi(ansi)
+            .finish()
+            .with(ignore_info_spans)
+            .init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .finish()
+            .with(ignore_info_spans)
+            .init(),
+    }
 }
@@ -1,50 +1,115 @@
 mod args;
+mod changelog;
 mod changelog_config;
+mod check;
 mod config;
+mod error;
 mod generate_schema;
 pub mod init;
 mod log;
 mod update_checker;
+mod workspaces;
 
+use std::{path::Path, process::ExitCode};
+
+use anyhow::Context as _;
 use args::OutputType;
 use clap::Parser;
 use release_plz_core::ReleaseRequest;
 use serde::Serialize;
 use tracing::error;
 
-use crate::args::{CliArgs, Command, manifest_command::ManifestCommand as _};
+use crate::{
+    args::{
+        CliArgs, Command,
+        manifest_command::{ManifestCommand as _, Workspaces},
+    },
+    error::{CliError, ErrorKind, ResultExt as _},
+    workspaces::{WorkspacesOutput, check_distinct_branch_prefixes, load_workspaces},
+};
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> ExitCode {
     let args = CliArgs::parse();
-    log::init(args.verbosity()?);
-    run(args).await.map_err(|e| {
-        error!("{:?}", e);
-        e
-    })?;
-
-    Ok(())
+    let verbosity = match args.verbosity() {
+        Ok(verbosity) => verbosity,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            return ErrorKind::Config.exit_code();
+        }
+    };
+    log::init(verbosity, args.default_log_level(), args.log_format);
+    if let Some(cargo_bin) = &args.cargo_bin
+        && let Err(e) = cargo_utils::set_cargo_bin(cargo_bin.clone())
+    {
+        error!("{e:?}");
+        return ErrorKind::Config.exit_code();
+    }
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("{:?}", e.error());
+            e.kind().exit_code()
+        }
+    }
 }
 
-async fn run(args: CliArgs) -> anyhow::Result<()> {
+async fn run(args: CliArgs) -> Result<(), CliError> {
     match args.command {
-        Command::Update(cmd_args) => {
-            let cargo_metadata = cmd_args.cargo_metadata()?;
-            let config = cmd_args.config.load()?;
-            let update_request = cmd_args.update_request(&config, cargo_metadata)?;
-            let (packages_update, _temp_repo) = release_plz_core::update(&update_request).await?;
-            println!("{}", packages_update.summary());
-        }
-        Command::ReleasePr(cmd_args) => {
-            anyhow::ensure!(
-                cmd_args.update.git_token.is_some(),
-                "please provide the git token with the --git-token cli argument."
-            );
-            let cargo_metadata = cmd_args.update.cargo_metadata()?;
-            let config = cmd_args.update.config.load()?;
-            let request = cmd_args.release_pr_req(&config, cargo_metadata)?;
-            let release_pr = release_plz_core::release_pr(&request).await?;
+        Command::Update(mut cmd_args) => {
+            let workspaces = load_workspaces(&mut cmd_args.update).err_kind(ErrorKind::Config)?;
+            let multiple_workspaces = workspaces.len() > 1;
+            let mut outputs = vec![];
+            for workspace in workspaces {
+                cmd_args.update.select_workspace(workspace.manifest);
+                let update_request = cmd_args
+                    .update
+                    .update_request(&workspace.config, workspace.cargo_metadata)
+                    .err_kind(ErrorKind::Config)?;
+                let output_dir = cmd_args.output_dir().err_kind(ErrorKind::Config)?;
+                let packages_update = if let Some(output_dir) = output_dir {
+                    release_plz_core::update_to_output_dir(&update_request, output_dir).await?
+                } else {
+                    release_plz_core::update(&update_request).await?.0
+                };
+                if cmd_args.output.is_some() {
+                    outputs.push((
+                        workspace.manifest_path,
+                        packages_update.output(cmd_args.verbose_output),
+                    ));
+                } else {
+                    if multiple_workspaces {
+                        println!("{}:", workspace.manifest_path);
+                    }
+                    println!("{}", packages_update.summary());
+                }
+            }
             if let Some(output_type) = cmd_args.output {
+                print_output(
+                    output_type,
+                    cmd_args.output_file.as_deref(),
+                    WorkspacesOutput::new(outputs),
+                )?;
+            }
+        }
+        Command::ReleasePr(mut cmd_args) => {
+            if cmd_args.update.git_token.is_none() {
+                return Err(CliError::new(
+                    anyhow::anyhow!(
+                        "please provide the git token with the --git-token cli argument."
+                    ),
+                    ErrorKind::Config,
+                ));
+            }
+            let workspaces = load_workspaces(&mut cmd_args.update).err_kind(ErrorKind::Config)?;
+            check_distinct_branch_prefixes(&workspaces).err_kind(ErrorKind::Config)?;
+            let mut outputs = vec![];
+            for workspace in workspaces {
+                cmd_args.update.select_workspace(workspace.manifest);
+                let request = cmd_args
+                    .release_pr_req(&workspace.config, workspace.cargo_metadata)
+                    .err_kind(ErrorKind::Config)?;
+                let release_pr = release_plz_core::release_pr(&request).await?;
                 let prs = match release_pr {
                     Some(pr) => vec![pr],
                     None => vec![],
@@ -52,39 +117,152 @@ async fn run(args: CliArgs) -> anyhow::Result<()> {
                 let prs_json = serde_json::json!({
                     "prs": prs
                 });
-                print_output(output_type, prs_json);
+                outputs.push((workspace.manifest_path, prs_json));
+            }
+            if let Some(output_type) = cmd_args.output {
+                print_output(
+                    output_type,
+                    cmd_args.output_file.as_deref(),
+                    WorkspacesOutput::new(outputs),
+                )?;
             }
         }
-        Command::Release(cmd_args) => {
-            let cargo_metadata = cmd_args.cargo_metadata()?;
-            let config = cmd_args.config.load()?;
-            let cmd_args_output = cmd_args.output;
-            let request: ReleaseRequest = cmd_args.release_request(&config, cargo_metadata)?;
-            let output = release_plz_core::release(&request)
-                .await?
-                .unwrap_or_default();
-            if let Some(output_type) = cmd_args_output {
-                print_output(output_type, output);
+        Command::Release(mut cmd_args) => {
+            let workspaces = load_workspaces(&mut cmd_args).err_kind(ErrorKind::Config)?;
+            let mut outputs = vec![];
+            let mut failures = vec![];
+            for workspace in workspaces {
+                cmd_args.select_workspace(workspace.manifest);
+                let request: ReleaseRequest = cmd_args
+                    .release_request(&workspace.config, workspace.cargo_metadata)
+                    .err_kind(ErrorKind::Config)?;
+                let output = release_plz_core::release(&request)
+                    .await
+                    .err_kind(ErrorKind::Publish)?
+                    .unwrap_or_default();
+                failures.push(output.check_failures());
+                outputs.push((workspace.manifest_path, output));
+            }
+            if let Some(output_type) = cmd_args.output {
+                print_output(
+                    output_type,
+                    cmd_args.output_file.as_deref(),
+                    WorkspacesOutput::new(outputs),
+                )?;
             }
+            failures
+                .into_iter()
+                .collect::<anyhow::Result<()>>()
+                .err_kind(ErrorKind::Publish)?;
+        }
+        Command::Changelog(cmd_args) => {
+            let update = cmd_args.into_update();
+            let cargo_metadata = update.cargo_metadata().err_kind(ErrorKind::Config)?;
+            let config = update.config.load().err_kind(ErrorKind::Config)?;
+            let update_request = update
+                .update_request(&config, cargo_metadata)
+                .err_kind(ErrorKind::Config)?;
+            println!("{}", changelog::changelog_entries(&update_request).await?);
+        }
+        Command::Check(cmd_args) => {
+            check::check(&cmd_args.into_update())
+                .await
+                .err_kind(ErrorKind::Config)?;
         }
         Command::GenerateCompletions(cmd_args) => cmd_args.print(),
-        Command::CheckUpdates => update_checker::check_update().await?,
+        Command::CheckUpdates(cmd_args) => {
+            let update_check = update_checker::check_update(args.offline).await?;
+            match cmd_args.output {
+                Some(output_type) => print_output(output_type, None, update_check)?,
+                None => println!("{}", update_check.summary()),
+            }
+        }
         Command::GenerateSchema => generate_schema::generate_schema_to_disk()?,
-        Command::Init(cmd_args) => init::init(&cmd_args.manifest_path(), !cmd_args.no_toml_check)?,
+        Command::Init(cmd_args) => init::init(
+            &cmd_args.manifest_path(),
+            !cmd_args.no_toml_check,
+            &cmd_args.runs_on,
+            &cmd_args.pr_concurrency(),
+        )?,
         Command::SetVersion(cmd_args) => {
-            let config = cmd_args.config.load()?;
-            let request = cmd_args.set_version_request(&config)?;
-            release_plz_core::set_version::set_version(&request)?;
+            let config = cmd_args.config.load().err_kind(ErrorKind::Config)?;
+            let cmd_args_output = cmd_args.output;
+            let cmd_args_output_file = cmd_args.output_file.clone();
+            let request = cmd_args
+                .set_version_request(&config)
+                .err_kind(ErrorKind::Config)?;
+            let output = release_plz_core::set_version::set_version(&request)?;
+            if let Some(output_type) = cmd_args_output {
+                print_output(output_type, cmd_args_output_file.as_deref(), output)?;
+            }
+        }
+        Command::Config(cmd_args) => {
+            let cargo_metadata = cmd_args.cargo_metadata().err_kind(ErrorKind::Config)?;
+            let config = cmd_args.config.load().err_kind(ErrorKind::Config)?;
+            let package_names: Vec<&str> = cargo_metadata
+                .workspace_packages()
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            let packages_config = config.resolved_packages_config(&package_names);
+            match cmd_args.output {
+                Some(output_type) => print_output(output_type, None, packages_config)?,
+                None => {
+                    let toml = toml::to_string(&packages_config)
+                        .context("can't serialize the configuration to TOML")?;
+                    print!("{toml}");
+                }
+            }
+        }
+        Command::Yank(cmd_args) => {
+            let cargo_metadata = cmd_args.cargo_metadata().err_kind(ErrorKind::Config)?;
+            let request = cmd_args
+                .yank_request(cargo_metadata)
+                .err_kind(ErrorKind::Config)?;
+            release_plz_core::yank(&request)?;
+            println!("{}", request.summary());
+        }
+        Command::ReleaseNotes(cmd_args) => {
+            let cargo_metadata = cmd_args.cargo_metadata().err_kind(ErrorKind::Config)?;
+            let config = cmd_args.config.load().err_kind(ErrorKind::Config)?;
+            let request = cmd_args
+                .release_notes_request(&config, cargo_metadata)
+                .err_kind(ErrorKind::Config)?;
+            println!("{}", release_plz_core::release_notes(&request)?);
         }
     }
     Ok(())
 }
 
-fn print_output(output_type: OutputType, output: impl Serialize) {
-    match output_type {
+/// Print the output to stdout, or write it to `output_file` if specified.
+fn print_output(
+    output_type: OutputType,
+    output_file: Option<&Path>,
+    output: impl Serialize,
+) -> anyhow::Result<()> {
+    let output = match output_type {
         OutputType::Json => match serde_json::to_string(&output) {
-            Ok(json) => println!("{json}"),
-            Err(e) => tracing::error!("can't serialize release pr to json: {e}"),
+            Ok(json) => json,
+            Err(e) => {
+                tracing::error!("can't serialize release pr to json: {e}");
+                return Ok(());
+            }
         },
+    };
+    match output_file {
+        Some(output_file) => write_output_file(output_file, &output)
+            .with_context(|| format!("can't write output to {}", output_file.display())),
+        None => {
+            println!("{output}");
+            Ok(())
+        }
     }
 }
+
+fn write_output_file(output_file: &Path, output: &str) -> anyhow::Result<()> {
+    if let Some(parent) = output_file.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+    fs_err::write(output_file, format!("{output}\n"))?;
+    Ok(())
+}
@@ -1,24 +1,54 @@
 use anyhow::Context;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[derive(Debug, Deserialize)]
 pub struct ClientResponse {
     pub tag_name: String,
 }
 
-pub async fn check_update() -> anyhow::Result<()> {
-    const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Outcome of the `check-updates` command.
+#[derive(Debug, Serialize)]
+pub struct UpdateCheck {
+    pub current: &'static str,
+    /// Latest release-plz version. `None` if the check was skipped.
+    pub latest: Option<String>,
+    pub update_available: bool,
+}
+
+impl UpdateCheck {
+    fn new(latest: Option<String>) -> Self {
+        let update_available = latest.as_deref().is_some_and(|l| l != CURRENT_VERSION);
+        Self {
+            current: CURRENT_VERSION,
+            latest,
+            update_available,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match &self.latest {
+            None => "Update check skipped (offline)".to_string(),
+            Some(latest) if self.update_available => format!(
+                "Your release-plz version is {}. A newer version ({latest}) is available at https://github.com/release-plz/release-plz",
+                self.current
+            ),
+            Some(_) => format!("Your release-plz version ({}) is up to date", self.current),
+        }
+    }
+}
+
+/// Check if a newer version of release-plz is available.
+/// If `offline` is true, no request is sent.
+pub async fn check_update(offline: bool) -> anyhow::Result<UpdateCheck> {
+    if offline {
+        return Ok(UpdateCheck::new(None));
+    }
     let latest_version = get_latest_version()
         .await
         .context("error while checking for updates")?;
-    if latest_version == CURRENT_VERSION {
-        println!("Your release-plz version ({CURRENT_VERSION}) is up to date");
-    } else {
-        println!(
-            "Your release-plz version is {CURRENT_VERSION}. A newer version ({latest_version}) is available at https://github.com/release-plz/release-plz"
-        );
-    }
-    Ok(())
+    Ok(UpdateCheck::new(Some(latest_version)))
 }
 
 async fn get_latest_version() -> anyhow::Result<String> {
@@ -51,11 +81,25 @@ fn extract_version(tag: &str) -> Option<&str> {
 
 #[cfg(test)]
 mod tests {
-    use super::extract_version;
+    use super::*;
 
     #[test]
     fn version_is_extracted() {
         let tag = "release-plz-v0.2.37";
         assert_eq!(extract_version(tag), Some("0.2.37"));
     }
+
+    #[tokio::test]
+    async fn offline_check_is_skipped() {
+        let update_check = check_update(true).await.unwrap();
+        assert_eq!(update_check.summary(), "Update check skipped (offline)");
+        assert_eq!(
+            serde_json::to_value(&update_check).unwrap(),
+            serde_json::json!({
+                "current": CURRENT_VERSION,
+                "latest": null,
+                "update_available": false,
+            })
+        );
+    }
 }
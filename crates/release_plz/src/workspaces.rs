@@ -0,0 +1,166 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use release_plz_core::DEFAULT_BRANCH_PREFIX;
+use serde::Serialize;
+
+use crate::{args::manifest_command::Workspaces, config::Config};
+
+/// Workspace processed by a command.
+pub struct Workspace {
+    /// Value of the `--manifest-path` argument of this workspace.
+    pub manifest: Option<PathBuf>,
+    pub manifest_path: Utf8PathBuf,
+    pub cargo_metadata: cargo_metadata::Metadata,
+    pub config: Config,
+}
+
+/// Read the metadata and the configuration of every workspace of the command,
+/// so that a misconfigured workspace is detected before any workspace is processed.
+///
+/// When processing several workspaces, each workspace looks for its default config files
+/// next to its manifest rather than in the current directory.
+pub fn load_workspaces(cmd: &mut impl Workspaces) -> anyhow::Result<Vec<Workspace>> {
+    let manifests = cmd.workspace_manifests();
+    let multiple_workspaces = manifests.len() > 1;
+    manifests
+        .into_iter()
+        .map(|manifest| {
+            cmd.select_workspace(manifest.clone());
+            let cargo_metadata = cmd.cargo_metadata()?;
+            let manifest_path = cmd.manifest_path();
+            let config = if multiple_workspaces {
+                let workspace_dir = manifest_path.parent().unwrap_or(Utf8Path::new(""));
+                cmd.config_path().load_in(workspace_dir.as_std_path())?
+            } else {
+                cmd.config_path().load()?
+            };
+            Ok(Workspace {
+                manifest,
+                manifest_path,
+                cargo_metadata,
+                config,
+            })
+        })
+        .collect()
+}
+
+/// Release PRs are found by their branch prefix, so workspaces sharing a prefix
+/// would update each other's release PR.
+pub fn check_distinct_branch_prefixes(workspaces: &[Workspace]) -> anyhow::Result<()> {
+    let mut prefixes: BTreeMap<&str, &Utf8Path> = BTreeMap::new();
+    for workspace in workspaces {
+        let prefix = workspace
+            .config
+            .workspace
+            .pr_branch_prefix
+            .as_deref()
+            .unwrap_or(DEFAULT_BRANCH_PREFIX);
+        if let Some(other) = prefixes.insert(prefix, &workspace.manifest_path) {
+            anyhow::bail!(
+                "workspaces {other} and {} use the same `pr_branch_prefix` ({prefix:?}). Set a different `pr_branch_prefix` in the configuration of each workspace",
+                workspace.manifest_path
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Output of a command run on one or more workspaces.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum WorkspacesOutput<T> {
+    /// With a single workspace, the output isn't wrapped, as in previous versions.
+    Single(T),
+    /// With several workspaces, the outputs are keyed by workspace manifest.
+    Multiple(BTreeMap<Utf8PathBuf, T>),
+}
+
+impl<T> WorkspacesOutput<T> {
+    pub fn new(mut outputs: Vec<(Utf8PathBuf, T)>) -> Self {
+        if outputs.len() == 1 {
+            let (_, output) = outputs.remove(0);
+            Self::Single(output)
+        } else {
+            Self::Multiple(outputs.into_iter().collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser as _;
+
+    use crate::args::update::Update;
+
+    use super::*;
+
+    fn write_workspace(dir: &Utf8Path, name: &str, pr_branch_prefix: &str) {
+        let package_dir = dir.join(name);
+        fs_err::create_dir_all(package_dir.join("src")).unwrap();
+        fs_err::write(
+            package_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+        )
+        .unwrap();
+        fs_err::write(package_dir.join("src").join("lib.rs"), "").unwrap();
+        fs_err::write(
+            package_dir.join("release-plz.toml"),
+            format!("[workspace]\npr_branch_prefix = \"{pr_branch_prefix}\"\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn each_workspace_loads_its_own_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(temp_dir.path()).unwrap();
+        write_workspace(dir, "a", "release-a-");
+        write_workspace(dir, "b", "release-b-");
+        let mut update = Update::try_parse_from([
+            "update",
+            "--manifest-path",
+            dir.join("a").join("Cargo.toml").as_str(),
+            "--manifest-path",
+            dir.join("b").join("Cargo.toml").as_str(),
+        ])
+        .unwrap();
+
+        let workspaces = load_workspaces(&mut update).unwrap();
+
+        let loaded: Vec<(&str, Option<&str>)> = workspaces
+            .iter()
+            .map(|workspace| {
+                (
+                    workspace.cargo_metadata.workspace_packages()[0]
+                        .name
+                        .as_str(),
+                    workspace.config.workspace.pr_branch_prefix.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            loaded,
+            [("a", Some("release-a-")), ("b", Some("release-b-"))]
+        );
+        check_distinct_branch_prefixes(&workspaces).unwrap();
+    }
+
+    #[test]
+    fn single_workspace_output_is_not_keyed() {
+        let output = WorkspacesOutput::new(vec![(Utf8PathBuf::from("Cargo.toml"), 1)]);
+        assert_eq!(serde_json::to_string(&output).unwrap(), "1");
+    }
+
+    #[test]
+    fn multiple_workspaces_output_is_keyed_by_manifest() {
+        let output = WorkspacesOutput::new(vec![
+            (Utf8PathBuf::from("b/Cargo.toml"), 2),
+            (Utf8PathBuf::from("a/Cargo.toml"), 1),
+        ]);
+        assert_eq!(
+            serde_json::to_string(&output).unwrap(),
+            r#"{"a/Cargo.toml":1,"b/Cargo.toml":2}"#
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use crate::helpers;
+
+#[test]
+fn invalid_manifest_path_exits_with_config_error_code() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manifest_path = tmp.path().join("Cargo.toml");
+    helpers::cmd::release_plz_cmd("target".into())
+        .arg("update")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn invalid_verbosity_exits_with_config_error_code() {
+    helpers::cmd::release_plz_cmd("target".into())
+        .arg("update")
+        .arg("-vvvv")
+        .assert()
+        .code(2);
+}
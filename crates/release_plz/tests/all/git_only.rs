@@ -112,6 +112,40 @@ git_only = true
     assert_eq!(opened_prs[0].title, "chore: release v0.2.1");
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+async fn git_only_considers_previous_tag_patterns() {
+    let context = TestContext::new().await;
+
+    let config = r#"
+[workspace]
+git_only = true
+previous_tag_patterns = ["release-{{ version }}"]
+"#;
+    context.write_release_plz_toml(config);
+
+    use cargo_metadata::semver::Version;
+
+    // Tag created with the release-plz template
+    context.repo.tag("v0.1.0", "Release v0.1.0").unwrap();
+
+    // Newer tag created with the template used before adopting release-plz
+    context.set_package_version(&context.gitea.repo, &Version::parse("0.2.0").unwrap());
+    context.push_all_changes("chore: bump version to 0.2.0");
+    context.repo.tag("release-0.2.0", "Release 0.2.0").unwrap();
+
+    let readme = context.repo_dir().join("README.md");
+    fs_err::write(&readme, "# Updated README").unwrap();
+    context.push_all_changes("fix: update readme");
+
+    context.run_release_pr().success();
+
+    // The highest version across all the patterns wins.
+    let opened_prs = context.opened_release_prs().await;
+    assert_eq!(opened_prs.len(), 1);
+    assert_eq!(opened_prs[0].title, "chore: release v0.2.1");
+}
+
 #[tokio::test]
 #[cfg_attr(not(feature = "docker-tests"), ignore)]
 async fn git_only_ignores_non_matching_tags() {
@@ -345,7 +379,9 @@ git_tag_name = "{{ package }}-v{{ version }}"
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/)."
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->"
         )
         .trim(),
         pr_body.trim()
@@ -419,7 +455,9 @@ git_tag_name = "api-v{{ version }}"
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/)."
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->"
         )
         .trim(),
         pr_body.trim()
@@ -631,7 +669,9 @@ git_tag_name = "{{ package }}-v{{ version }}"
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/)."
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->"
         )
         .trim(),
         pr_body.trim()
@@ -983,7 +1023,9 @@ publish = false
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/)."
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->"
         )
         .trim(),
         pr_body.trim()
@@ -1,4 +1,4 @@
-use std::{process::Command, time::Duration};
+use std::{collections::BTreeMap, process::Command, time::Duration};
 
 use crate::helpers::gitea::CARGO_INDEX_REPO;
 use assert_cmd::assert::Assert;
@@ -95,6 +95,8 @@ impl TestContext {
             body: "This is my pull request".to_string(),
             draft: false,
             labels: vec![],
+            label_styles: BTreeMap::new(),
+            head_repo: None,
         };
         self.git_client.open_pr(&pr).await.unwrap();
         // go back to main
@@ -272,16 +274,19 @@ impl TestContext {
     }
 }
 
-pub fn run_set_version(directory: &Utf8Path, change: &str) {
+pub fn run_set_version(directory: &Utf8Path, change: &str) -> Assert {
+    set_version_cmd(directory, change).assert().success()
+}
+
+pub fn set_version_cmd(directory: &Utf8Path, change: &str) -> assert_cmd::Command {
     let change: Vec<_> = change.split(' ').collect();
     let target_dir = Utf8PathBuf::from("target");
-    super::cmd::release_plz_cmd(&target_dir)
-        .current_dir(directory)
+    let mut cmd = super::cmd::release_plz_cmd(&target_dir);
+    cmd.current_dir(directory)
         .env(RELEASE_PLZ_LOG, log_level())
         .arg("set-version")
-        .args(&change)
-        .assert()
-        .success();
+        .args(&change);
+    cmd
 }
 
 fn log_level() -> String {
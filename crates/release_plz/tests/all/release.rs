@@ -267,3 +267,25 @@ async fn release_plz_can_do_backports() {
     .to_string();
     outcome.stdout(format!("{expected_stdout}\n"));
 }
+
+#[tokio::test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+async fn verify_command_does_not_run_for_already_published_package() {
+    let context = TestContext::new().await;
+    let crate_name = &context.gitea.repo;
+    context.run_cargo_publish(crate_name);
+
+    // The release would fail if the verify command ran.
+    let config = r#"
+    [workspace]
+    verify_command = ["false"]
+    "#;
+    context.write_release_plz_toml(config);
+
+    let outcome = context.run_release().success();
+    let expected_stdout = serde_json::json!({
+        "releases": [],
+    })
+    .to_string();
+    outcome.stdout(format!("{expected_stdout}\n"));
+}
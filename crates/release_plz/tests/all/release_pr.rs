@@ -52,7 +52,9 @@ async fn release_plz_opens_pr_with_default_config() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -114,7 +116,9 @@ async fn release_plz_opens_pr_without_breaking_changes() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -204,7 +208,9 @@ async fn release_plz_can_do_backport_prs() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -299,7 +305,9 @@ Failed in:
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -392,7 +400,9 @@ async fn release_plz_updates_binary_when_library_changes() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -415,6 +425,62 @@ This PR was generated with [release-plz](https://github.com/release-plz/release-
     .assert_eq(&binary_cargo_toml);
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+async fn release_plz_does_not_release_package_with_only_dependency_changes_if_disabled() {
+    let binary = "binary";
+    let library = "library";
+    let context = TestContext::new_workspace_with_packages(&[
+        TestPackage::new(binary)
+            .with_type(PackageType::Bin)
+            .with_path_dependencies(vec![format!("../{library}")]),
+        TestPackage::new(library).with_type(PackageType::Lib),
+    ])
+    .await;
+
+    context.run_release_pr().success();
+    context.merge_release_pr().await;
+    context.run_release().success();
+
+    let config = format!(
+        r#"
+    [[package]]
+    name = "{binary}"
+    release_on_dependency_change = false
+    "#
+    );
+    context.write_release_plz_toml(&config);
+
+    // Update the library.
+    let lib_file = context.package_path(library).join("src").join("aa.rs");
+    fs_err::write(&lib_file, "pub fn foo() {}").unwrap();
+    context.push_all_changes("edit library");
+
+    context.run_release_pr().success();
+    let opened_prs = context.opened_release_prs().await;
+    assert_eq!(opened_prs.len(), 1);
+    let pr_body = opened_prs[0].body.as_ref().unwrap();
+    assert!(pr_body.contains(&format!("* `{library}`: 0.1.0 -> 0.1.1")));
+    assert!(!pr_body.contains(&format!("* `{binary}`")));
+
+    context.merge_release_pr().await;
+
+    // The binary isn't released, but it depends on the new version of the library.
+    let binary_cargo_toml =
+        fs_err::read_to_string(context.package_path(binary).join(CARGO_TOML)).unwrap();
+    expect_test::expect![[r#"
+        [package]
+        name = "binary"
+        version = "0.1.0"
+        edition = "2024"
+        publish = ["test-registry"]
+
+        [dependencies]
+        library = { version = "0.1.1", path = "../library", registry = "test-registry" }
+    "#]]
+    .assert_eq(&binary_cargo_toml);
+}
+
 #[tokio::test]
 #[cfg_attr(not(feature = "docker-tests"), ignore)]
 async fn release_plz_opens_pr_with_two_packages_and_default_config() {
@@ -470,7 +536,9 @@ async fn release_plz_opens_pr_with_two_packages_and_default_config() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -528,7 +596,9 @@ Changes:
 
 - add config file
 - cargo init
-- Initial commit",
+- Initial commit
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -558,6 +628,7 @@ async fn release_plz_should_fail_for_multi_package_pr() {
     let outcome = context.run_release_pr().failure();
     let stderr = String::from_utf8_lossy(&outcome.get_output().stderr);
     assert!(stderr.contains("failed to render pr_name"));
+    assert!(stderr.contains("Variable `package` is not defined"));
 }
 
 #[tokio::test]
@@ -593,6 +664,67 @@ async fn release_plz_detects_edited_readme_cargo_toml_field() {
     .assert_eq(&gitea_release.body);
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+async fn release_plz_does_not_open_pr_if_only_ignored_files_changed() {
+    let context = TestContext::new().await;
+
+    let config = r#"
+    [workspace]
+    compare_ignore_files = ["schema.json"]
+    "#;
+    context.write_release_plz_toml(config);
+
+    let schema_path = context.repo_dir().join("schema.json");
+    fs_err::write(&schema_path, "{}").unwrap();
+    context.push_all_changes("add schema");
+
+    context.run_release_pr().success();
+    context.merge_release_pr().await;
+    context.run_release().success();
+
+    fs_err::write(&schema_path, r#"{"type": "object"}"#).unwrap();
+    context.push_all_changes("fix: regenerate schema");
+
+    context.run_release_pr().success();
+    let opened_prs = context.opened_release_prs().await;
+    assert!(opened_prs.is_empty());
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+async fn release_plz_does_not_open_pr_if_only_excluded_paths_changed() {
+    let context = TestContext::new().await;
+
+    let cliff_config = r#"
+    [git]
+    exclude_paths = ["docs/**"]
+    "#;
+    fs_err::write(context.repo_dir().join("cliff.toml"), cliff_config).unwrap();
+    let config = r#"
+    [workspace]
+    changelog_config = "cliff.toml"
+    "#;
+    context.write_release_plz_toml(config);
+
+    let docs_dir = context.repo_dir().join("docs");
+    fs_err::create_dir(&docs_dir).unwrap();
+    let guide_path = docs_dir.join("guide.md");
+    fs_err::write(&guide_path, "# Guide").unwrap();
+    context.push_all_changes("add guide");
+
+    context.run_release_pr().success();
+    context.merge_release_pr().await;
+    context.run_release().success();
+
+    fs_err::write(&guide_path, "# Updated guide").unwrap();
+    context.push_all_changes("fix: update guide");
+
+    context.run_release_pr().success();
+    let opened_prs = context.opened_release_prs().await;
+    assert!(opened_prs.is_empty());
+}
+
 #[tokio::test]
 #[cfg_attr(not(feature = "docker-tests"), ignore)]
 async fn release_plz_honors_features_always_increment_minor_flag() {
@@ -804,7 +936,9 @@ async fn release_plz_detects_cargo_lock_updates_from_registry() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -1057,7 +1191,9 @@ Failed in:
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
     )
     .trim()
     .to_string();
@@ -1229,7 +1365,9 @@ Failed in:
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
     )
     .trim()
     .to_string();
@@ -1504,7 +1642,9 @@ async fn release_plz_updates_binary_when_library_changes_commit_regex() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -1591,7 +1731,9 @@ async fn release_plz_updates_changelog_when_version_already_bumped() {
 </p></details>
 
 ---
-This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).",
+This PR was generated with [release-plz](https://github.com/release-plz/release-plz/).
+
+<!-- release-plz-pr -->",
         )
         .trim()
     );
@@ -1,8 +1,8 @@
 use cargo_metadata::camino::Utf8Path;
 use cargo_utils::CARGO_TOML;
-use release_plz_core::{CHANGELOG_FILENAME, copy_to_temp_dir};
+use release_plz_core::{CHANGELOG_FILENAME, copy_to_temp_dir, fs_utils};
 
-use crate::helpers::test_context::run_set_version;
+use crate::helpers::test_context::{run_set_version, set_version_cmd};
 
 #[test]
 #[cfg_attr(not(feature = "docker-tests"), ignore)]
@@ -123,3 +123,171 @@ fn set_version_updates_version_in_package() {
     assert!(lockfile.contains("name = \"set-version-in-package\""));
     assert!(lockfile.contains("version = \"0.1.1\""));
 }
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_prints_json_output() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-version-in-package");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-version-in-package");
+    let outcome = run_set_version(&project_dir, "0.1.1 --output json");
+
+    let stdout = String::from_utf8(outcome.get_output().stdout.clone()).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let manifest = fs_utils::canonicalize_utf8(&project_dir.join(CARGO_TOML)).unwrap();
+    assert_eq!(
+        output,
+        serde_json::json!({
+            "packages": [
+                {
+                    "package_name": "set-version-in-package",
+                    "old_version": "0.1.0",
+                    "new_version": "0.1.1",
+                    "manifests": [manifest],
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_writes_json_output_to_file() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-version-in-package");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-version-in-package");
+    let output_file = dest_dir.path().join("output").join("set-version.json");
+    let outcome = run_set_version(
+        &project_dir,
+        &format!("0.1.1 --output json --output-file {output_file}"),
+    );
+
+    let stdout = String::from_utf8(outcome.get_output().stdout.clone()).unwrap();
+    assert!(stdout.is_empty(), "{stdout}");
+    let output: serde_json::Value =
+        serde_json::from_str(&fs_err::read_to_string(&output_file).unwrap()).unwrap();
+    assert_eq!(output["packages"][0]["new_version"], "0.1.1");
+}
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_does_not_update_cargo_lock_if_disabled() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-version-in-workspace");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-version-in-workspace");
+    let config = project_dir.join("release-plz.toml");
+    let config_content = fs_err::read_to_string(&config).unwrap();
+    fs_err::write(
+        &config,
+        format!("{config_content}\n[workspace]\nupdate_cargo_lock = false\n"),
+    )
+    .unwrap();
+    let workspace_lock = project_dir.join("Cargo.lock");
+    fs_err::copy(fixture_dir.join("Cargo.lock"), &workspace_lock).unwrap();
+    let old_lock = fs_err::read_to_string(&workspace_lock).unwrap();
+
+    run_set_version(&project_dir, "one@0.1.1");
+
+    let one_manifest = fs_err::read_to_string(project_dir.join("crates/one").join(CARGO_TOML));
+    assert!(one_manifest.unwrap().contains("version = \"0.1.1\""));
+    assert_eq!(fs_err::read_to_string(&workspace_lock).unwrap(), old_lock);
+}
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_refuses_to_edit_dirty_files() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-version-in-package");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-version-in-package");
+    for args in [
+        &["init"][..],
+        &["config", "user.name", "author_name"],
+        &["config", "user.email", "author@example.com"],
+        &["add", "."],
+        &["commit", "--no-gpg-sign", "-m", "init"],
+    ] {
+        git_cmd::git_in_dir(&project_dir, args).unwrap();
+    }
+    let changelog = project_dir.join(CHANGELOG_FILENAME);
+    let changelog_content = fs_err::read_to_string(&changelog).unwrap();
+    fs_err::write(&changelog, format!("{changelog_content}\nlocal edit\n")).unwrap();
+
+    let outcome = set_version_cmd(&project_dir, "0.1.1").assert().failure();
+    let stderr = String::from_utf8(outcome.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("uncommitted changes"), "{stderr}");
+    assert!(stderr.contains(CHANGELOG_FILENAME), "{stderr}");
+
+    run_set_version(&project_dir, "0.1.1 --allow-dirty");
+    let manifest = fs_err::read_to_string(project_dir.join(CARGO_TOML)).unwrap();
+    assert!(manifest.contains("version = \"0.1.1\""));
+}
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_updates_workspace_version() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-workspace-version");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-workspace-version");
+    run_set_version(&project_dir, "--workspace 0.2.0");
+
+    expect_test::expect![[r#"
+        [workspace]
+        resolver = "3"
+        members = ["crates/*"]
+
+        [workspace.package]
+        version = "0.2.0"
+        edition = "2024"
+    "#]]
+    .assert_eq(&fs_err::read_to_string(project_dir.join(CARGO_TOML)).unwrap());
+
+    let crates_dir = project_dir.join("crates");
+    expect_test::expect![[r#"
+        [package]
+        name = "one"
+        version.workspace = true
+        edition.workspace = true
+
+        [dependencies]
+        two = { path = "../two", version = "0.2.0" }
+    "#]]
+    .assert_eq(&fs_err::read_to_string(crates_dir.join("one").join(CARGO_TOML)).unwrap());
+
+    // Packages with an independent version are untouched, but their dependencies are updated.
+    expect_test::expect![[r#"
+        [package]
+        name = "three"
+        version = "0.5.0"
+        edition.workspace = true
+
+        [dependencies]
+        two = { path = "../two", version = "0.2.0" }
+    "#]]
+    .assert_eq(&fs_err::read_to_string(crates_dir.join("three").join(CARGO_TOML)).unwrap());
+
+    let two_changelog = fs_err::read_to_string(crates_dir.join("two").join(CHANGELOG_FILENAME));
+    assert!(two_changelog.unwrap().contains("## [0.2.0] - 2024-05-16"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "docker-tests"), ignore)]
+fn set_version_fails_if_no_package_inherits_workspace_version() {
+    let fixture_dir = Utf8Path::new("../../tests/fixtures/set-version-in-workspace");
+    assert!(fixture_dir.is_dir());
+    let dest_dir = copy_to_temp_dir(fixture_dir).unwrap();
+    let project_dir = dest_dir.path().join("set-version-in-workspace");
+
+    let outcome = set_version_cmd(&project_dir, "--workspace 0.2.0")
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(outcome.get_output().stderr.clone()).unwrap();
+    assert!(
+        stderr.contains("no package inherits the workspace version"),
+        "{stderr}"
+    );
+}
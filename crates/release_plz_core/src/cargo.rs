@@ -1,14 +1,25 @@
 use anyhow::Context;
-use cargo_metadata::{Package, camino::Utf8Path};
+use cargo_metadata::{Package, camino::Utf8Path, semver::BuildMetadata};
 use secrecy::{ExposeSecret, SecretString};
 use std::{
-    env,
     process::{Command, ExitStatus},
     time::{Duration, Instant},
 };
 use tracing::{debug, info};
 use url::Url;
 
+/// Protocol used to read the registry index when checking if a package is published.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndexProtocol {
+    /// Use the protocol of the index url.
+    #[default]
+    Auto,
+    /// Always use the sparse protocol.
+    /// The git index of crates.io is replaced with its sparse index.
+    /// Fail if a registry only offers a git index.
+    Sparse,
+}
+
 pub struct CargoRegistry {
     /// Name of the registry.
     /// [`Option::None`] means default 'crate.io'.
@@ -17,8 +28,7 @@ pub struct CargoRegistry {
 }
 
 fn cargo_cmd() -> Command {
-    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
-    let mut command = Command::new(cargo);
+    let mut command = Command::new(cargo_utils::cargo_bin());
     cargo_utils::disable_cargo_quiet(&mut command);
     command
 }
@@ -75,11 +85,19 @@ pub async fn is_published(
     timeout: Duration,
     registry: Option<&str>,
     index_url: Option<&Url>,
+    index_protocol: IndexProtocol,
     token: Option<&SecretString>,
 ) -> anyhow::Result<bool> {
     tokio::time::timeout(timeout, async {
-        let output = run_cargo_info(workspace_root, package, registry, index_url, token)
-            .context("cannot run cargo info")?;
+        let output = run_cargo_info(
+            workspace_root,
+            package,
+            registry,
+            index_url,
+            index_protocol,
+            token,
+        )
+        .context("cannot run cargo info")?;
         if output.status.success() {
             Ok(true)
         } else if cargo_info_reports_missing(&output) {
@@ -116,25 +134,51 @@ fn cargo_info_reports_missing(output: &CmdOutput) -> bool {
     stdout_and_stderr.contains("could not find")
 }
 
+/// Url of the sparse index of the registry.
+fn sparse_index_url(index_url: &Url) -> anyhow::Result<Url> {
+    if index_url.scheme().starts_with("sparse+") {
+        Ok(index_url.clone())
+    } else if index_url.as_str().trim_end_matches('/') == cargo_utils::CRATES_IO_INDEX {
+        Ok(Url::parse(cargo_utils::CRATES_IO_SPARSE_INDEX)?)
+    } else {
+        anyhow::bail!(
+            "the registry index `{index_url}` doesn't use the sparse protocol. Use a `sparse+` index url or don't set the index protocol to `sparse`"
+        )
+    }
+}
+
 fn run_cargo_info(
     workspace_root: &Utf8Path,
     package: &Package,
     registry: Option<&str>,
     index_url: Option<&Url>,
+    index_protocol: IndexProtocol,
     token: Option<&SecretString>,
 ) -> anyhow::Result<CmdOutput> {
     let registry_name = cargo_info_registry_name(registry);
-    let mut args = vec![
-        "info".to_string(),
-        format!("{}@{}", package.name, package.version),
-    ];
+    // Build metadata is ignored when comparing versions, and registries
+    // don't allow publishing two versions that differ only in metadata.
+    let mut version = package.version.clone();
+    version.build = BuildMetadata::EMPTY;
+    let mut args = vec!["info".to_string(), format!("{}@{version}", package.name)];
 
+    let mut envs = vec![];
     if let Some(index_url) = index_url {
+        let index_url = match index_protocol {
+            IndexProtocol::Auto => index_url.clone(),
+            IndexProtocol::Sparse => sparse_index_url(index_url)?,
+        };
         args.push("--index".to_string());
         args.push(index_url.as_str().to_string());
     } else {
         args.push("--registry".to_string());
         args.push(registry_name.to_string());
+        if index_protocol == IndexProtocol::Sparse && registry_name == "crates-io" {
+            envs.push((
+                "CARGO_REGISTRIES_CRATES_IO_PROTOCOL".to_string(),
+                SecretString::from("sparse"),
+            ));
+        }
     }
 
     debug!("Run `cargo {}` in {workspace_root}", args.join(" "));
@@ -142,8 +186,6 @@ fn run_cargo_info(
     let mut cmd = cargo_cmd();
     cmd.current_dir(workspace_root).args(&args);
 
-    let mut envs = vec![];
-
     if let Some(token) = token {
         let env_var = cargo_utils::cargo_registries_token_env_var_name(registry_name)?;
         envs.push((env_var, token.clone()));
@@ -159,6 +201,7 @@ pub async fn wait_until_published(
     timeout: Duration,
     registry: Option<&str>,
     index_url: Option<&Url>,
+    index_protocol: IndexProtocol,
     token: Option<&SecretString>,
 ) -> anyhow::Result<()> {
     let now: Instant = Instant::now();
@@ -166,8 +209,16 @@ pub async fn wait_until_published(
     let mut logged = false;
 
     loop {
-        let is_published =
-            is_published(workspace_root, package, timeout, registry, index_url, token).await?;
+        let is_published = is_published(
+            workspace_root,
+            package,
+            timeout,
+            registry,
+            index_url,
+            index_protocol,
+            token,
+        )
+        .await?;
         if is_published {
             break;
         } else if timeout < now.elapsed() {
@@ -191,3 +242,29 @@ pub async fn wait_until_published(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crates_io_git_index_is_replaced_with_sparse_index() {
+        let index_url = Url::parse(cargo_utils::CRATES_IO_INDEX).unwrap();
+        assert_eq!(
+            sparse_index_url(&index_url).unwrap().as_str(),
+            cargo_utils::CRATES_IO_SPARSE_INDEX
+        );
+    }
+
+    #[test]
+    fn sparse_index_is_unchanged() {
+        let index_url = Url::parse("sparse+https://my-registry.com/index/").unwrap();
+        assert_eq!(sparse_index_url(&index_url).unwrap(), index_url);
+    }
+
+    #[test]
+    fn git_index_is_rejected() {
+        let index_url = Url::parse("https://github.com/my-org/my-registry-index").unwrap();
+        assert!(sparse_index_url(&index_url).is_err());
+    }
+}
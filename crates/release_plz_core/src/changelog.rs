@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
 use chrono::{NaiveDate, TimeZone, Utc};
 use git_cliff_core::{
     changelog::Changelog as GitCliffChangelog,
@@ -11,7 +14,7 @@ use regex::Regex;
 use serde::Serialize;
 use tracing::warn;
 
-use crate::changelog_parser;
+use crate::changelog_parser::{self, ChangelogRelease};
 
 pub const CHANGELOG_HEADER: &str = r"# Changelog
 
@@ -23,9 +26,46 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 ## [Unreleased]
 ";
 
+/// Heading of the section of unreleased changes in [`CHANGELOG_HEADER`].
+pub const DEFAULT_UNRELEASED_HEADING: &str = "## [Unreleased]";
+
+/// [`CHANGELOG_HEADER`] with `unreleased_heading` instead of [`DEFAULT_UNRELEASED_HEADING`].
+pub fn changelog_header(unreleased_heading: &str) -> String {
+    CHANGELOG_HEADER.replace(DEFAULT_UNRELEASED_HEADING, unreleased_heading)
+}
+
 pub const CHANGELOG_FILENAME: &str = "CHANGELOG.md";
+/// File names recognized as changelogs when the changelog path isn't configured.
+pub const DEFAULT_CHANGELOG_FILENAMES: &[&str] = &[
+    CHANGELOG_FILENAME,
+    "CHANGES.md",
+    "HISTORY.md",
+    "RELEASES.md",
+];
 pub const RELEASE_LINK: &str = "release_link";
 pub const REMOTE: &str = "remote";
+pub const PACKAGE: &str = "package";
+
+/// Format of the changelog file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChangelogOutputFormat {
+    /// Render the changelog with the git-cliff template.
+    #[default]
+    Markdown,
+    /// Write the context of the releases, as serialized by git-cliff.
+    /// The changelog file has the `.json` extension.
+    Json,
+}
+
+impl ChangelogOutputFormat {
+    /// Path of the changelog file in this format.
+    pub fn changelog_path(self, changelog_path: Utf8PathBuf) -> Utf8PathBuf {
+        match self {
+            Self::Markdown => changelog_path,
+            Self::Json => changelog_path.with_extension("json"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Changelog<'a> {
@@ -35,6 +75,7 @@ pub struct Changelog<'a> {
     package: String,
     remote: Option<Remote>,
     pr_link: Option<String>,
+    template_context: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -70,14 +111,23 @@ impl Changelog<'_> {
             // The changelog already contains this version, so we don't update the changelog.
             return Ok(old_changelog);
         }
-        let old_header = changelog_parser::parse_header(&old_changelog);
+        let unreleased_heading = self.unreleased_heading().map(str::to_string);
+        let old_header = changelog_parser::parse_header_with_unreleased_heading(
+            &old_changelog,
+            unreleased_heading.as_deref(),
+        );
         let config = self.changelog_config(old_header.clone());
         let changelog = self.get_changelog(&config)?;
 
         // If we successfully parsed an old header, compose manually to preserve exact formatting
         // and avoid potential header duplication.
         if let Some(header) = old_header {
-            return compose_changelog(&old_changelog, &changelog, &header);
+            return compose_changelog(
+                &old_changelog,
+                &changelog,
+                &header,
+                unreleased_heading.as_deref(),
+            );
         }
 
         // Fallback: let git-cliff handle the prepend.
@@ -88,6 +138,59 @@ impl Changelog<'_> {
         String::from_utf8(out).context("cannot convert bytes to string")
     }
 
+    /// Generate the changelog as JSON: an array with the context of each release,
+    /// from the newest to the oldest.
+    /// The `extra` field of each release contains the context added by release-plz,
+    /// such as the package name and the release link.
+    pub fn generate_json(self) -> anyhow::Result<String> {
+        self.prepend_json("[]")
+    }
+
+    /// Update an existing JSON changelog.
+    pub fn prepend_json(self, old_changelog: &str) -> anyhow::Result<String> {
+        if is_version_unchanged(&self.release) {
+            // The changelog already contains this version, so we don't update the changelog.
+            return Ok(old_changelog.to_string());
+        }
+        let mut releases: Vec<serde_json::Value> =
+            serde_json::from_str(old_changelog).context("cannot parse JSON changelog")?;
+        let extra = self.extra_context()?;
+        let config = self.changelog_config(None);
+        let changelog = self.get_changelog(&config)?;
+        let new_releases = changelog
+            .releases
+            .into_iter()
+            .map(|mut release| {
+                release.extra = Some(extra.clone());
+                serde_json::to_value(release)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .context("cannot serialize release")?;
+        releases.splice(0..0, new_releases);
+        let mut json =
+            serde_json::to_string_pretty(&releases).context("cannot serialize changelog")?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Context added by release-plz to the changelog template.
+    fn extra_context(&self) -> anyhow::Result<serde_json::Value> {
+        let mut extra: serde_json::Map<String, serde_json::Value> = self
+            .template_context
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone().into()))
+            .collect();
+        extra.insert(PACKAGE.to_string(), self.package.clone().into());
+        if let Some(release_link) = &self.release_link {
+            extra.insert(RELEASE_LINK.to_string(), release_link.clone().into());
+        }
+        if let Some(remote) = &self.remote {
+            let remote = serde_json::to_value(remote).context("failed to serialize remote")?;
+            extra.insert(REMOTE.to_string(), remote);
+        }
+        Ok(extra.into())
+    }
+
     fn get_changelog<'a>(
         &'a self,
         config: &'a Config,
@@ -95,12 +198,23 @@ impl Changelog<'_> {
         let mut changelog =
             GitCliffChangelog::new(vec![self.release.clone()], config.clone(), None)
                 .context("error while building changelog")?;
+        // Added first, so that the context of release-plz takes precedence.
+        for (key, value) in &self.template_context {
+            add_context(&mut changelog, key, value)?;
+        }
         add_package_context(&mut changelog, &self.package)?;
         add_release_link_context(&mut changelog, self.release_link.as_deref())?;
         add_remote_context(&mut changelog, self.remote.as_ref())?;
         Ok(changelog)
     }
 
+    /// Last line of the configured header, if it's a level 2 heading, e.g. `## [Unreleased]`.
+    fn unreleased_heading(&self) -> Option<&str> {
+        let header = self.config.as_ref()?.changelog.header.as_deref()?;
+        let last_line = header.trim_end().lines().last()?;
+        last_line.starts_with("## ").then_some(last_line)
+    }
+
     fn changelog_config(&self, header: Option<String>) -> Config {
         let user_config = self.config.clone().unwrap_or(default_git_cliff_config());
         Config {
@@ -116,6 +230,7 @@ fn compose_changelog(
     old_changelog: &str,
     changelog: &GitCliffChangelog<'_>,
     header: &str,
+    unreleased_heading: Option<&str>,
 ) -> Result<String, anyhow::Error> {
     let generated = {
         let mut new_out = Vec::new();
@@ -125,7 +240,8 @@ fn compose_changelog(
         String::from_utf8(new_out).context("cannot convert bytes to string")?
     };
     // Parse the header so we can remove it later
-    let generated_header = crate::changelog_parser::parse_header(&generated);
+    let generated_header =
+        changelog_parser::parse_header_with_unreleased_heading(&generated, unreleased_heading);
     let header_to_strip = if let Some(gen_h) = generated_header {
         gen_h
     } else {
@@ -191,7 +307,7 @@ fn add_package_context(
     package: &str,
 ) -> Result<(), anyhow::Error> {
     changelog
-        .add_context("package", package)
+        .add_context(PACKAGE, package)
         .with_context(|| format!("failed to add `{package}` to the `package` changelog context"))?;
     Ok(())
 }
@@ -265,7 +381,7 @@ fn is_version_unchanged(release: &Release) -> bool {
     previous_version == new_version
 }
 
-fn default_git_cliff_config() -> Config {
+pub(crate) fn default_git_cliff_config() -> Config {
     Config {
         changelog: default_changelog_config(None),
         git: default_git_config(None),
@@ -274,6 +390,81 @@ fn default_git_cliff_config() -> Config {
     }
 }
 
+/// Render the changelog entry of `version` of `package` from raw commit messages,
+/// without reading a git repository.
+/// Useful to preview the changelog of a list of commits.
+/// If `config` is [`Option::None`], the default [keep a changelog](https://keepachangelog.com)
+/// configuration of release-plz is used.
+pub fn render_changelog_entry(
+    commits: &[String],
+    version: &str,
+    package: &str,
+    config: Option<Config>,
+) -> anyhow::Result<String> {
+    let commits = commits
+        .iter()
+        .map(|message| Commit::new(crate::NO_COMMIT_ID.to_string(), message.clone()))
+        .collect();
+    let config = entry_config(config.unwrap_or_else(default_git_cliff_config));
+    let entry = ChangelogBuilder::new(commits, version, package)
+        .with_config(config)
+        .build()
+        .generate()?;
+    Ok(entry.trim().to_string())
+}
+
+/// Version of the last release of a JSON changelog.
+pub(crate) fn last_version_from_json(changelog: &str) -> Option<String> {
+    let releases: Vec<serde_json::Value> = serde_json::from_str(changelog).ok()?;
+    let version = releases.first()?.get("version")?.as_str()?;
+    Some(version.to_string())
+}
+
+/// Last release of a JSON changelog generated by [`Changelog::generate_json`], rendered
+/// as markdown with the body template of `config`.
+/// If `config` is [`Option::None`], the default configuration of release-plz is used.
+pub(crate) fn last_release_from_json(
+    changelog: &str,
+    config: Option<Config>,
+) -> anyhow::Result<Option<ChangelogRelease>> {
+    let releases: Vec<serde_json::Value> =
+        serde_json::from_str(changelog).context("cannot parse JSON changelog")?;
+    let Some(mut release) = releases.into_iter().next() else {
+        return Ok(None);
+    };
+    let extra = release.get_mut("extra").map(serde_json::Value::take);
+    let mut config = entry_config(config.unwrap_or_else(default_git_cliff_config));
+    config.changelog = apply_defaults_to_changelog_config(config.changelog, None);
+    let context = serde_json::to_vec(&[release]).context("cannot serialize release")?;
+    let mut changelog = GitCliffChangelog::from_context(&mut context.as_slice(), config)
+        .context("cannot read the release of the JSON changelog")?;
+    if let Some(serde_json::Value::Object(extra)) = extra {
+        for (key, value) in extra {
+            add_context(&mut changelog, &key, value)?;
+        }
+    }
+    let mut out = Vec::new();
+    changelog
+        .generate(&mut out)
+        .context("cannot generate changelog entry")?;
+    let entry = String::from_utf8(out).context("cannot convert bytes to string")?;
+    changelog_parser::last_release_from_str(&entry)
+}
+
+/// Configuration that generates only the changelog entry, without header and footer.
+pub(crate) fn entry_config(config: Config) -> Config {
+    Config {
+        changelog: ChangelogConfig {
+            // If we set None, later this will be overriden with the defaults.
+            // Instead we just want the body.
+            header: Some(String::new()),
+            footer: Some(String::new()),
+            ..config.changelog
+        },
+        ..config
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ChangelogBuilder<'a> {
     commits: Vec<Commit<'a>>,
@@ -285,6 +476,7 @@ pub struct ChangelogBuilder<'a> {
     release_link: Option<String>,
     package: String,
     pr_link: Option<String>,
+    template_context: BTreeMap<String, String>,
 }
 
 impl<'a> ChangelogBuilder<'a> {
@@ -303,6 +495,7 @@ impl<'a> ChangelogBuilder<'a> {
             release_link: None,
             package: package.into(),
             pr_link: None,
+            template_context: BTreeMap::new(),
         }
     }
 
@@ -348,6 +541,14 @@ impl<'a> ChangelogBuilder<'a> {
         }
     }
 
+    /// User-defined variables available in the changelog template.
+    pub fn with_template_context(self, template_context: BTreeMap<String, String>) -> Self {
+        Self {
+            template_context,
+            ..self
+        }
+    }
+
     pub fn config(&self) -> Option<&Config> {
         self.config.as_ref()
     }
@@ -406,6 +607,7 @@ impl<'a> ChangelogBuilder<'a> {
             config: self.config.clone(),
             package: self.package.clone(),
             pr_link: self.pr_link.clone(),
+            template_context: self.template_context.clone(),
         }
     }
 
@@ -450,6 +652,17 @@ pub fn default_git_config(pr_link: Option<&str>) -> GitConfig {
     }
 }
 
+/// Commit preprocessor that removes the trailing PR number, e.g. ` (#123)`,
+/// from the lines of the commit messages.
+/// Add it before the other preprocessors, so that the PR number isn't linked.
+pub fn strip_pr_suffix_preprocessor() -> TextProcessor {
+    TextProcessor {
+        pattern: Regex::new(r"(?m)[ \t]*\(#[0-9]+\)$").expect("invalid regex"),
+        replace: Some(String::new()),
+        replace_command: None,
+    }
+}
+
 fn commit_parser(regex: &str, group: &str) -> CommitParser {
     CommitParser {
         message: Regex::new(regex).ok(),
@@ -505,6 +718,58 @@ fn default_changelog_body_config() -> &'static str {
 {% endfor %}"#
 }
 
+/// Like the default changelog body, but every entry ends with the GitHub handle of the
+/// commit author, e.g. `- fix bug by @octocat`.
+/// The handle is omitted if the commit isn't associated with a user of the git forge.
+pub fn default_changelog_body_with_authors() -> &'static str {
+    r#"
+## [{{ version }}]{%- if release_link -%}({{ release_link }}){% endif %} - {{ timestamp | date(format="%Y-%m-%d") }}
+{% for group, commits in commits | group_by(attribute="group") %}
+### {{ group | upper_first }}
+
+{% for commit in commits %}
+{%- if commit.scope -%}
+- *({{commit.scope}})* {% if commit.breaking %}[**breaking**] {% endif %}{{ commit.message }}{%- if commit.links %} ({% for link in commit.links %}[{{link.text}}]({{link.href}}) {% endfor -%}){% endif %}{% if commit.remote.username %} by @{{ commit.remote.username }}{% endif %}
+{% else -%}
+- {% if commit.breaking %}[**breaking**] {% endif %}{{ commit.message }}{% if commit.remote.username %} by @{{ commit.remote.username }}{% endif %}
+{% endif -%}
+{% endfor -%}
+{% endfor %}"#
+}
+
+/// Path of the changelog of the package in `package_dir`.
+/// Return the first of `filenames` that exists in `package_dir`, comparing
+/// file names case-insensitively.
+/// If none of them exists, return `package_dir/CHANGELOG.md`.
+pub fn find_changelog<S: AsRef<str>>(package_dir: &Utf8Path, filenames: &[S]) -> Utf8PathBuf {
+    let existing_files: Vec<String> = package_dir
+        .read_dir_utf8()
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_ok_and(|t| !t.is_dir()))
+                .map(|entry| entry.file_name().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    filenames
+        .iter()
+        .find_map(|filename| {
+            let filename = filename.as_ref();
+            // Prefer the exact match, in case the file system is case-sensitive
+            // and contains multiple files differing only by case.
+            existing_files.iter().find(|f| *f == filename).or_else(|| {
+                existing_files
+                    .iter()
+                    .find(|f| f.eq_ignore_ascii_case(filename))
+            })
+        })
+        .map_or_else(
+            || package_dir.join(CHANGELOG_FILENAME),
+            |filename| package_dir.join(filename),
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::NO_COMMIT_ID;
@@ -544,6 +809,42 @@ mod tests {
         .assert_eq(&changelog.generate().unwrap());
     }
 
+    #[test]
+    fn json_changelog_is_prepended_and_rendered_as_markdown() {
+        let release = |version: &str, message: &str| {
+            let commits = vec![Commit::new(NO_COMMIT_ID.to_string(), message.to_string())];
+            ChangelogBuilder::new(commits, version, "my_pkg")
+                .with_release_date(NaiveDate::from_ymd_opt(2015, 5, 15).unwrap())
+                .with_release_link(format!("https://example.com/{version}"))
+        };
+        let changelog = release("1.0.0", "feat: first")
+            .build()
+            .generate_json()
+            .unwrap();
+        let changelog = release("1.1.0", "fix: myfix")
+            .with_previous_version("1.0.0")
+            .build()
+            .prepend_json(&changelog)
+            .unwrap();
+
+        let releases: Vec<serde_json::Value> = serde_json::from_str(&changelog).unwrap();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[1]["version"], "1.0.0");
+        assert_eq!(releases[0]["extra"][PACKAGE], "my_pkg");
+        assert_eq!(last_version_from_json(&changelog).as_deref(), Some("1.1.0"));
+
+        let last_release = last_release_from_json(&changelog, None).unwrap().unwrap();
+        assert_eq!(
+            last_release.title(),
+            "[1.1.0](https://example.com/1.1.0) - 2015-05-15"
+        );
+        expect_test::expect![[r#"
+            ### Fixed
+
+            - myfix"#]]
+        .assert_eq(last_release.notes());
+    }
+
     #[test]
     fn changelog_entry_with_link_is_generated() {
         let commits = vec![Commit::new(
@@ -771,6 +1072,170 @@ mod tests {
         .assert_eq(&changelog.generate().unwrap());
     }
 
+    #[test]
+    fn template_context_is_available_in_changelog() {
+        let commits = vec![Commit::new(
+            NO_COMMIT_ID.to_string(),
+            "fix: myfix".to_string(),
+        )];
+        let template_context = BTreeMap::from([
+            ("team".to_string(), "core".to_string()),
+            (PACKAGE.to_string(), "ignored".to_string()),
+        ]);
+        let changelog = ChangelogBuilder::new(commits, "1.1.1", "my_pkg")
+            .with_config(Config {
+                changelog: ChangelogConfig {
+                    header: Some("# Changelog".to_string()),
+                    body: "{{ package }} is maintained by {{ team }}".to_string(),
+                    ..default_changelog_config(None)
+                },
+                git: default_git_config(None),
+                remote: RemoteConfig::default(),
+                bump: Bump::default(),
+            })
+            .with_template_context(template_context)
+            .build();
+
+        expect_test::expect![[r#"
+            # Changelog
+            my_pkg is maintained by core"#]]
+        .assert_eq(&changelog.generate().unwrap());
+    }
+
+    #[test]
+    fn pr_suffix_is_stripped_before_linking_prs() {
+        let commits = vec![
+            Commit::new(NO_COMMIT_ID.to_string(), "fix: thing (#5)".to_string()),
+            Commit::new(
+                NO_COMMIT_ID.to_string(),
+                "feat: see #6 for details".to_string(),
+            ),
+        ];
+        let mut git_config = default_git_config(Some("https://github.com/owner/repo/pull"));
+        git_config
+            .commit_preprocessors
+            .insert(0, strip_pr_suffix_preprocessor());
+        let changelog = ChangelogBuilder::new(commits, "1.1.1", "my_pkg")
+            .with_release_date(NaiveDate::from_ymd_opt(2015, 5, 15).unwrap())
+            .with_config(Config {
+                changelog: default_changelog_config(Some("# Changelog".to_string())),
+                git: git_config,
+                remote: RemoteConfig::default(),
+                bump: Bump::default(),
+            })
+            .build();
+
+        expect_test::expect![[r#"
+            # Changelog
+
+            ## [1.1.1] - 2015-05-15
+
+            ### Added
+
+            - see #6 for details
+
+            ### Fixed
+
+            - thing
+        "#]]
+        .assert_eq(&changelog.generate().unwrap());
+    }
+
+    #[test]
+    fn changelog_entry_is_rendered_from_commit_messages() {
+        let commits = [
+            "feat: add new api".to_string(),
+            "fix: handle empty input".to_string(),
+        ];
+        let entry = render_changelog_entry(&commits, "1.2.0", "my_pkg", None).unwrap();
+        let today = Utc::now().date_naive().to_string();
+        let entry = entry.replace(&today, "<today>");
+
+        expect_test::expect![[r"
+            ## [1.2.0] - <today>
+
+            ### Added
+
+            - add new api
+
+            ### Fixed
+
+            - handle empty input"]]
+        .assert_eq(&entry);
+    }
+
+    #[test]
+    fn changelog_shows_authors() {
+        let commits = vec![
+            Commit {
+                remote: Some(RemoteContributor {
+                    username: Some("octocat".to_string()),
+                    ..RemoteContributor::default()
+                }),
+                ..Commit::new("1111111".to_string(), "fix: myfix".to_string())
+            },
+            Commit::new(NO_COMMIT_ID.to_string(), "fix: another fix".to_string()),
+        ];
+        let changelog = ChangelogBuilder::new(commits, "1.1.1", "my_pkg")
+            .with_release_date(NaiveDate::from_ymd_opt(2015, 5, 15).unwrap())
+            .with_config(Config {
+                changelog: ChangelogConfig {
+                    header: Some("# Changelog".to_string()),
+                    body: default_changelog_body_with_authors().to_string(),
+                    ..default_changelog_config(None)
+                },
+                git: default_git_config(None),
+                remote: RemoteConfig::default(),
+                bump: Bump::default(),
+            })
+            .build();
+
+        expect_test::expect![[r"
+            # Changelog
+
+            ## [1.1.1] - 2015-05-15
+
+            ### Fixed
+
+            - myfix by @octocat
+            - another fix
+        "]]
+        .assert_eq(&changelog.generate().unwrap());
+    }
+
+    #[test]
+    fn changelog_shows_trailers() {
+        let mut commit = crate::diff::Commit::new(
+            "1111111".to_string(),
+            "fix: myfix\n\nCloses: #123".to_string(),
+        );
+        commit.trailers = Some(crate::diff::parse_trailers(&commit.message));
+        let body = r#"
+{% for commit in commits %}
+- {{ commit.message }}{% for trailer in commit.extra.trailers %} ({{ trailer.key }} {{ trailer.value }}){% endfor %}
+{%- endfor %}
+"#;
+        let changelog = ChangelogBuilder::new(vec![commit.to_cliff_commit()], "1.1.1", "my_pkg")
+            .with_config(Config {
+                changelog: ChangelogConfig {
+                    header: Some("# Changelog".to_string()),
+                    body: body.to_string(),
+                    ..default_changelog_config(None)
+                },
+                git: default_git_config(None),
+                remote: RemoteConfig::default(),
+                bump: Bump::default(),
+            })
+            .build();
+
+        expect_test::expect![[r#"
+            # Changelog
+
+
+            - myfix (Closes #123)"#]]
+        .assert_eq(&changelog.generate().unwrap());
+    }
+
     #[test]
     fn changelog_sort_newest() {
         let commits = vec![
@@ -809,6 +1274,92 @@ mod tests {
         "]]
         .assert_eq(&changelog.generate().unwrap());
     }
+
+    #[test]
+    fn changelog_is_found_case_insensitively() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap();
+        fs_err::write(dir.join("History.md"), "").unwrap();
+        fs_err::write(dir.join("Changes.md"), "").unwrap();
+        assert_eq!(
+            find_changelog(dir, DEFAULT_CHANGELOG_FILENAMES),
+            dir.join("Changes.md")
+        );
+        assert_eq!(find_changelog(dir, &["history.md"]), dir.join("History.md"));
+    }
+
+    #[test]
+    fn changelog_defaults_to_changelog_md() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap();
+        assert_eq!(
+            find_changelog(dir, DEFAULT_CHANGELOG_FILENAMES),
+            dir.join(CHANGELOG_FILENAME)
+        );
+    }
+
+    #[test]
+    fn custom_unreleased_heading_survives_prepend() {
+        let config = || Config {
+            changelog: default_changelog_config(Some(changelog_header("## [En desarrollo]"))),
+            ..default_git_cliff_config()
+        };
+        let release_date = NaiveDate::from_ymd_opt(2015, 5, 15).unwrap();
+        let old = ChangelogBuilder::new(
+            vec![Commit::new(
+                NO_COMMIT_ID.to_string(),
+                "fix: myfix".to_string(),
+            )],
+            "1.1.0",
+            "my_pkg",
+        )
+        .with_config(config())
+        .with_release_date(release_date)
+        .build()
+        .generate()
+        .unwrap();
+        let new = ChangelogBuilder::new(
+            vec![Commit::new(
+                NO_COMMIT_ID.to_string(),
+                "feat: new feature".to_string(),
+            )],
+            "1.2.0",
+            "my_pkg",
+        )
+        .with_config(config())
+        .with_release_date(release_date)
+        .build()
+        .prepend(old)
+        .unwrap();
+
+        expect_test::expect![[r"
+            # Changelog
+
+            All notable changes to this project will be documented in this file.
+
+            The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.0.0/),
+            and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+            ## [En desarrollo]
+
+            ## [1.2.0] - 2015-05-15
+
+            ### Added
+
+            - new feature
+
+            ## [1.1.0] - 2015-05-15
+
+            ### Fixed
+
+            - myfix
+        "]]
+        .assert_eq(&new);
+        assert_eq!(
+            changelog_parser::last_version_from_str(&new).unwrap(),
+            Some("1.2.0".to_string())
+        );
+    }
 }
 
 #[test]
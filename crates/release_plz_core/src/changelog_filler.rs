@@ -4,7 +4,10 @@ use anyhow::Context as _;
 use git_cliff_core::{config::ChangelogConfig, contributor::RemoteContributor};
 use git_cmd::Repo;
 
-use crate::{GitClient, NO_COMMIT_ID, diff::Commit};
+use crate::{
+    GitClient, NO_COMMIT_ID,
+    diff::{Commit, parse_trailers},
+};
 
 #[derive(Debug)]
 pub struct RequiredInfo {
@@ -14,12 +17,19 @@ pub struct RequiredInfo {
     committer_email: bool,
     remote_username: bool,
     remote_pr_number: bool,
+    trailers: bool,
 }
 
 impl RequiredInfo {
     fn is_remote_required(&self) -> bool {
         self.remote_username || self.remote_pr_number
     }
+
+    /// Don't fetch the information that requires querying the git forge.
+    pub fn skip_remote(&mut self) {
+        self.remote_username = false;
+        self.remote_pr_number = false;
+    }
 }
 
 pub async fn fill_commit<'a>(
@@ -33,6 +43,7 @@ pub async fn fill_commit<'a>(
         commit.author = existing_commit.author.clone();
         commit.committer = existing_commit.committer.clone();
         commit.remote = existing_commit.remote.clone();
+        commit.trailers = existing_commit.trailers.clone();
     } else {
         if required_info.author_name {
             commit.author.name = Some(repository.get_author_name(&commit.id)?);
@@ -46,6 +57,9 @@ pub async fn fill_commit<'a>(
         if required_info.committer_email {
             commit.committer.email = Some(repository.get_committer_email(&commit.id)?);
         }
+        if required_info.trailers {
+            commit.trailers = Some(parse_trailers(&commit.message));
+        }
         if required_info.is_remote_required() {
             let git_client = git_client
                 .context("The changelog template requires information from the remote, but git token wasn't provided")?;
@@ -80,6 +94,7 @@ pub fn get_required_info(changelog_config: &ChangelogConfig) -> RequiredInfo {
         committer_email: false,
         remote_username: false,
         remote_pr_number: false,
+        trailers: false,
     };
 
     required_info.author_name = changelog_config.body.contains("author.name");
@@ -88,6 +103,33 @@ pub fn get_required_info(changelog_config: &ChangelogConfig) -> RequiredInfo {
     required_info.committer_email = changelog_config.body.contains("committer.email");
     required_info.remote_username = changelog_config.body.contains("remote.username");
     required_info.remote_pr_number = changelog_config.body.contains("remote.pr_number");
+    required_info.trailers = changelog_config.body.contains("trailers");
 
     required_info
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn remote_info_is_not_required_if_skipped() {
+        let changelog_config = ChangelogConfig {
+            body: "{{ commit.remote.username }}".to_string(),
+            ..Default::default()
+        };
+        let mut required_info = get_required_info(&changelog_config);
+        assert!(required_info.is_remote_required());
+        required_info.skip_remote();
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let mut commit = Commit::new(repo.current_commit_hash().unwrap(), "fix: bug".to_string());
+        let mut all_commits = HashMap::new();
+        // Without a git client, filling the remote info would fail.
+        fill_commit(&mut commit, &required_info, &repo, &mut all_commits, None)
+            .await
+            .unwrap();
+        assert_eq!(commit.remote, RemoteContributor::default());
+    }
+}
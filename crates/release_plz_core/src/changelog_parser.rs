@@ -27,6 +27,31 @@ pub fn parse_header(changelog: &str) -> Option<String> {
     parse_header_fallback_strategy(changelog)
 }
 
+/// Like [`parse_header`], but the header ends with the `unreleased_heading` line
+/// if it comes before any other level 2 heading.
+/// E.g. `## [En desarrollo]` for a localized changelog.
+pub fn parse_header_with_unreleased_heading(
+    changelog: &str,
+    unreleased_heading: Option<&str>,
+) -> Option<String> {
+    static START_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^#\s*changelog").unwrap());
+    if let Some(unreleased_heading) = unreleased_heading
+        && START_RE.is_match(changelog)
+    {
+        let mut header_len = 0;
+        for line in changelog.split_inclusive('\n') {
+            header_len += line.len();
+            if line.trim_end() == unreleased_heading {
+                return Some(changelog[..header_len].to_string());
+            }
+            if line.starts_with("## ") {
+                break;
+            }
+        }
+    }
+    parse_header(changelog)
+}
+
 /// Fallback: be tolerant with formatting (e.g., no blank line after `# Changelog`).
 /// Strategy:
 /// - Ensure file starts with a Changelog H1 (case-insensitive).
@@ -87,6 +112,35 @@ pub fn last_release_from_str(changelog: &str) -> anyhow::Result<Option<Changelog
     Ok(last_release)
 }
 
+/// Split the changelog before its `max_entries + 1`-th release, so that the first part
+/// contains the header and the `max_entries` most recent releases, and the second part
+/// contains the older releases.
+/// The section of unreleased changes isn't counted as a release.
+/// Returns [`None`] if the changelog doesn't contain more than `max_entries` releases.
+pub fn split_old_releases(
+    changelog: &str,
+    max_entries: usize,
+) -> anyhow::Result<Option<(&str, &str)>> {
+    let parser = ChangelogParser::new(changelog)?;
+    let first_old_release = parser
+        .changelog
+        .values()
+        .filter(|release| !is_unreleased_section(release))
+        .nth(max_entries);
+    let split = first_old_release.map(|release| {
+        let start = heading_start(changelog, release.title);
+        changelog.split_at(start)
+    });
+    Ok(split)
+}
+
+/// Byte offset of the start of the heading line containing `title`,
+/// which is a slice of `changelog`.
+fn heading_start(changelog: &str, title: &str) -> usize {
+    let title_offset = title.as_ptr() as usize - changelog.as_ptr() as usize;
+    changelog[..title_offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
 #[derive(Debug)]
 pub struct ChangelogRelease {
     title: String,
@@ -122,7 +176,7 @@ impl<'a> ChangelogParser<'a> {
 
     fn last_release(&self) -> Option<&parse_changelog::Release<'_>> {
         let last_release = release_at(&self.changelog, 0)?;
-        let last_release = if last_release.version.to_lowercase().contains("unreleased") {
+        let last_release = if is_unreleased_section(last_release) {
             release_at(&self.changelog, 1)?
         } else {
             last_release
@@ -131,6 +185,13 @@ impl<'a> ChangelogParser<'a> {
     }
 }
 
+/// The section of unreleased changes is named `Unreleased`, or it's a custom heading
+/// without a version, e.g. `## [En desarrollo]`, that release-plz leaves empty.
+fn is_unreleased_section(release: &parse_changelog::Release) -> bool {
+    release.version.to_lowercase().contains("unreleased")
+        || (release.notes.trim().is_empty() && !release.version.chars().any(|c| c.is_ascii_digit()))
+}
+
 fn release_at<'a>(
     changelog: &'a parse_changelog::Changelog,
     index: usize,
@@ -143,6 +204,55 @@ fn release_at<'a>(
 mod tests {
     use super::*;
 
+    #[test]
+    fn old_releases_are_split_from_changelog() {
+        let changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [0.3.0] - 2024-03-01
+
+- feature
+
+```md
+## not a release
+```
+
+## [0.2.0] - 2024-02-01
+
+- fix
+
+## [0.1.0] - 2024-01-01
+
+- initial release
+";
+        let (recent, old) = split_old_releases(changelog, 2).unwrap().unwrap();
+        assert_eq!(
+            recent,
+            "\
+# Changelog
+
+## [Unreleased]
+
+## [0.3.0] - 2024-03-01
+
+- feature
+
+```md
+## not a release
+```
+
+## [0.2.0] - 2024-02-01
+
+- fix
+
+"
+        );
+        assert_eq!(old, "## [0.1.0] - 2024-01-01\n\n- initial release\n");
+        assert!(split_old_releases(changelog, 3).unwrap().is_none());
+    }
+
     fn last_changes_from_str_test(changelog: &str) -> String {
         last_changes_from_str(changelog).unwrap().unwrap()
     }
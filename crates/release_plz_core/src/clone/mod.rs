@@ -132,6 +132,29 @@ impl Cloner {
         Ok(cloned_pkgs)
     }
 
+    /// Return the checksum of the specified crate reported by the registry index,
+    /// after downloading its `.crate` file.
+    ///
+    /// Returns `Ok(None)` if the registry doesn't contain the crate.
+    /// Returns an error if the download fails, e.g. because cargo found that the
+    /// downloaded `.crate` file doesn't match the checksum of the index.
+    pub async fn published_checksum(&self, crate_: &Crate) -> CargoResult<Option<String>> {
+        let _lock = self.acquire_cargo_package_cache_lock()?;
+        let src = self.get_source()?;
+        let name = &crate_.name;
+        let vers = crate_.version.as_deref();
+        let Some(summary) = query_latest_package_summary(src.as_ref(), name, vers).await? else {
+            return Ok(None);
+        };
+        let checksum = summary
+            .as_summary()
+            .checksum()
+            .with_context(|| format!("the registry index doesn't contain the checksum of {name}"))?
+            .to_string();
+        self.download_package(&summary)?;
+        Ok(Some(checksum))
+    }
+
     fn acquire_cargo_package_cache_lock(
         &self,
     ) -> CargoResult<cargo::util::cache_lock::CacheLock<'_>> {
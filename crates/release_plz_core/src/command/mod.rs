@@ -1,9 +1,13 @@
 mod release;
+mod release_notes;
 mod release_pr;
 pub mod set_version;
 mod trusted_publishing;
 mod update;
+mod yank;
 
 pub use release::*;
+pub use release_notes::*;
 pub use release_pr::*;
 pub use update::*;
+pub use yank::*;
@@ -1,9 +1,10 @@
 use std::{
     collections::{BTreeMap, HashSet},
+    sync::OnceLock,
     time::Duration,
 };
 
-use crate::command::trusted_publishing;
+use crate::command::{trusted_publishing, update::updater::publishes_to_crates_io};
 use anyhow::Context;
 use cargo::util::VersionExt;
 use cargo_metadata::{
@@ -11,21 +12,39 @@ use cargo_metadata::{
     camino::{Utf8Path, Utf8PathBuf},
     semver::Version,
 };
-use git_cmd::Repo;
+use cargo_utils::CARGO_TOML;
+use git_cmd::{GitIdentity, Repo};
+use regex::Regex;
 use secrecy::SecretString;
 use serde::Serialize;
-use tracing::{debug, info, instrument, trace, warn};
+use sha2::{Digest as _, Sha256};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{debug, error, info, instrument, trace, warn};
 use url::Url;
 
 use crate::{
-    CHANGELOG_FILENAME, DEFAULT_BRANCH_PREFIX, GitForge, PackagePath, Project, Publishable as _,
-    ReleaseMetadata, ReleaseMetadataBuilder, Remote,
-    cargo::{CargoRegistry, CmdOutput, is_published, run_cargo_with_env, wait_until_published},
+    ChangelogOutputFormat, DEFAULT_BRANCH_PREFIX, GitForge, PackagePath, Project, Publishable as _,
+    ReleaseMetadata, ReleaseMetadataBuilder, Remote, RepoUrl,
+    cargo::{
+        CargoRegistry, CmdOutput, IndexProtocol, is_published, run_cargo_with_env,
+        wait_until_published,
+    },
+    changelog::last_release_from_json,
     changelog_parser,
-    git::forge::GitClient,
+    command::release_notes::{notes_from_commits, package_glob},
+    download, fs_utils,
+    git::forge::{ForgeType, GitClient, GitPr},
+    manifest_dir,
+    pr::release_pr_marker,
     pr_parser::{Pr, prs_from_text},
+    release_order, root_repo_path,
+    tera::{check_template_context, combined_tag_name, single_tag_name_template},
+    user_command::UserCommandContext,
 };
 
+/// Name of the crates.io registry in the cargo configuration.
+const CRATES_IO_REGISTRY: &str = "crates-io";
+
 #[derive(Debug)]
 pub struct ReleaseRequest {
     /// Cargo metadata.
@@ -37,11 +56,31 @@ pub struct ReleaseRequest {
     registry: Option<String>,
     /// Token used to publish to the cargo registry.
     token: Option<SecretString>,
+    /// Command that prints the token used to publish to the cargo registry.
+    /// Used if [`Self::token`] is unspecified.
+    registry_token_command: Vec<String>,
+    /// Token printed by [`Self::registry_token_command`].
+    /// The command runs at most once per release.
+    registry_token_from_command: OnceLock<SecretString>,
     /// Perform all checks without uploading.
     dry_run: bool,
+    /// If true, run the `verify_command` of the packages even in dry-run mode.
+    dry_run_verify: bool,
+    /// If true, update the name and the body of the git releases that already exist.
+    force_release_body: bool,
+    /// If true, list the tags of the remote repository once and skip the packages whose
+    /// git tag already exists there, before checking anything else.
+    skip_existing_tags: bool,
+    /// If true, keep releasing the other packages when the release of a package fails.
+    keep_going: bool,
+    /// If true, don't create git tags, regardless of the configuration of the packages.
+    /// Git releases are still created for the existing tags.
+    no_tag: bool,
     /// If true, release on every commit.
     /// If false, release only on Release PR merge.
     release_always: bool,
+    /// If true, post a comment summarizing the release on the merged release PR.
+    comment_on_release: bool,
     /// Publishes GitHub release.
     git_release: Option<GitRelease>,
     /// GitHub/Gitea/Gitlab repository url where your project is hosted.
@@ -54,6 +93,67 @@ pub struct ReleaseRequest {
     publish_timeout: Duration,
     /// PR Branch Prefix
     branch_prefix: String,
+    /// If set, release PRs are identified by matching their branch against this regex,
+    /// instead of checking if it starts with `branch_prefix`.
+    release_pr_branch_regex: Option<Regex>,
+    /// Branch where release PRs are merged.
+    /// If unspecified, the branch where release-plz runs is used.
+    base_branch: Option<String>,
+    /// Tagger of the annotated git tags created locally.
+    git_identity: GitIdentity,
+    /// If true, create a single git tag and git release for all the packages,
+    /// instead of one per package.
+    single_tag: bool,
+    /// If set, after releasing the packages, create an additional git release
+    /// covering all of them.
+    combined_git_release: Option<CombinedGitRelease>,
+    /// If true, after publishing a package, download its `.crate` file from the registry
+    /// and check that its checksum matches the one of the `.crate` file built locally.
+    verify_published_checksum: bool,
+    /// If true, don't wait for the published package to be available in the registry index.
+    skip_publish_wait: bool,
+    /// If false, don't query the git forge to find the contributors of the release.
+    fetch_contributors: bool,
+    /// Protocol used to read the registry index when checking if a package is published.
+    index_protocol: IndexProtocol,
+    /// Channel where the progress of the release is sent.
+    progress_sender: Option<UnboundedSender<ReleaseEvent>>,
+    /// File names recognized as changelogs, used when the changelog path of a package
+    /// isn't configured.
+    changelog_filenames: Vec<String>,
+    /// Format of the changelogs.
+    changelog_output_format: ChangelogOutputFormat,
+    /// git-cliff configuration used to render the git release body from JSON changelogs.
+    /// If unspecified, the default release-plz configuration is used.
+    changelog_config: Option<git_cliff_core::config::Config>,
+    /// User-defined variables available in the git release body template.
+    template_context: BTreeMap<String, String>,
+}
+
+/// Step of the release of a package, sent to the
+/// [`ReleaseRequest::with_progress_sender`] channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseEvent {
+    pub package_name: String,
+    pub version: Version,
+    pub kind: ReleaseEventKind,
+    /// If true, the step was skipped because release-plz is running in dry-run mode.
+    /// The event is sent anyway, so that you can see what release-plz would do.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseEventKind {
+    /// `cargo publish` is about to run.
+    PublishStarted,
+    /// The package is available in the registry.
+    Published,
+    /// The git tag of the package was created.
+    TagCreated,
+    /// The git release of the package was created.
+    ReleaseCreated,
+    /// The package wasn't released because it was already published or tagged.
+    Skipped,
 }
 
 impl ReleaseRequest {
@@ -63,13 +163,38 @@ impl ReleaseRequest {
             metadata,
             registry: None,
             token: None,
+            registry_token_command: vec![],
+            registry_token_from_command: OnceLock::new(),
             dry_run: false,
+            dry_run_verify: false,
+            force_release_body: false,
+            skip_existing_tags: false,
+            keep_going: false,
+            no_tag: false,
             git_release: None,
             repo_url: None,
             packages_config: PackagesConfig::default(),
             publish_timeout: minutes_30,
             release_always: true,
+            comment_on_release: false,
             branch_prefix: DEFAULT_BRANCH_PREFIX.to_string(),
+            release_pr_branch_regex: None,
+            base_branch: None,
+            git_identity: GitIdentity::default(),
+            single_tag: false,
+            combined_git_release: None,
+            verify_published_checksum: false,
+            skip_publish_wait: false,
+            fetch_contributors: true,
+            index_protocol: IndexProtocol::default(),
+            progress_sender: None,
+            changelog_filenames: crate::DEFAULT_CHANGELOG_FILENAMES
+                .iter()
+                .map(|f| (*f).to_string())
+                .collect(),
+            changelog_output_format: ChangelogOutputFormat::default(),
+            changelog_config: None,
+            template_context: BTreeMap::new(),
         }
     }
 
@@ -78,6 +203,10 @@ impl ReleaseRequest {
         cargo_utils::workspace_manifest(&self.metadata)
     }
 
+    pub fn cargo_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
     pub fn with_registry(mut self, registry: impl Into<String>) -> Self {
         self.registry = Some(registry.into());
         self
@@ -88,11 +217,41 @@ impl ReleaseRequest {
         self
     }
 
+    pub fn with_registry_token_command(mut self, registry_token_command: Vec<String>) -> Self {
+        self.registry_token_command = registry_token_command;
+        self
+    }
+
     pub fn with_dry_run(mut self, dry_run: bool) -> Self {
         self.dry_run = dry_run;
         self
     }
 
+    pub fn with_dry_run_verify(mut self, dry_run_verify: bool) -> Self {
+        self.dry_run_verify = dry_run_verify;
+        self
+    }
+
+    pub fn with_force_release_body(mut self, force_release_body: bool) -> Self {
+        self.force_release_body = force_release_body;
+        self
+    }
+
+    pub fn with_skip_existing_tags(mut self, skip_existing_tags: bool) -> Self {
+        self.skip_existing_tags = skip_existing_tags;
+        self
+    }
+
+    pub fn with_keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    pub fn with_no_tag(mut self, no_tag: bool) -> Self {
+        self.no_tag = no_tag;
+        self
+    }
+
     pub fn with_git_release(mut self, git_release: GitRelease) -> Self {
         self.git_release = Some(git_release);
         self
@@ -125,6 +284,111 @@ impl ReleaseRequest {
         self
     }
 
+    pub fn with_release_pr_branch_regex(mut self, regex: &str) -> anyhow::Result<Self> {
+        let regex = Regex::new(regex).context("invalid release_pr_branch_regex pattern")?;
+        self.release_pr_branch_regex = Some(regex);
+        Ok(self)
+    }
+
+    /// Whether the branch is the branch of a release PR.
+    fn is_release_pr_branch(&self, branch: &str) -> bool {
+        match &self.release_pr_branch_regex {
+            Some(regex) => regex.is_match(branch),
+            None => branch.starts_with(&self.branch_prefix),
+        }
+    }
+
+    /// Whether the PR is a release PR of this workspace.
+    /// The `pr_marker` identifies release PRs opened before a change of the branch prefix.
+    fn is_release_pr(&self, pr: &GitPr, pr_marker: &str) -> bool {
+        self.is_release_pr_branch(pr.branch()) || pr.has_marker(pr_marker)
+    }
+
+    pub fn with_base_branch(mut self, base_branch: Option<String>) -> Self {
+        self.base_branch = base_branch;
+        self
+    }
+
+    pub fn with_git_identity(mut self, git_identity: GitIdentity) -> Self {
+        self.git_identity = git_identity;
+        self
+    }
+
+    pub fn with_single_tag(mut self, single_tag: bool) -> Self {
+        self.single_tag = single_tag;
+        self
+    }
+
+    pub fn with_combined_git_release(mut self, combined_git_release: CombinedGitRelease) -> Self {
+        self.combined_git_release = Some(combined_git_release);
+        self
+    }
+
+    pub fn with_verify_published_checksum(mut self, verify_published_checksum: bool) -> Self {
+        self.verify_published_checksum = verify_published_checksum;
+        self
+    }
+
+    pub fn with_skip_publish_wait(mut self, skip_publish_wait: bool) -> Self {
+        self.skip_publish_wait = skip_publish_wait;
+        self
+    }
+
+    /// Set the user-defined variables available in the git release body template.
+    /// Returns an error if a variable is already set by release-plz.
+    pub fn with_template_context(
+        mut self,
+        template_context: BTreeMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        check_template_context(&template_context)?;
+        self.template_context = template_context;
+        Ok(self)
+    }
+
+    pub fn with_comment_on_release(mut self, comment_on_release: bool) -> Self {
+        self.comment_on_release = comment_on_release;
+        self
+    }
+
+    pub fn with_fetch_contributors(mut self, fetch_contributors: bool) -> Self {
+        self.fetch_contributors = fetch_contributors;
+        self
+    }
+
+    pub fn with_index_protocol(mut self, index_protocol: IndexProtocol) -> Self {
+        self.index_protocol = index_protocol;
+        self
+    }
+
+    /// Set the file names recognized as changelogs, in order of preference.
+    /// They are used when the changelog path of a package isn't configured.
+    pub fn with_changelog_filenames(mut self, changelog_filenames: Vec<String>) -> Self {
+        self.changelog_filenames = changelog_filenames;
+        self
+    }
+
+    pub fn with_changelog_output_format(
+        mut self,
+        changelog_output_format: ChangelogOutputFormat,
+    ) -> Self {
+        self.changelog_output_format = changelog_output_format;
+        self
+    }
+
+    pub fn with_changelog_config(
+        mut self,
+        changelog_config: git_cliff_core::config::Config,
+    ) -> Self {
+        self.changelog_config = Some(changelog_config);
+        self
+    }
+
+    /// Send a [`ReleaseEvent`] to the given channel for every step of the release.
+    pub fn with_progress_sender(mut self, progress_sender: UnboundedSender<ReleaseEvent>) -> Self {
+        self.progress_sender = Some(progress_sender);
+        self
+    }
+
     /// Set release config for a specific package.
     pub fn with_package_config(
         mut self,
@@ -137,15 +401,31 @@ impl ReleaseRequest {
 
     pub fn changelog_path(&self, package: &Package) -> Utf8PathBuf {
         let config = self.get_package_config(&package.name);
-        config
+        let changelog_path = config
             .changelog_path
             .map(|p| self.metadata.workspace_root.join(p))
             .unwrap_or_else(|| {
-                package
+                let package_path = package
                     .package_path()
-                    .expect("can't determine package path")
-                    .join(CHANGELOG_FILENAME)
-            })
+                    .expect("can't determine package path");
+                crate::find_changelog(package_path, &self.changelog_filenames)
+            });
+        self.changelog_output_format.changelog_path(changelog_path)
+    }
+
+    fn send_event(&self, package: &Package, kind: ReleaseEventKind) {
+        if let Some(progress_sender) = &self.progress_sender {
+            let event = ReleaseEvent {
+                package_name: package.name.to_string(),
+                version: package.version.clone(),
+                kind,
+                dry_run: self.dry_run,
+            };
+            // The receiver might have been dropped. This shouldn't stop the release.
+            if progress_sender.send(event).is_err() {
+                trace!("release progress receiver dropped");
+            }
+        }
     }
 
     fn is_publish_enabled(&self, package: &str) -> bool {
@@ -153,14 +433,29 @@ impl ReleaseRequest {
         config.publish.enabled
     }
 
+    /// With `no_tag`, the tag might exist while the package isn't published yet,
+    /// so we rely on the registry to know if the package needs to be released.
+    fn is_tag_check_skipped(&self, package: &str) -> bool {
+        self.no_tag && self.is_publish_enabled(package)
+    }
+
+    /// If `single_tag` is enabled, packages don't have their own git release.
+    /// The same happens if the combined git release replaces the package releases.
     fn is_git_release_enabled(&self, package: &str) -> bool {
         let config = self.get_package_config(package);
-        config.git_release.enabled
+        let combined_only = self.combined_git_release.as_ref().is_some_and(|c| c.only);
+        !self.single_tag && !combined_only && config.git_release.enabled
     }
 
+    /// Whether a git tag is created for the package, either its own or the single tag.
+    fn is_tag_created(&self, package: &str) -> bool {
+        self.is_git_tag_enabled(package) || (self.single_tag && !self.no_tag)
+    }
+
+    /// If `single_tag` is enabled, packages don't have their own git tag.
     fn is_git_tag_enabled(&self, package: &str) -> bool {
         let config = self.get_package_config(package);
-        config.git_tag.enabled
+        !self.no_tag && !self.single_tag && config.git_tag.enabled
     }
 
     pub fn get_package_config(&self, package: &str) -> ReleaseConfig {
@@ -177,6 +472,11 @@ impl ReleaseRequest {
         config.no_verify
     }
 
+    pub fn locked(&self, package: &str) -> bool {
+        let config = self.get_package_config(package);
+        config.locked
+    }
+
     pub fn features(&self, package: &str) -> Vec<String> {
         let config = self.get_package_config(package);
         config.features.clone()
@@ -187,16 +487,43 @@ impl ReleaseRequest {
         config.all_features
     }
 
+    pub fn target(&self, package: &str) -> Option<String> {
+        let config = self.get_package_config(package);
+        config.target
+    }
+
+    /// Verify command of the package.
+    /// If `cargo publish` runs with `--locked`, `--locked` is added to cargo commands, too.
+    pub fn verify_command(&self, package: &str) -> Vec<String> {
+        let config = self.get_package_config(package);
+        if config.locked {
+            with_locked_flag(config.verify_command)
+        } else {
+            config.verify_command
+        }
+    }
+
     /// Find the token to use for the given `registry` ([`Option::None`] means crates.io).
     fn find_registry_token(&self, registry: Option<&str>) -> anyhow::Result<Option<SecretString>> {
-        let is_registry_same_as_request = self.registry.as_deref() == registry;
-        let token = is_registry_same_as_request
-            .then(|| self.token.clone())
-            .flatten()
-            // if the registry is not the same as the request or if there's no token in the request,
-            // try to find the token in the Cargo credentials file or in the environment variables.
-            .or(cargo_utils::registry_token(self.registry.as_deref())?);
-        Ok(token)
+        let request_token = if self.registry.as_deref() == registry {
+            self.request_token()?
+        } else {
+            None
+        };
+        find_registry_token(self.registry.as_deref(), request_token, registry)
+    }
+
+    /// Token provided by the user for [`Self::registry`], either directly or
+    /// via [`Self::registry_token_command`].
+    fn request_token(&self) -> anyhow::Result<Option<&SecretString>> {
+        if self.token.is_some() || self.registry_token_command.is_empty() {
+            return Ok(self.token.as_ref());
+        }
+        if let Some(token) = self.registry_token_from_command.get() {
+            return Ok(Some(token));
+        }
+        let token = run_registry_token_command(&self.registry_token_command)?;
+        Ok(Some(self.registry_token_from_command.get_or_init(|| token)))
     }
 
     /// Checks for inconsistency in the `publish` fields in the workspace metadata and release-plz config.
@@ -228,8 +555,14 @@ impl ReleaseRequest {
 impl ReleaseMetadataBuilder for ReleaseRequest {
     fn get_release_metadata(&self, package_name: &str) -> Option<ReleaseMetadata> {
         let config = self.get_package_config(package_name);
+        let tag_name_template = if self.single_tag {
+            Some(single_tag_name_template())
+        } else {
+            config.git_tag.name_template.clone()
+        };
         config.release.then(|| ReleaseMetadata {
-            tag_name_template: config.git_tag.name_template.clone(),
+            tag_name_template,
+            tag_prefix: config.git_tag.prefix.clone(),
             release_name_template: config.git_release.name_template.clone(),
         })
     }
@@ -286,6 +619,9 @@ pub struct ReleaseConfig {
     /// Don't verify the contents by building them.
     /// If true, `release-plz` adds the `--no-verify` flag to `cargo publish`.
     no_verify: bool,
+    /// Fail if the `Cargo.lock` file needs to be updated.
+    /// If true, `release-plz` adds the `--locked` flag to `cargo publish`.
+    locked: bool,
     /// Allow dirty working directories to be packaged.
     /// If true, `release-plz` adds the `--allow-dirty` flag to `cargo publish`.
     allow_dirty: bool,
@@ -295,12 +631,21 @@ pub struct ReleaseConfig {
     /// Enable all features when packaging the crate.
     /// If true, pass the `--all-features` flag to `cargo publish`.
     all_features: bool,
+    /// Target triple used to verify the package, e.g. `wasm32-unknown-unknown`.
+    /// If set, pass the `--target` flag to `cargo publish`.
+    target: Option<String>,
+    /// Command run in the package directory before publishing the package,
+    /// e.g. `["cargo", "check", "--all-targets"]`.
+    /// If the command fails, the release fails.
+    verify_command: Vec<String>,
     /// High-level toggle to process this package or ignore it
     release: bool,
     changelog_path: Option<Utf8PathBuf>,
     /// Whether this package has a changelog that release-plz updates or not.
     /// Default: `true`.
     changelog_update: bool,
+    /// Source of the changes used in the git release body.
+    changelog_body_from: ChangelogBodyFrom,
 }
 
 impl ReleaseConfig {
@@ -319,6 +664,11 @@ impl ReleaseConfig {
         self
     }
 
+    pub fn with_git_tag_prefix(mut self, prefix: String) -> Self {
+        self.git_tag = self.git_tag.set_prefix(prefix);
+        self
+    }
+
     pub fn with_no_verify(mut self, no_verify: bool) -> Self {
         self.no_verify = no_verify;
         self
@@ -329,6 +679,11 @@ impl ReleaseConfig {
         self
     }
 
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
     pub fn with_features(mut self, features: Vec<String>) -> Self {
         self.features = features;
         self
@@ -339,6 +694,16 @@ impl ReleaseConfig {
         self
     }
 
+    pub fn with_target(mut self, target: String) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_verify_command(mut self, verify_command: Vec<String>) -> Self {
+        self.verify_command = verify_command;
+        self
+    }
+
     pub fn with_release(mut self, release: bool) -> Self {
         self.release = release;
         self
@@ -354,6 +719,11 @@ impl ReleaseConfig {
         self
     }
 
+    pub fn with_changelog_body_from(mut self, changelog_body_from: ChangelogBodyFrom) -> Self {
+        self.changelog_body_from = changelog_body_from;
+        self
+    }
+
     pub fn publish(&self) -> &PublishConfig {
         &self.publish
     }
@@ -370,12 +740,16 @@ impl Default for ReleaseConfig {
             git_release: GitReleaseConfig::default(),
             git_tag: GitTagConfig::default(),
             no_verify: false,
+            locked: false,
             allow_dirty: false,
             features: vec![],
             all_features: false,
+            target: None,
+            verify_command: vec![],
             release: true,
             changelog_path: None,
             changelog_update: true,
+            changelog_body_from: ChangelogBodyFrom::default(),
         }
     }
 }
@@ -383,6 +757,11 @@ impl Default for ReleaseConfig {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PublishConfig {
     enabled: bool,
+    /// Registries where the package can be published.
+    /// If empty, the package can be published to all its registries.
+    registries: Vec<String>,
+    /// Registries where the package must not be published.
+    excluded_registries: Vec<String>,
 }
 
 impl Default for PublishConfig {
@@ -393,12 +772,36 @@ impl Default for PublishConfig {
 
 impl PublishConfig {
     pub fn enabled(enabled: bool) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            registries: vec![],
+            excluded_registries: vec![],
+        }
+    }
+
+    pub fn with_registries(mut self, registries: Vec<String>) -> Self {
+        self.registries = registries;
+        self
+    }
+
+    pub fn with_excluded_registries(mut self, excluded_registries: Vec<String>) -> Self {
+        self.excluded_registries = excluded_registries;
+        self
     }
 
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    /// Whether the package can be published to the given registry.
+    /// If the registry name is unknown, the package is published to crates.io.
+    fn is_registry_enabled(&self, registry: Option<&str>) -> bool {
+        let registry = registry.unwrap_or(CRATES_IO_REGISTRY);
+        let is_allowed =
+            self.registries.is_empty() || self.registries.iter().any(|r| r == registry);
+        let is_excluded = self.excluded_registries.iter().any(|r| r == registry);
+        is_allowed && !is_excluded
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -409,14 +812,42 @@ pub enum ReleaseType {
     Auto,
 }
 
+/// Source of the changes of the release, used in the git release body.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogBodyFrom {
+    /// Last entry of the changelog file.
+    #[default]
+    File,
+    /// Commits since the previous release of the package.
+    Commits,
+}
+
+/// Whether to mark the git release as the latest release of the repository.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GitReleaseLatest {
+    /// Let the git forge decide.
+    #[default]
+    Default,
+    /// Mark the git release as latest (`true`) or not (`false`).
+    Enabled(bool),
+    /// Mark the git release as latest only if its version is the highest one among the
+    /// released packages and the existing git tags.
+    /// Pre-releases are never marked as latest.
+    Auto,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitReleaseConfig {
     enabled: bool,
     draft: bool,
-    latest: Option<bool>,
+    latest: GitReleaseLatest,
     release_type: ReleaseType,
     name_template: Option<String>,
     body_template: Option<String>,
+    /// If true, fail instead of creating a git release with an empty body.
+    require_changelog_body: bool,
+    /// If true, attach a `CycloneDX` SBOM of the package to the git release.
+    sbom: bool,
 }
 
 impl Default for GitReleaseConfig {
@@ -430,10 +861,12 @@ impl GitReleaseConfig {
         Self {
             enabled,
             draft: false,
-            latest: None,
+            latest: GitReleaseLatest::Default,
             release_type: ReleaseType::default(),
             name_template: None,
             body_template: None,
+            require_changelog_body: false,
+            sbom: false,
         }
     }
 
@@ -446,8 +879,8 @@ impl GitReleaseConfig {
         self
     }
 
-    pub fn set_latest(mut self, latest: bool) -> Self {
-        self.latest = Some(latest);
+    pub fn set_latest(mut self, latest: GitReleaseLatest) -> Self {
+        self.latest = latest;
         self
     }
 
@@ -466,6 +899,16 @@ impl GitReleaseConfig {
         self
     }
 
+    pub fn set_require_changelog_body(mut self, require_changelog_body: bool) -> Self {
+        self.require_changelog_body = require_changelog_body;
+        self
+    }
+
+    pub fn set_sbom(mut self, sbom: bool) -> Self {
+        self.sbom = sbom;
+        self
+    }
+
     pub fn is_pre_release(&self, version: &Version) -> bool {
         match self.release_type {
             ReleaseType::Pre => true,
@@ -473,12 +916,31 @@ impl GitReleaseConfig {
             ReleaseType::Prod => false,
         }
     }
+
+    /// Value of the `latest` flag of the git release of `version`.
+    /// `highest_version` is the highest stable version among the released packages
+    /// and the existing git tags.
+    /// `None` lets the git forge decide.
+    fn latest(&self, version: &Version, highest_version: Option<&Version>) -> Option<bool> {
+        match self.latest {
+            GitReleaseLatest::Default => None,
+            GitReleaseLatest::Enabled(latest) => Some(latest),
+            GitReleaseLatest::Auto => Some(
+                !version.is_prerelease()
+                    && highest_version.is_none_or(|highest| version >= highest),
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitTagConfig {
     enabled: bool,
     name_template: Option<String>,
+    /// Prefix prepended to the name template.
+    prefix: String,
+    /// If false, create lightweight tags, without message.
+    annotated: bool,
 }
 
 impl Default for GitTagConfig {
@@ -492,14 +954,26 @@ impl GitTagConfig {
         Self {
             enabled,
             name_template: None,
+            prefix: String::new(),
+            annotated: true,
         }
     }
 
+    pub fn with_annotated(mut self, annotated: bool) -> Self {
+        self.annotated = annotated;
+        self
+    }
+
     pub fn set_name_template(mut self, name_template: Option<String>) -> Self {
         self.name_template = name_template;
         self
     }
 
+    pub fn set_prefix(mut self, prefix: String) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -511,9 +985,62 @@ pub struct GitRelease {
     pub forge: GitForge,
 }
 
+/// Git release linking the releases of all the packages released together.
+#[derive(Debug, Clone, Default)]
+pub struct CombinedGitRelease {
+    /// Tera template of the git tag of the release.
+    /// If unspecified, `release-{{ date }}-{{ time }}` is used.
+    tag_name_template: Option<String>,
+    /// Create the combined git release instead of the git releases of the packages.
+    only: bool,
+}
+
+impl CombinedGitRelease {
+    pub fn with_tag_name_template(mut self, tag_name_template: impl Into<String>) -> Self {
+        self.tag_name_template = Some(tag_name_template.into());
+        self
+    }
+
+    /// If `true`, the packages don't have their own git release.
+    /// Their git tags are still created.
+    pub fn with_only(mut self, only: bool) -> Self {
+        self.only = only;
+        self
+    }
+}
+
 #[derive(Serialize, Default, Debug)]
 pub struct Release {
     releases: Vec<PackageRelease>,
+    /// Packages that weren't released because of an error.
+    /// Only populated when [`ReleaseRequest::with_keep_going`] is enabled.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<PackageFailure>,
+}
+
+impl Release {
+    /// Returns an error listing the packages that failed to be released, if any.
+    pub fn check_failures(&self) -> anyhow::Result<()> {
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+        let failures: Vec<String> = self
+            .failures
+            .iter()
+            .map(|f| format!("- {}: {}", f.package_name, f.error))
+            .collect();
+        anyhow::bail!(
+            "failed to release {} package(s):\n{}",
+            self.failures.len(),
+            failures.join("\n")
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageFailure {
+    package_name: String,
+    error: String,
 }
 
 #[derive(Serialize, Debug)]
@@ -532,16 +1059,14 @@ pub struct PackageRelease {
 #[instrument(skip(input))]
 pub async fn release(input: &ReleaseRequest) -> anyhow::Result<Option<Release>> {
     let overrides = input.packages_config.overridden_packages();
-    let project = Project::new(
-        &input.local_manifest(),
-        None,
-        &overrides,
-        &input.metadata,
-        input,
-    )?;
-    let repo = Repo::new(&input.metadata.workspace_root)?;
+    let local_manifest = input.local_manifest();
+    let project = Project::new(&local_manifest, None, &overrides, &input.metadata, input)?;
+    let repo = Repo::new(&input.metadata.workspace_root)?.with_identity(input.git_identity.clone());
     let git_client = get_git_client(input)?;
-    let should_release = should_release(input, &repo, &git_client).await?;
+    let workspace_dir = fs_utils::strip_prefix(manifest_dir(&local_manifest)?, project.root())?;
+    let pr_marker = release_pr_marker(workspace_dir);
+    let (should_release, release_pr) =
+        should_release(input, &repo, &git_client, &pr_marker).await?;
     debug!("should release: {should_release:?}");
 
     if should_release == ShouldRelease::No {
@@ -557,7 +1082,7 @@ pub async fn release(input: &ReleaseRequest) -> anyhow::Result<Option<Release>>
                 checkout_done = true;
             }
             // The commit does not exist if the PR was squashed.
-            Err(_) => trace!("checkout failed; continuing"),
+            Err(e) => warn!("can't checkout commit {commit}, releasing the current commit: {e:?}"),
         }
     }
 
@@ -573,9 +1098,71 @@ pub async fn release(input: &ReleaseRequest) -> anyhow::Result<Option<Release>>
         }
     }
 
+    if let (Ok(Some(release)), Some(pr_number)) = (&release, release_pr)
+        && input.comment_on_release
+        && !input.dry_run
+        && !release.releases.is_empty()
+    {
+        let summary = release_summary(input, &project, &repo, &release.releases);
+        // The packages are already released, so don't fail because of the comment.
+        if let Err(e) = git_client.comment_pr(pr_number, &summary).await {
+            warn!("can't comment the release summary on pr #{pr_number}: {e:?}");
+        }
+    }
+
     release
 }
 
+/// Comment listing the released packages, with links to their git tag and crates.io page.
+fn release_summary(
+    input: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    releases: &[PackageRelease],
+) -> String {
+    let repo_url = match &input.repo_url {
+        Some(url) => RepoUrl::new(url).ok(),
+        None => RepoUrl::from_repo(repo).ok(),
+    };
+    let lines: Vec<String> = releases
+        .iter()
+        .map(|release| {
+            let tag = input
+                .is_tag_created(&release.package_name)
+                .then(|| match &repo_url {
+                    Some(url) => format!(
+                        "[`{}`]({})",
+                        release.tag,
+                        url.git_release_link(&release.tag, &release.tag)
+                    ),
+                    None => format!("`{}`", release.tag),
+                });
+            let on_crates_io = input.is_publish_enabled(&release.package_name)
+                && project
+                    .publishable_packages()
+                    .iter()
+                    .find(|p| p.name == release.package_name)
+                    .is_some_and(|p| publishes_to_crates_io(p, input.registry.as_deref()));
+            release_summary_line(release, tag.as_deref(), on_crates_io)
+        })
+        .collect();
+    format!("Released:\n\n{}", lines.join("\n"))
+}
+
+fn release_summary_line(release: &PackageRelease, tag: Option<&str>, on_crates_io: bool) -> String {
+    let mut line = format!("- `{}` {}", release.package_name, release.version);
+    if let Some(tag) = tag {
+        line.push_str(&format!(": {tag}"));
+    }
+    if on_crates_io {
+        line.push_str(&format!(
+            " ([crates.io](https://crates.io/crates/{}/{}))",
+            release.package_name, release.version
+        ));
+    }
+    line
+}
+
 async fn release_packages(
     input: &ReleaseRequest,
     project: &Project,
@@ -588,21 +1175,72 @@ async fn release_packages(
         info!("nothing to release");
     }
 
+    anyhow::ensure!(
+        !(input.single_tag && input.combined_git_release.is_some()),
+        "`single_tag` and `combined_git_release` can't be enabled at the same time"
+    );
+    let single_tag_version = if input.single_tag {
+        lockstep_version(&packages)?
+    } else {
+        None
+    };
+    let highest_version = highest_stable_version(&packages, &repo.get_all_tags());
+
+    let remote_tags = if input.skip_existing_tags {
+        repo.remote_tags()?
+    } else {
+        HashSet::new()
+    };
+
     let mut package_releases: Vec<PackageRelease> = vec![];
+    let mut failures: Vec<PackageFailure> = vec![];
     // The same trusted publishing token can be used for all packages.
     let mut trusted_publishing_client: Option<trusted_publishing::TrustedPublisher> = None;
-    for package in packages {
-        if let Some(pkg_release) = release_package_if_needed(
-            input,
-            project,
-            package,
-            repo,
-            git_client,
-            &mut trusted_publishing_client,
-        )
-        .await?
+    for package in &packages {
+        // Packages are in release order, so the failed dependencies are already known.
+        if let Some(failed_dependency) = failures
+            .iter()
+            .find(|f| release_order::depends_on(package, &f.package_name))
         {
-            package_releases.push(pkg_release);
+            let error = format!(
+                "skipped because its dependency `{}` wasn't released",
+                failed_dependency.package_name
+            );
+            warn!("package `{}` {error}", package.name);
+            failures.push(PackageFailure {
+                package_name: package.name.to_string(),
+                error,
+            });
+            continue;
+        }
+        let pkg_release = async {
+            let git_tag = project.git_tag(&package.name, &package.version.to_string())?;
+            if is_tag_in_remote(input, package, &git_tag, &remote_tags) {
+                return Ok(None);
+            }
+            release_package_if_needed(
+                input,
+                project,
+                package,
+                repo,
+                git_client,
+                highest_version.as_ref(),
+                &mut trusted_publishing_client,
+            )
+            .await
+        }
+        .await;
+        match pkg_release {
+            Ok(Some(pkg_release)) => package_releases.push(pkg_release),
+            Ok(None) => {}
+            Err(e) if input.keep_going => {
+                error!("failed to release package `{}`: {e:?}", package.name);
+                failures.push(PackageFailure {
+                    package_name: package.name.to_string(),
+                    error: format!("{e:#}"),
+                });
+            }
+            Err(e) => return Err(e),
         }
     }
     if let Some(tp) = trusted_publishing_client.as_ref()
@@ -610,31 +1248,110 @@ async fn release_packages(
     {
         warn!("Failed to revoke trusted publishing token: {e:?}");
     }
-    let release = (!package_releases.is_empty()).then_some(Release {
+    if let Some(version) = single_tag_version
+        && !package_releases.is_empty()
+    {
+        let released_packages: Vec<&Package> = packages
+            .iter()
+            .filter(|p| {
+                package_releases
+                    .iter()
+                    .any(|r| r.package_name == p.name.as_str())
+            })
+            .copied()
+            .collect();
+        create_single_git_tag_and_release(
+            input,
+            project,
+            repo,
+            git_client,
+            &version,
+            &released_packages,
+            highest_version.as_ref(),
+        )
+        .await
+        .context("failed to create single git tag and release")?;
+    }
+    if let Some(combined_git_release) = &input.combined_git_release
+        && !package_releases.is_empty()
+    {
+        let released_packages: Vec<&Package> = packages
+            .iter()
+            .filter(|p| {
+                package_releases
+                    .iter()
+                    .any(|r| r.package_name == p.name.as_str())
+            })
+            .copied()
+            .collect();
+        create_combined_git_release(
+            input,
+            project,
+            repo,
+            git_client,
+            combined_git_release,
+            &released_packages,
+            highest_version.as_ref(),
+        )
+        .await
+        .context("failed to create combined git release")?;
+    }
+    let release = (!package_releases.is_empty() || !failures.is_empty()).then_some(Release {
         releases: package_releases,
+        failures,
     });
     Ok(release)
 }
 
+/// Returns `true` if the git tag of the package is among the `remote_tags`,
+/// i.e. the package was already released and there's nothing to do.
+/// This avoids the network calls needed to check if the package is published.
+fn is_tag_in_remote(
+    input: &ReleaseRequest,
+    package: &Package,
+    git_tag: &str,
+    remote_tags: &HashSet<String>,
+) -> bool {
+    // The existing git release needs to be updated.
+    if input.force_release_body && input.is_git_release_enabled(&package.name) {
+        return false;
+    }
+    if input.is_tag_check_skipped(&package.name) || !remote_tags.contains(git_tag) {
+        return false;
+    }
+    info!(
+        "{} {}: Already published - Tag {git_tag} already exists in the remote repository",
+        package.name, package.version
+    );
+    input.send_event(package, ReleaseEventKind::Skipped);
+    true
+}
+
 async fn release_package_if_needed(
     input: &ReleaseRequest,
     project: &Project,
     package: &Package,
     repo: &Repo,
     git_client: &GitClient,
+    highest_version: Option<&Version>,
     trusted_publishing_client: &mut Option<trusted_publishing::TrustedPublisher>,
 ) -> anyhow::Result<Option<PackageRelease>> {
     let git_tag = project.git_tag(&package.name, &package.version.to_string())?;
     let release_name = project.release_name(&package.name, &package.version.to_string())?;
-    if repo.tag_exists(&git_tag)? {
-        info!(
-            "{} {}: Already published - Tag {} already exists",
-            package.name, package.version, &git_tag
-        );
+    // Render the tag of a placeholder version to find the part of the tag around the version.
+    let placeholder_tag = project.git_tag(&package.name, VERSION_PLACEHOLDER)?;
+    let prev_version = previous_version(&placeholder_tag, &repo.get_all_tags(), &package.version);
+    let tag_exists = !input.is_tag_check_skipped(&package.name) && repo.tag_exists(&git_tag)?;
+    let is_release_body_updated =
+        input.force_release_body && input.is_git_release_enabled(&package.name);
+    if tag_exists && !is_release_body_updated {
+        skip_existing_tag(input, package, &git_tag);
         return Ok(None);
     }
 
-    let changelog = last_changelog_entry(input, package);
+    // Computed after the skip check, because the changes can be rendered from the
+    // commits, which requires reading the git history.
+    let changelog = release_changes(input, project, repo, package, prev_version.as_ref());
     let prs = prs_from_text(&changelog);
     let release_info = ReleaseInfo {
         package,
@@ -642,16 +1359,41 @@ async fn release_package_if_needed(
         release_name: &release_name,
         changelog: &changelog,
         prs: &prs,
+        highest_version,
+        prev_version: prev_version.as_ref(),
     };
-
+    if tag_exists {
+        update_git_release(input, git_client, &release_info)
+            .await
+            .context("failed to update git release")?;
+        skip_existing_tag(input, package, &git_tag);
+        return Ok(None);
+    }
     let should_publish = input.is_publish_enabled(&package.name);
+
     let mut package_was_released = false;
 
     if should_publish {
         let registry_indexes = registry_indexes(package, input.registry.clone())
             .context("can't determine registry indexes")?;
 
+        let publish_config = input.get_package_config(&package.name).publish;
+        if input.dry_run {
+            log_dry_run_registries(package, &registry_indexes, &publish_config);
+        }
+        // The verify command doesn't depend on the registry, so it runs once per package,
+        // only if the package is about to be published.
+        let mut is_package_verified = false;
         for CargoRegistry { name, index_url } in registry_indexes {
+            if !publish_config.is_registry_enabled(name.as_deref()) {
+                info!(
+                    "{} {}: publishing to registry {} is disabled",
+                    package.name,
+                    package.version,
+                    name.as_deref().unwrap_or(CRATES_IO_REGISTRY)
+                );
+                continue;
+            }
             let token = input.find_registry_token(name.as_deref())?;
             let pkg_is_published = is_published(
                 &input.metadata.workspace_root,
@@ -659,6 +1401,7 @@ async fn release_package_if_needed(
                 input.publish_timeout,
                 name.as_deref(),
                 index_url.as_ref(),
+                input.index_protocol,
                 token.as_ref(),
             )
             .await
@@ -666,8 +1409,13 @@ async fn release_package_if_needed(
 
             if pkg_is_published {
                 info!("{} {}: already published", package.name, package.version);
+                input.send_event(package, ReleaseEventKind::Skipped);
                 continue;
             }
+            if !is_package_verified {
+                verify_package(input, &release_info)?;
+                is_package_verified = true;
+            }
             let package_was_released_at_index = release_package(
                 input,
                 repo,
@@ -708,59 +1456,437 @@ async fn release_package_if_needed(
     Ok(package_release)
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum ShouldRelease {
+fn skip_existing_tag(input: &ReleaseRequest, package: &Package, git_tag: &str) {
+    info!(
+        "{} {}: Already published - Tag {} already exists",
+        package.name, package.version, git_tag
+    );
+    input.send_event(package, ReleaseEventKind::Skipped);
+}
+
+/// Version rendered in the git tag template to find the git tags of a package.
+const VERSION_PLACEHOLDER: &str = "0.0.0-placeholder";
+
+/// Highest version lower than `version` among the git `tags` of the package.
+/// `placeholder_tag` is the git tag of the package rendered with [`VERSION_PLACEHOLDER`].
+fn previous_version(placeholder_tag: &str, tags: &[String], version: &Version) -> Option<Version> {
+    let (prefix, suffix) = placeholder_tag.split_once(VERSION_PLACEHOLDER)?;
+    tags.iter()
+        .filter_map(|tag| tag.strip_prefix(prefix)?.strip_suffix(suffix))
+        .filter_map(|tag_version| Version::parse(tag_version).ok())
+        .filter(|tag_version| tag_version < version)
+        .max()
+}
+
+/// Highest stable version among the versions of `packages` and the versions of the git `tags`.
+fn highest_stable_version(packages: &[&Package], tags: &[String]) -> Option<Version> {
+    packages
+        .iter()
+        .map(|package| package.version.clone())
+        .chain(tags.iter().filter_map(|tag| version_from_tag(tag)))
+        .filter(|version| !version.is_prerelease())
+        .max()
+}
+
+/// Extract the semver version contained at the end of a git tag, e.g. `1.2.3` from `my-pkg-v1.2.3`.
+fn version_from_tag(tag: &str) -> Option<Version> {
+    tag.char_indices()
+        .filter(|(i, c)| {
+            c.is_ascii_digit()
+                && tag[..*i]
+                    .chars()
+                    .last()
+                    .is_none_or(|prev| !prev.is_ascii_digit() && prev != '.')
+        })
+        .find_map(|(i, _)| Version::parse(&tag[i..]).ok())
+}
+
+/// Version shared by all the packages.
+/// Returns an error if the packages have different versions.
+fn lockstep_version(packages: &[&Package]) -> anyhow::Result<Option<Version>> {
+    let Some(first) = packages.first() else {
+        return Ok(None);
+    };
+    let divergent_packages: Vec<String> = packages
+        .iter()
+        .filter(|p| p.version != first.version)
+        .map(|p| format!("{}@{}", p.name, p.version))
+        .collect();
+    anyhow::ensure!(
+        divergent_packages.is_empty(),
+        "`single_tag` is enabled, but not all packages have version {} of package {}: {}",
+        first.version,
+        first.name,
+        divergent_packages.join(", ")
+    );
+    Ok(Some(first.version.clone()))
+}
+
+/// Create a git tag and a git release covering all the released packages.
+/// The body of the git release contains the changelog of every package.
+async fn create_single_git_tag_and_release(
+    input: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    git_client: &GitClient,
+    version: &Version,
+    released_packages: &[&Package],
+    highest_version: Option<&Version>,
+) -> anyhow::Result<()> {
+    let Some(first_package) = released_packages.first() else {
+        return Ok(());
+    };
+    // All packages share the same tag, so we can use any of them to render it.
+    let git_tag = project.git_tag(&first_package.name, &version.to_string())?;
+    let default_config = &input.packages_config.default;
+    let should_create_git_tag = !input.no_tag && default_config.git_tag.enabled;
+    // With `no_tag`, the git forge would create the missing tag together with the release.
+    let should_create_git_release =
+        default_config.git_release.enabled && (!input.no_tag || repo.tag_exists(&git_tag)?);
+
+    if input.dry_run {
+        info!(
+            "{git_tag}: due to dry run, skipping the creation of the single git tag and git release"
+        );
+        return Ok(());
+    }
+
+    if should_create_git_tag {
+        let message = format!("chore: Release version {version}");
+        let message = default_config.git_tag.annotated.then_some(message.as_str());
+        create_git_tag(repo, git_client, &input.git_identity, &git_tag, message).await?;
+        for package in released_packages {
+            input.send_event(package, ReleaseEventKind::TagCreated);
+        }
+    }
+
+    if should_create_git_release {
+        let mut package_bodies = vec![];
+        for package in released_packages {
+            let body =
+                package_release_body(input, project, repo, git_client, package, &git_tag).await?;
+            package_bodies.push(format!("## `{}`\n\n{body}", package.name));
+        }
+        let git_release_info = GitReleaseInfo {
+            git_tag: git_tag.clone(),
+            release_name: git_tag.clone(),
+            release_body: package_bodies.join("\n\n"),
+            draft: default_config.git_release.draft,
+            latest: default_config.git_release.latest(version, highest_version),
+            pre_release: default_config.git_release.is_pre_release(version),
+            assets: vec![],
+        };
+        git_client.create_release(&git_release_info).await?;
+        for package in released_packages {
+            input.send_event(package, ReleaseEventKind::ReleaseCreated);
+        }
+    }
+
+    info!("created single git tag {git_tag}");
+    Ok(())
+}
+
+/// Create a git tag and a git release linking the git tags of all the released packages.
+/// The body of the git release contains the changelog of every package.
+async fn create_combined_git_release(
+    input: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    git_client: &GitClient,
+    combined_git_release: &CombinedGitRelease,
+    released_packages: &[&Package],
+    highest_version: Option<&Version>,
+) -> anyhow::Result<()> {
+    let now = chrono::Utc::now();
+    let git_tag = combined_tag_name(
+        combined_git_release.tag_name_template.as_deref(),
+        &now.date_naive().to_string(),
+        &now.format("%H%M%S").to_string(),
+    )?;
+    if input.dry_run {
+        info!("{git_tag}: due to dry run, skipping the creation of the combined git release");
+        return Ok(());
+    }
+    if input.no_tag {
+        info!(
+            "{git_tag}: tag creation is disabled, skipping the creation of the combined git release"
+        );
+        return Ok(());
+    }
+    anyhow::ensure!(
+        !repo.tag_exists(&git_tag)?,
+        "can't create the combined git release: tag {git_tag} already exists. Use the `{{{{ time }}}}` variable in `combined_git_release_tag_name` to create a tag per release"
+    );
+
+    let repo_url = match &input.repo_url {
+        Some(url) => RepoUrl::new(url).ok(),
+        None => RepoUrl::from_repo(repo).ok(),
+    };
+    let mut package_bodies = vec![];
+    for package in released_packages {
+        let package_tag = project.git_tag(&package.name, &package.version.to_string())?;
+        let tag_link = match &repo_url {
+            Some(url) => format!(
+                "[`{package_tag}`]({})",
+                url.git_release_link(&package_tag, &package_tag)
+            ),
+            None => format!("`{package_tag}`"),
+        };
+        let body =
+            package_release_body(input, project, repo, git_client, package, &package_tag).await?;
+        package_bodies.push(format!("## `{}` - {tag_link}\n\n{body}", package.name));
+    }
+
+    let default_config = &input.packages_config.default;
+    let message = format!("chore: release {git_tag}");
+    let message = default_config.git_tag.annotated.then_some(message.as_str());
+    create_git_tag(repo, git_client, &input.git_identity, &git_tag, message).await?;
+    let pre_release = released_packages.iter().any(|package| {
+        input
+            .get_package_config(&package.name)
+            .git_release
+            .is_pre_release(&package.version)
+    });
+    // The combined release is as recent as its most recent package.
+    let latest = released_packages
+        .iter()
+        .map(|package| &package.version)
+        .max()
+        .and_then(|version| default_config.git_release.latest(version, highest_version));
+    let git_release_info = GitReleaseInfo {
+        git_tag: git_tag.clone(),
+        release_name: git_tag.clone(),
+        release_body: package_bodies.join("\n\n"),
+        draft: default_config.git_release.draft,
+        latest,
+        pre_release,
+        assets: vec![],
+    };
+    git_client.create_release(&git_release_info).await?;
+    info!("created combined git release {git_tag}");
+    Ok(())
+}
+
+/// Release body of the package, used in git releases covering multiple packages.
+async fn package_release_body(
+    input: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    git_client: &GitClient,
+    package: &Package,
+    git_tag: &str,
+) -> anyhow::Result<String> {
+    let placeholder_tag = project.git_tag(&package.name, VERSION_PLACEHOLDER)?;
+    let prev_version = previous_version(&placeholder_tag, &repo.get_all_tags(), &package.version);
+    let changelog = release_changes(input, project, repo, package, prev_version.as_ref());
+    let prs = prs_from_text(&changelog);
+    let release_info = ReleaseInfo {
+        package,
+        git_tag,
+        release_name: git_tag,
+        changelog: &changelog,
+        prs: &prs,
+        highest_version: None,
+        prev_version: None,
+    };
+    let remote = Remote {
+        owner: String::new(),
+        repo: String::new(),
+        link: String::new(),
+        contributors: get_contributors(input, &release_info, git_client).await,
+    };
+    let body = release_body(input, package, &changelog, &remote);
+    ensure_release_body_is_not_empty(input, package, &body)?;
+    Ok(body.trim().to_string())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ShouldRelease {
     Yes,
     YesWithCommit(String),
     No,
 }
 
+/// Also returns the number of the merged release PR, if the current commit comes from one.
 async fn should_release(
     input: &ReleaseRequest,
     repo: &Repo,
     git_client: &GitClient,
-) -> anyhow::Result<ShouldRelease> {
+    pr_marker: &str,
+) -> anyhow::Result<(ShouldRelease, Option<u64>)> {
     let last_commit = repo.current_commit_hash()?;
     let prs = git_client.associated_prs(&last_commit).await?;
-    let associated_release_pr = prs
-        .iter()
-        .find(|pr| pr.branch().starts_with(&input.branch_prefix));
+    let associated_release_pr = prs.iter().find(|pr| input.is_release_pr(pr, pr_marker));
 
     match associated_release_pr {
         Some(pr) => {
             let pr_commits = git_client.pr_commits(pr.number).await?;
-            // Get the last commit of the PR, i.e. the last commit that was pushed before the PR was merged
-            match pr_commits.last() {
-                Some(commit) if commit.sha != last_commit => {
-                    if is_pr_commit_in_original_branch(repo, commit) {
-                        // I need to checkout the last commit of the PR if it exists
-                        Ok(ShouldRelease::YesWithCommit(commit.sha.clone()))
-                    } else {
-                        // The commit is not in the original branch, probably the PR was squashed
-                        Ok(ShouldRelease::Yes)
-                    }
-                }
-                _ => {
-                    // I'm already at the right commit
-                    Ok(ShouldRelease::Yes)
-                }
-            }
+            let base_branch = input
+                .base_branch
+                .as_deref()
+                .unwrap_or(repo.original_branch());
+            let should_release =
+                release_pr_commit(repo, &last_commit, base_branch, pr, &pr_commits);
+            Ok((should_release, Some(pr.number)))
         }
         None => {
             if input.release_always {
-                Ok(ShouldRelease::Yes)
+                Ok((ShouldRelease::Yes, None))
             } else {
                 info!("skipping release: current commit is not from a release PR");
-                Ok(ShouldRelease::No)
+                Ok((ShouldRelease::No, None))
+            }
+        }
+    }
+}
+
+/// Determine the commit of the base branch to release, given the merged release `pr` and its commits.
+fn release_pr_commit(
+    repo: &Repo,
+    last_commit: &str,
+    base_branch: &str,
+    pr: &GitPr,
+    pr_commits: &[crate::git::forge::PrCommit],
+) -> ShouldRelease {
+    // Get the last commit of the PR, i.e. the last commit that was pushed before the PR was merged
+    match pr_commits.last() {
+        Some(commit) if commit.sha != last_commit => {
+            if is_pr_commit_in_branch(repo, commit, base_branch) {
+                // I need to checkout the last commit of the PR if it exists
+                ShouldRelease::YesWithCommit(commit.sha.clone())
+            } else if let Some(release_commit) = find_rebased_pr_commit(repo, pr, pr_commits) {
+                // The PR was rebased, so its commits have a different sha in the base branch.
+                warn!(
+                    "last commit {} of release PR #{} not found in branch `{base_branch}`, probably the PR was rebased. Releasing commit {release_commit}, found from the version of the release PR",
+                    commit.sha, pr.number
+                );
+                if release_commit == last_commit {
+                    ShouldRelease::Yes
+                } else {
+                    ShouldRelease::YesWithCommit(release_commit)
+                }
+            } else {
+                // The commit is not in the base branch, probably the PR was squashed
+                info!(
+                    "last commit {} of the release PR not found in branch `{base_branch}`, probably the PR was squashed. Releasing the current commit",
+                    commit.sha
+                );
+                ShouldRelease::Yes
+            }
+        }
+        _ => {
+            // I'm already at the right commit
+            ShouldRelease::Yes
+        }
+    }
+}
+
+/// Find the rebased counterpart of the last commit of the release PR in the current branch.
+/// Unlike the commit sha, the version set by the PR doesn't change when the PR is rebased,
+/// so the commit setting the version of the PR is searched back from the current commit.
+fn find_rebased_pr_commit(
+    repo: &Repo,
+    pr: &GitPr,
+    pr_commits: &[crate::git::forge::PrCommit],
+) -> Option<String> {
+    let version = released_version(pr)?;
+    let version_commit = find_version_commit(repo, &version)?;
+    Some(last_rebased_pr_commit(repo, version_commit, pr_commits))
+}
+
+/// Version released by the PR, read from its title or, if missing, from its branch.
+/// E.g. `1.2.0` from the title `chore: release v1.2.0`.
+fn released_version(pr: &GitPr) -> Option<Version> {
+    [pr.title.as_str(), pr.branch()]
+        .into_iter()
+        .find_map(|text| {
+            text.split(|c: char| c.is_whitespace() || c == '/')
+                .filter_map(|word| Version::parse(word.trim_start_matches('v')).ok())
+                .next_back()
+        })
+}
+
+/// Most recent commit that sets `version` in a `Cargo.toml` file or in the `Cargo.lock` file.
+fn find_version_commit(repo: &Repo, version: &Version) -> Option<String> {
+    let version_pattern = format!(
+        r#"version *= *"{}""#,
+        version.to_string().replace('.', r"\.")
+    );
+    let log = repo
+        .git(&[
+            "log",
+            "-p",
+            "--unified=0",
+            "--format=commit %H",
+            "-G",
+            &version_pattern,
+            "--",
+            CARGO_TOML,
+            ":(glob)**/Cargo.toml",
+            "Cargo.lock",
+        ])
+        .inspect_err(|e| warn!("can't read the git log to find the release commit: {e:?}"))
+        .ok()?;
+    let mut commit = None;
+    for line in log.lines() {
+        if let Some(sha) = line.strip_prefix("commit ") {
+            commit = Some(sha);
+        } else if let Some(added_line) = line.strip_prefix('+')
+            && !line.starts_with("+++")
+            && is_version_line(added_line, version)
+        {
+            return commit.map(str::to_string);
+        }
+        // Otherwise, the line was removed or it's unrelated to the version.
+    }
+    None
+}
+
+/// Whether the TOML line sets the `version` key to `version`, e.g. `version = "1.2.0"`.
+fn is_version_line(line: &str, version: &Version) -> bool {
+    line.split_once('=').is_some_and(|(key, value)| {
+        key.trim() == "version" && value.trim() == format!("\"{version}\"")
+    })
+}
+
+/// The release PR can contain commits after the one setting the version,
+/// e.g. to fix the changelog.
+/// Follow them, matching the PR commits by subject, to find the last commit of the PR.
+fn last_rebased_pr_commit(
+    repo: &Repo,
+    version_commit: String,
+    pr_commits: &[crate::git::forge::PrCommit],
+) -> String {
+    let Ok(version_subject) = repo.git(&["log", "-1", "--format=%s", &version_commit]) else {
+        return version_commit;
+    };
+    let range = format!("{version_commit}..HEAD");
+    let Ok(log) = repo.git(&["log", "--reverse", "--format=%H %s", &range]) else {
+        return version_commit;
+    };
+    let mut pr_subjects = pr_commits
+        .iter()
+        .map(|c| c.subject())
+        .skip_while(|subject| *subject != version_subject.trim())
+        .skip(1)
+        .peekable();
+    let mut last_commit = version_commit;
+    for line in log.lines() {
+        match line.split_once(' ') {
+            Some((sha, subject)) if pr_subjects.peek() == Some(&subject) => {
+                pr_subjects.next();
+                last_commit = sha.to_string();
             }
+            _ => break,
         }
     }
+    last_commit
 }
 
-fn is_pr_commit_in_original_branch(repo: &Repo, commit: &crate::git::forge::PrCommit) -> bool {
+fn is_pr_commit_in_branch(repo: &Repo, commit: &crate::git::forge::PrCommit, branch: &str) -> bool {
     let branches_of_commit = repo.get_branches_of_commit(&commit.sha);
     if let Ok(branches) = branches_of_commit {
-        branches.contains(&repo.original_branch().to_string())
+        branches.iter().any(|b| b == branch)
     } else {
         false
     }
@@ -807,6 +1933,10 @@ struct ReleaseInfo<'a> {
     release_name: &'a str,
     changelog: &'a str,
     prs: &'a [Pr],
+    /// Highest stable version among the released packages and the existing git tags.
+    highest_version: Option<&'a Version>,
+    /// Version of the previous git tag of the package.
+    prev_version: Option<&'a Version>,
 }
 
 /// Return `true` if package was published, `false` otherwise.
@@ -823,11 +1953,11 @@ async fn release_package(
     index_url: Option<&Url>,
 ) -> anyhow::Result<bool> {
     let workspace_root = &input.metadata.workspace_root;
-    let is_crates_io = registry_name.is_none() || registry_name == Some("crates-io");
+    let is_crates_io = registry_name.is_none() || registry_name == Some(CRATES_IO_REGISTRY);
 
     let should_publish = input.is_publish_enabled(&release_info.package.name);
     let should_create_git_tag = input.is_git_tag_enabled(&release_info.package.name);
-    let should_create_git_release = input.is_git_release_enabled(&release_info.package.name);
+    let should_create_git_release = should_create_git_release(input, repo, release_info)?;
 
     let mut publish_token: Option<SecretString> = token.cloned();
     let should_use_trusted_publishing = {
@@ -856,6 +1986,7 @@ async fn release_package(
     }
 
     if should_publish {
+        input.send_event(release_info.package, ReleaseEventKind::PublishStarted);
         // Run `cargo publish`. Note that `--dry-run` is added if `input.dry_run` is true.
         let output = run_cargo_publish(
             release_info.package,
@@ -874,7 +2005,14 @@ async fn release_package(
                     "skipping publish of {} {}: already published",
                     release_info.package.name, release_info.package.version
                 );
+                input.send_event(release_info.package, ReleaseEventKind::Skipped);
                 return Ok(false);
+            } else if is_lockfile_outdated(&output) {
+                anyhow::bail!(
+                    "failed to publish {}: the Cargo.lock file is out of date. Run `release-plz update` or `cargo update` and commit the Cargo.lock file before releasing: {}",
+                    release_info.package.name,
+                    output.stderr
+                );
             } else {
                 anyhow::bail!(
                     "failed to publish {}: {}",
@@ -887,6 +2025,13 @@ async fn release_package(
 
     if input.dry_run {
         log_dry_run_info(
+            release_info,
+            should_publish.then_some(registry.unwrap_or(CRATES_IO_REGISTRY)),
+            should_create_git_tag,
+            should_create_git_release,
+        );
+        send_dry_run_events(
+            input,
             release_info,
             should_publish,
             should_create_git_tag,
@@ -895,15 +2040,27 @@ async fn release_package(
         Ok(false)
     } else {
         if should_publish {
-            wait_until_published(
-                workspace_root,
-                release_info.package,
-                input.publish_timeout,
-                registry,
-                index_url,
-                token,
-            )
-            .await?;
+            if input.skip_publish_wait {
+                info!(
+                    "{} {}: not waiting for the package to be available in the registry",
+                    release_info.package.name, release_info.package.version
+                );
+            } else {
+                wait_until_published(
+                    workspace_root,
+                    release_info.package,
+                    input.publish_timeout,
+                    registry,
+                    index_url,
+                    input.index_protocol,
+                    token,
+                )
+                .await?;
+            }
+            if input.verify_published_checksum {
+                verify_published_checksum(input, release_info.package, registry).await?;
+            }
+            input.send_event(release_info.package, ReleaseEventKind::Published);
         }
 
         create_git_tag_and_release(
@@ -924,6 +2081,62 @@ async fn release_package(
     }
 }
 
+/// Check that the `.crate` file published in the registry is the one built by `cargo publish`.
+async fn verify_published_checksum(
+    input: &ReleaseRequest,
+    package: &Package,
+    registry: Option<&str>,
+) -> anyhow::Result<()> {
+    let local_crate = input
+        .metadata
+        .target_directory
+        .join("package")
+        .join(format!("{}-{}.crate", package.name, package.version));
+    let local_crate_content = fs_err::read(&local_crate)
+        .context("can't read the `.crate` file built by `cargo publish`")?;
+    let local_checksum = format!("{:x}", Sha256::digest(local_crate_content));
+    let published_checksum = download::published_checksum(
+        &package.name,
+        &package.version,
+        registry,
+        &input.metadata.workspace_root,
+    )
+    .await
+    .with_context(|| format!("can't verify the checksum of {}", package.name))?;
+    anyhow::ensure!(
+        local_checksum == published_checksum,
+        "checksum mismatch for {} {}: the registry index reports checksum {published_checksum}, but the `.crate` file built locally has checksum {local_checksum}. The published package might have been corrupted or tampered with.",
+        package.name,
+        package.version
+    );
+    info!(
+        "{} {}: published checksum verified",
+        package.name, package.version
+    );
+    Ok(())
+}
+
+/// `cargo publish --locked` failed because it needed to update the `Cargo.lock` file.
+fn is_lockfile_outdated(output: &CmdOutput) -> bool {
+    output
+        .stderr
+        .contains("needs to be updated but --locked was passed")
+}
+
+/// Add the `--locked` flag to `command` if it's a cargo command that doesn't have it yet.
+/// The flag is added before `--`, so that it isn't passed to the program run by cargo.
+fn with_locked_flag(mut command: Vec<String>) -> Vec<String> {
+    let is_cargo_command = command.first().is_some_and(|program| program == "cargo");
+    if is_cargo_command && !command.iter().any(|arg| arg == "--locked") {
+        let flag_position = command
+            .iter()
+            .position(|arg| arg == "--")
+            .unwrap_or(command.len());
+        command.insert(flag_position, "--locked".to_string());
+    }
+    command
+}
+
 fn is_already_published(output: &CmdOutput, release_info: &ReleaseInfo<'_>) -> bool {
     // Error happening if the crate was published while `cargo publish` was running.
     let already_uploaded_message = format!(
@@ -954,12 +2167,19 @@ async fn release_package_git_only(
     release_info: &ReleaseInfo<'_>,
 ) -> anyhow::Result<bool> {
     let should_create_git_tag = input.is_git_tag_enabled(&release_info.package.name);
-    let should_create_git_release = input.is_git_release_enabled(&release_info.package.name);
+    let should_create_git_release = should_create_git_release(input, repo, release_info)?;
 
     if input.dry_run {
         log_dry_run_info(
             release_info,
-            false, // should_publish is always false in git-only mode
+            None, // nothing is published in git-only mode
+            should_create_git_tag,
+            should_create_git_release,
+        );
+        send_dry_run_events(
+            input,
+            release_info,
+            false,
             should_create_git_tag,
             should_create_git_release,
         );
@@ -983,6 +2203,26 @@ async fn release_package_git_only(
     }
 }
 
+/// With `no_tag`, the git release is created only if its tag already exists,
+/// because the git forge would create the missing tag.
+fn should_create_git_release(
+    input: &ReleaseRequest,
+    repo: &Repo,
+    release_info: &ReleaseInfo<'_>,
+) -> anyhow::Result<bool> {
+    if !input.is_git_release_enabled(&release_info.package.name) {
+        return Ok(false);
+    }
+    if input.no_tag && !repo.tag_exists(release_info.git_tag)? {
+        info!(
+            "{}: tag creation is disabled and tag {} doesn't exist, skipping the git release",
+            release_info.package.name, release_info.git_tag
+        );
+        return Ok(false);
+    }
+    Ok(true)
+}
+
 /// Create git tag and/or git release for a package.
 async fn create_git_tag_and_release(
     input: &ReleaseRequest,
@@ -992,61 +2232,221 @@ async fn create_git_tag_and_release(
     should_create_git_tag: bool,
     should_create_git_release: bool,
 ) -> anyhow::Result<()> {
+    // Compute the release body before creating the tag, so that we don't leave
+    // a tag without a release if the body is invalid.
+    let git_release_info = if should_create_git_release {
+        let mut git_release_info = git_release_info(input, git_client, release_info).await?;
+        let release_config = input
+            .get_package_config(&release_info.package.name)
+            .git_release;
+        if release_config.sbom {
+            git_release_info.assets = sbom_asset(input, release_info.package)
+                .into_iter()
+                .collect();
+        }
+        Some(git_release_info)
+    } else {
+        None
+    };
+
     if should_create_git_tag {
         // Use same tag message of cargo-release
-        let message = format!(
+        let mut message = format!(
             "chore: Release package {} version {}",
             release_info.package.name, release_info.package.version
         );
-        let should_sign_tags = repo
-            .git(&["config", "--default", "false", "--get", "tag.gpgSign"])
-            .map(|s| s.trim() == "true")?;
-        // If tag signing is enabled, create the tag locally instead of using the API
-        if should_sign_tags {
-            repo.tag(release_info.git_tag, &message)?;
-            repo.push(release_info.git_tag)?;
-        } else {
-            let sha = repo.current_commit_hash()?;
-            git_client
-                .create_tag(release_info.git_tag, &message, &sha)
-                .await?;
+        // Bitbucket doesn't support git releases, so the tag describes the release.
+        if git_client.forge == ForgeType::Bitbucket
+            && let Some(git_release_info) = &git_release_info
+        {
+            message = format!("{message}\n\n{}", git_release_info.release_body);
         }
+        let is_tag_annotated = input
+            .get_package_config(&release_info.package.name)
+            .git_tag
+            .annotated;
+        create_git_tag(
+            repo,
+            git_client,
+            &input.git_identity,
+            release_info.git_tag,
+            is_tag_annotated.then_some(message.as_str()),
+        )
+        .await?;
+        input.send_event(release_info.package, ReleaseEventKind::TagCreated);
     }
 
-    if should_create_git_release {
-        let contributors = get_contributors(release_info, git_client).await;
-
-        // TODO fill the rest
-        let remote = Remote {
-            owner: String::new(),
-            repo: String::new(),
-            link: String::new(),
-            contributors,
-        };
-        let release_body =
-            release_body(input, release_info.package, release_info.changelog, &remote);
-        let release_config = input
-            .get_package_config(&release_info.package.name)
-            .git_release;
-        let is_pre_release = release_config.is_pre_release(&release_info.package.version);
-        let git_release_info = GitReleaseInfo {
-            git_tag: release_info.git_tag.to_string(),
-            release_name: release_info.release_name.to_string(),
-            release_body,
-            draft: release_config.draft,
-            latest: release_config.latest,
-            pre_release: is_pre_release,
-        };
+    if let Some(git_release_info) = git_release_info {
         git_client.create_release(&git_release_info).await?;
+        input.send_event(release_info.package, ReleaseEventKind::ReleaseCreated);
     }
 
     Ok(())
 }
 
+/// Git release of the package, without assets.
+async fn git_release_info(
+    input: &ReleaseRequest,
+    git_client: &GitClient,
+    release_info: &ReleaseInfo<'_>,
+) -> anyhow::Result<GitReleaseInfo> {
+    let contributors = get_contributors(input, release_info, git_client).await;
+
+    // TODO fill the rest
+    let remote = Remote {
+        owner: String::new(),
+        repo: String::new(),
+        link: String::new(),
+        contributors,
+    };
+    let release_body = release_body(input, release_info.package, release_info.changelog, &remote);
+    ensure_release_body_is_not_empty(input, release_info.package, &release_body)?;
+    let release_config = input
+        .get_package_config(&release_info.package.name)
+        .git_release;
+    let is_pre_release = release_config.is_pre_release(&release_info.package.version);
+    Ok(GitReleaseInfo {
+        git_tag: release_info.git_tag.to_string(),
+        release_name: release_info.release_name.to_string(),
+        release_body,
+        draft: release_config.draft,
+        latest: release_config.latest(&release_info.package.version, release_info.highest_version),
+        pre_release: is_pre_release,
+        assets: vec![],
+    })
+}
+
+/// Update the name and the body of the existing git release of the package,
+/// e.g. after fixing its changelog.
+/// The git tag and the published package are left untouched.
+async fn update_git_release(
+    input: &ReleaseRequest,
+    git_client: &GitClient,
+    release_info: &ReleaseInfo<'_>,
+) -> anyhow::Result<()> {
+    let git_release_info = git_release_info(input, git_client, release_info).await?;
+    if input.dry_run {
+        info!(
+            "{}: due to dry run, skipping the update of the git release of tag {}",
+            release_info.package.name, release_info.git_tag
+        );
+        return Ok(());
+    }
+    if git_client.edit_release(&git_release_info).await? {
+        info!(
+            "{}: updated the name and the body of the existing git release of tag {}",
+            release_info.package.name, release_info.git_tag
+        );
+    } else {
+        warn!(
+            "{}: git release of tag {} not found, so it wasn't updated",
+            release_info.package.name, release_info.git_tag
+        );
+    }
+    Ok(())
+}
+
+/// Write the SBOM of the package in the target directory.
+/// Return `None` if the SBOM can't be generated, because the SBOM shouldn't block the release.
+fn sbom_asset(input: &ReleaseRequest, package: &Package) -> Option<Utf8PathBuf> {
+    let output_dir = input
+        .metadata
+        .target_directory
+        .join("release-plz")
+        .join("sbom");
+    crate::sbom::write_sbom(package, &output_dir)
+        .inspect_err(|e| warn!("{}: cannot generate the SBOM: {e:?}", package.name))
+        .ok()
+}
+
+/// Create an annotated tag with the given `message`,
+/// or a lightweight tag if `message` is [`Option::None`].
+async fn create_git_tag(
+    repo: &Repo,
+    git_client: &GitClient,
+    git_identity: &GitIdentity,
+    git_tag: &str,
+    message: Option<&str>,
+) -> anyhow::Result<()> {
+    let Some(message) = message else {
+        // Lightweight tags have no tagger and can't be signed.
+        let sha = repo.current_commit_hash()?;
+        git_client.create_lightweight_tag(git_tag, &sha).await?;
+        return Ok(());
+    };
+    let should_sign_tags = repo
+        .git(&["config", "--default", "false", "--get", "tag.gpgSign"])
+        .map(|s| s.trim() == "true")?;
+    // If tag signing is enabled or a custom tagger is configured,
+    // create the tag locally instead of using the API
+    if should_sign_tags || git_identity.is_set() {
+        repo.tag(git_tag, message)?;
+        repo.push(git_tag)?;
+    } else {
+        let sha = repo.current_commit_hash()?;
+        git_client.create_tag(git_tag, message, &sha).await?;
+    }
+    Ok(())
+}
+
+/// Sends the events of the steps that would have been taken had release been run without dry-run.
+fn send_dry_run_events(
+    input: &ReleaseRequest,
+    release_info: &ReleaseInfo,
+    should_publish: bool,
+    should_create_git_tag: bool,
+    should_create_git_release: bool,
+) {
+    let events = [
+        (should_publish, ReleaseEventKind::Published),
+        (should_create_git_tag, ReleaseEventKind::TagCreated),
+        (should_create_git_release, ReleaseEventKind::ReleaseCreated),
+    ];
+    for (enabled, kind) in events {
+        if enabled {
+            input.send_event(release_info.package, kind);
+        }
+    }
+}
+
+/// Lists the registries where the package would be published, so that users can check
+/// them before releasing without dry-run.
+fn log_dry_run_registries(
+    package: &Package,
+    registry_indexes: &[CargoRegistry],
+    publish_config: &PublishConfig,
+) {
+    let registries: Vec<String> = registry_indexes
+        .iter()
+        .map(|registry| {
+            let description = registry_description(registry);
+            if publish_config.is_registry_enabled(registry.name.as_deref()) {
+                description
+            } else {
+                format!("{description} (disabled)")
+            }
+        })
+        .collect();
+    info!(
+        "{} {}: registries to publish to: {}",
+        package.name,
+        package.version,
+        registries.join(", ")
+    );
+}
+
+fn registry_description(registry: &CargoRegistry) -> String {
+    match (&registry.name, &registry.index_url) {
+        (Some(name), Some(index_url)) => format!("{name} ({index_url})"),
+        (Some(name), None) => name.clone(),
+        (None, _) => CRATES_IO_REGISTRY.to_string(),
+    }
+}
+
 /// Traces the steps that would have been taken had release been run without dry-run.
 fn log_dry_run_info(
     release_info: &ReleaseInfo,
-    should_publish: bool,
+    publish_registry: Option<&str>,
     should_create_git_tag: bool,
     should_create_git_release: bool,
 ) {
@@ -1057,8 +2457,8 @@ fn log_dry_run_info(
 
     let mut items_to_skip = vec![];
 
-    if should_publish {
-        items_to_skip.push("cargo registry upload".to_string());
+    if let Some(registry) = publish_registry {
+        items_to_skip.push(format!("cargo registry upload to {registry}"));
     }
 
     if should_create_git_tag {
@@ -1077,9 +2477,13 @@ fn log_dry_run_info(
 }
 
 async fn get_contributors(
+    input: &ReleaseRequest,
     release_info: &ReleaseInfo<'_>,
     git_client: &GitClient,
 ) -> Vec<git_cliff_core::contributor::RemoteContributor> {
+    if !input.fetch_contributors {
+        return vec![];
+    }
     let prs_number = release_info
         .prs
         .iter()
@@ -1123,6 +2527,8 @@ pub struct GitReleaseInfo {
     pub latest: Option<bool>,
     pub draft: bool,
     pub pre_release: bool,
+    /// Files to attach to the release.
+    pub assets: Vec<Utf8PathBuf>,
 }
 
 /// Return `Err` if the cargo registry token environment variable is set to an empty string in CI.
@@ -1144,7 +2550,51 @@ fn verify_ci_cargo_registry_token(token_env_var: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn cargo_registry_token_env_var(registry: Option<&str>) -> anyhow::Result<String> {
+/// Run the `registry_token_command` and return its trimmed stdout.
+/// The output is never logged.
+fn run_registry_token_command(command: &[String]) -> anyhow::Result<SecretString> {
+    let (program, args) = command
+        .split_first()
+        .context("registry_token_command is empty")?;
+    debug!("running registry_token_command `{program}`");
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("failed to run registry_token_command `{program}`"))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "registry_token_command `{program}` failed with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    let token = String::from_utf8(output.stdout)
+        .context("the output of registry_token_command isn't valid UTF-8")?;
+    let token = token.trim();
+    anyhow::ensure!(
+        !token.is_empty(),
+        "registry_token_command `{program}` printed an empty token"
+    );
+    Ok(SecretString::from(token))
+}
+
+/// Find the token to use for the given `registry` ([`Option::None`] means crates.io).
+/// `request_token` is the token provided by the user for `request_registry`.
+pub(crate) fn find_registry_token(
+    request_registry: Option<&str>,
+    request_token: Option<&SecretString>,
+    registry: Option<&str>,
+) -> anyhow::Result<Option<SecretString>> {
+    let is_registry_same_as_request = request_registry == registry;
+    let token = is_registry_same_as_request
+        .then(|| request_token.cloned())
+        .flatten()
+        // if the registry is not the same as the request or if there's no token in the request,
+        // try to find the token in the Cargo credentials file or in the environment variables.
+        .or(cargo_utils::registry_token(request_registry)?);
+    Ok(token)
+}
+
+pub(crate) fn cargo_registry_token_env_var(registry: Option<&str>) -> anyhow::Result<String> {
     match registry {
         Some(registry) => cargo_utils::cargo_registries_token_env_var_name(registry),
         None => Ok("CARGO_REGISTRY_TOKEN".to_string()),
@@ -1172,7 +2622,10 @@ fn run_cargo_publish(
         args.push(registry);
     }
     let token_env_var = cargo_registry_token_env_var(registry)?;
-    let token = token.as_ref().or(input.token.as_ref());
+    let token = match token {
+        Some(token) => Some(token),
+        None => input.request_token()?,
+    };
     if token.is_none() {
         verify_ci_cargo_registry_token(&token_env_var)?;
     }
@@ -1185,6 +2638,9 @@ fn run_cargo_publish(
     if input.no_verify(&package.name) {
         args.push("--no-verify");
     }
+    if input.locked(&package.name) {
+        args.push("--locked");
+    }
     let features = input.features(&package.name).join(",");
     if !features.is_empty() {
         args.push("--features");
@@ -1193,12 +2649,66 @@ fn run_cargo_publish(
     if input.all_features(&package.name) {
         args.push("--all-features");
     }
+    let target = input.target(&package.name);
+    if let Some(target) = &target {
+        args.push("--target");
+        args.push(target);
+    }
     let envs = token
         .map(|token| vec![(token_env_var, token.clone())])
         .unwrap_or_default();
     run_cargo_with_env(workspace_root, &args, &envs)
 }
 
+/// Run the verify command of the package, if any, before publishing it.
+fn verify_package(input: &ReleaseRequest, release_info: &ReleaseInfo) -> anyhow::Result<()> {
+    let verify_command = input.verify_command(&release_info.package.name);
+    if verify_command.is_empty() {
+        return Ok(());
+    }
+    if input.dry_run && !input.dry_run_verify {
+        info!(
+            "{}: due to dry run, skipping verify command `{}`",
+            release_info.package.name,
+            verify_command.join(" ")
+        );
+        return Ok(());
+    }
+    let context = UserCommandContext {
+        package: release_info.package,
+        prev_version: release_info.prev_version,
+        tag: release_info.git_tag,
+        dry_run: input.dry_run,
+        changelog: release_info.changelog,
+    };
+    run_verify_command(&context, &verify_command)
+}
+
+/// Run the verify command in the directory of the package.
+/// The output of the command isn't captured, so that it's visible in the logs.
+/// Its stdout is redirected to stderr, to keep the stdout of release-plz
+/// (e.g. the JSON output) clean.
+fn run_verify_command(
+    context: &UserCommandContext,
+    verify_command: &[String],
+) -> anyhow::Result<()> {
+    let package = context.package;
+    let mut command = context.command(verify_command, "verify_command")?;
+    let command_str = verify_command.join(" ");
+    info!("{}: running verify command `{command_str}`", package.name);
+    let status = command
+        .current_dir(package.package_path()?)
+        .stdout(std::io::stderr())
+        .status()
+        .with_context(|| format!("failed to run verify command `{command_str}`"))?;
+    anyhow::ensure!(
+        status.success(),
+        "{}: verify command `{command_str}` failed with {status}",
+        package.name
+    );
+    Ok(())
+}
+
 /// Return an empty string if the changelog cannot be parsed.
 fn release_body(
     req: &ReleaseRequest,
@@ -1216,6 +2726,7 @@ fn release_body(
         changelog,
         remote,
         body_template.as_deref(),
+        &req.template_context,
     )
     .unwrap_or_else(|e| {
         warn!(
@@ -1226,6 +2737,77 @@ fn release_body(
     })
 }
 
+/// Fail if the git release body is empty and the package requires a changelog body.
+fn ensure_release_body_is_not_empty(
+    req: &ReleaseRequest,
+    package: &Package,
+    release_body: &str,
+) -> anyhow::Result<()> {
+    let require_changelog_body = req
+        .get_package_config(&package.name)
+        .git_release
+        .require_changelog_body;
+    if require_changelog_body && release_body.trim().is_empty() {
+        anyhow::bail!(
+            "{}: the git release body is empty. Check the changelog at path {:?}",
+            package.name,
+            req.changelog_path(package)
+        );
+    }
+    Ok(())
+}
+
+/// Changes of the release of the package, used in the git release body.
+/// `prev_version` is the version of the previous release of the package, if any.
+/// Return an empty string if not found.
+fn release_changes(
+    req: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    package: &Package,
+    prev_version: Option<&Version>,
+) -> String {
+    match req.get_package_config(&package.name).changelog_body_from {
+        ChangelogBodyFrom::File => last_changelog_entry(req, package),
+        ChangelogBodyFrom::Commits => changes_from_commits(req, project, repo, package, prev_version)
+            .unwrap_or_else(|e| {
+                warn!(
+                    "{}: failed to generate the changes from the commits: {:?}. The git release body will be empty.",
+                    package.name, e
+                );
+                String::new()
+            }),
+    }
+}
+
+/// Render the commits of the package since the tag of its previous release.
+/// If the package wasn't released before, all the commits of the package are rendered.
+fn changes_from_commits(
+    req: &ReleaseRequest,
+    project: &Project,
+    repo: &Repo,
+    package: &Package,
+    prev_version: Option<&Version>,
+) -> anyhow::Result<String> {
+    let prev_tag = prev_version
+        .map(|version| project.git_tag(&package.name, &version.to_string()))
+        .transpose()?;
+    let repo_root = fs_utils::canonicalize_utf8(&root_repo_path(&package.manifest_path)?)?;
+    let package_path = package.canonical_path()?;
+    let package_dir = fs_utils::strip_prefix(&package_path, &repo_root)?;
+    let commits =
+        repo.commits_at_globs(prev_tag.as_deref(), u32::MAX, &[package_glob(package_dir)])?;
+    let prev_version = prev_version.map(ToString::to_string);
+    notes_from_commits(
+        commits,
+        &package.name,
+        &package.version.to_string(),
+        prev_version.as_deref(),
+        req.changelog_config.clone(),
+        req.template_context.clone(),
+    )
+}
+
 /// Return an empty string if not found.
 fn last_changelog_entry(req: &ReleaseRequest, package: &Package) -> String {
     let changelog_update = req.get_package_config(&package.name).changelog_update;
@@ -1233,7 +2815,15 @@ fn last_changelog_entry(req: &ReleaseRequest, package: &Package) -> String {
         return String::new();
     }
     let changelog_path = req.changelog_path(package);
-    match changelog_parser::last_changes(&changelog_path) {
+    let last_changes = match req.changelog_output_format {
+        ChangelogOutputFormat::Markdown => changelog_parser::last_changes(&changelog_path),
+        // The git release body is markdown, so we render the last release of the JSON changelog.
+        ChangelogOutputFormat::Json => fs_err::read_to_string(&changelog_path)
+            .context("can't read changelog file")
+            .and_then(|changelog| last_release_from_json(&changelog, req.changelog_config.clone()))
+            .map(|release| release.map(|r| r.notes().to_string())),
+    };
+    match last_changes {
         Ok(Some(changes)) => changes,
         Ok(None) => {
             warn!(
@@ -1293,6 +2883,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn release_failures_are_reported() {
+        let release = Release {
+            releases: vec![],
+            failures: vec![
+                PackageFailure {
+                    package_name: "a".to_string(),
+                    error: "network error".to_string(),
+                },
+                PackageFailure {
+                    package_name: "b".to_string(),
+                    error: "skipped because its dependency `a` wasn't released".to_string(),
+                },
+            ],
+        };
+        expect_test::expect![[r#"
+            failed to release 2 package(s):
+            - a: network error
+            - b: skipped because its dependency `a` wasn't released"#]]
+        .assert_eq(&release.check_failures().unwrap_err().to_string());
+        Release::default().check_failures().unwrap();
+    }
+
     #[test]
     fn git_release_config_pre_release_default_works() {
         let config = GitReleaseConfig::default();
@@ -1325,6 +2938,72 @@ mod tests {
         assert!(config.is_pre_release(&rc_version));
     }
 
+    #[test]
+    fn git_release_config_latest_auto_works() {
+        let config = GitReleaseConfig::default().set_latest(GitReleaseLatest::Auto);
+        let highest = Version::parse("2.0.0").unwrap();
+        let patch = Version::parse("1.4.1").unwrap();
+        let rc_version = Version::parse("3.0.0-rc1").unwrap();
+
+        assert_eq!(config.latest(&highest, Some(&highest)), Some(true));
+        assert_eq!(config.latest(&patch, Some(&highest)), Some(false));
+        assert_eq!(config.latest(&rc_version, Some(&highest)), Some(false));
+        assert_eq!(config.latest(&patch, None), Some(true));
+    }
+
+    #[test]
+    fn highest_stable_version_considers_packages_and_tags() {
+        let mut package: Package = fake_package::FakePackage::new("fake_package").into();
+        package.version = Version::new(1, 4, 1);
+        let tags = [
+            "fake_package-v2.0.0",
+            "v2.1.0-rc1",
+            "other-package-v1.9.0",
+            "not-a-version",
+        ]
+        .map(String::from);
+        assert_eq!(
+            highest_stable_version(&[&package], &tags),
+            Some(Version::new(2, 0, 0))
+        );
+        assert_eq!(
+            highest_stable_version(&[&package], &[]),
+            Some(Version::new(1, 4, 1))
+        );
+    }
+
+    #[test]
+    fn previous_version_is_found_in_package_tags() {
+        let tags = [
+            "my_package-v0.1.0",
+            "my_package-v0.2.0",
+            "my_package-v0.3.0",
+            "other_package-v0.2.5",
+        ]
+        .map(String::from);
+        let placeholder_tag = format!("my_package-v{VERSION_PLACEHOLDER}");
+        assert_eq!(
+            previous_version(&placeholder_tag, &tags, &Version::new(0, 3, 0)),
+            Some(Version::new(0, 2, 0))
+        );
+        assert_eq!(
+            previous_version(&placeholder_tag, &tags, &Version::new(0, 1, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn version_is_extracted_from_tag() {
+        for (tag, expected) in [
+            ("v1.2.3", Some(Version::new(1, 2, 3))),
+            ("my-pkg2-v1.2.3", Some(Version::new(1, 2, 3))),
+            ("1.2.3", Some(Version::new(1, 2, 3))),
+            ("release-2024", None),
+        ] {
+            assert_eq!(version_from_tag(tag), expected, "{tag}");
+        }
+    }
+
     #[test]
     fn release_request_registry_token_env_works() {
         let registry_name = "my_registry";
@@ -1377,11 +3056,521 @@ mod tests {
         request = request.with_package_config(
             "fake_package".to_string(),
             ReleaseConfig {
-                publish: PublishConfig { enabled: true },
+                publish: PublishConfig::enabled(true),
                 ..Default::default()
             },
         );
 
         assert!(request.check_publish_fields().is_err());
     }
+
+    #[test]
+    fn empty_release_body_is_allowed_by_default() {
+        let request = ReleaseRequest::new(fake_metadata());
+        let package: Package = fake_package::FakePackage::new("fake_package").into();
+        ensure_release_body_is_not_empty(&request, &package, "").unwrap();
+    }
+
+    #[test]
+    fn empty_release_body_fails_if_changelog_body_is_required() {
+        let request = ReleaseRequest::new(fake_metadata()).with_package_config(
+            "fake_package",
+            ReleaseConfig {
+                git_release: GitReleaseConfig::default().set_require_changelog_body(true),
+                ..Default::default()
+            },
+        );
+        let package: Package = fake_package::FakePackage::new("fake_package").into();
+        let error = ensure_release_body_is_not_empty(&request, &package, "\n")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("fake_package: the git release body is empty"));
+        assert!(error.contains("CHANGELOG.md"));
+        ensure_release_body_is_not_empty(&request, &package, "- fix bug").unwrap();
+    }
+
+    #[test]
+    fn release_pr_branch_is_identified_by_prefix_by_default() {
+        let request = ReleaseRequest::new(fake_metadata());
+        assert!(request.is_release_pr_branch("release-plz-2024-01-01T00-00-00Z"));
+        assert!(!request.is_release_pr_branch("renovate/serde"));
+    }
+
+    #[test]
+    fn release_pr_branch_regex_overrides_prefix() {
+        let request = ReleaseRequest::new(fake_metadata())
+            .with_release_pr_branch_regex(r"^release-plz-\d{4}-")
+            .unwrap();
+        assert!(request.is_release_pr_branch("release-plz-2024-01-01T00-00-00Z"));
+        assert!(!request.is_release_pr_branch("release-plz-other-tool"));
+    }
+
+    #[test]
+    fn verify_command_failure_is_reported() {
+        let metadata = fake_metadata();
+        let package = metadata
+            .packages
+            .iter()
+            .find(|p| p.name.as_str() == "fake_package")
+            .unwrap();
+        let context = UserCommandContext {
+            package,
+            prev_version: None,
+            tag: "fake_package-v0.1.0",
+            dry_run: false,
+            changelog: "",
+        };
+        run_verify_command(&context, &["cargo".to_string(), "--version".to_string()]).unwrap();
+        let error = run_verify_command(
+            &context,
+            &["cargo".to_string(), "non-existent-subcommand".to_string()],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            error
+                .starts_with("fake_package: verify command `cargo non-existent-subcommand` failed"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn locked_flag_is_added_to_cargo_verify_commands() {
+        let command = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            with_locked_flag(command(&["cargo", "check", "--all-targets"])),
+            command(&["cargo", "check", "--all-targets", "--locked"])
+        );
+        assert_eq!(
+            with_locked_flag(command(&["cargo", "run", "--", "--check"])),
+            command(&["cargo", "run", "--locked", "--", "--check"])
+        );
+        assert_eq!(
+            with_locked_flag(command(&["cargo", "test", "--locked"])),
+            command(&["cargo", "test", "--locked"])
+        );
+        assert_eq!(
+            with_locked_flag(command(&["./verify.sh"])),
+            command(&["./verify.sh"])
+        );
+    }
+
+    #[test]
+    fn publish_config_enables_all_registries_by_default() {
+        let config = PublishConfig::default();
+        assert!(config.is_registry_enabled(None));
+        assert!(config.is_registry_enabled(Some("my-registry")));
+    }
+
+    #[test]
+    fn publish_config_filters_registries() {
+        let config = PublishConfig::default()
+            .with_registries(vec!["crates-io".to_string(), "mirror".to_string()])
+            .with_excluded_registries(vec!["mirror".to_string()]);
+        assert!(config.is_registry_enabled(None));
+        assert!(config.is_registry_enabled(Some("crates-io")));
+        assert!(!config.is_registry_enabled(Some("mirror")));
+        assert!(!config.is_registry_enabled(Some("other")));
+    }
+
+    #[test]
+    fn registry_token_is_read_from_command() {
+        let request = ReleaseRequest::new(fake_metadata())
+            .with_registry_token_command(vec!["echo".to_string(), " my-token ".to_string()]);
+        let token = request.find_registry_token(None).unwrap().unwrap();
+        assert_eq!(token.expose_secret(), "my-token");
+    }
+
+    #[test]
+    fn registry_token_command_must_print_a_token() {
+        let request = |command: &str| {
+            ReleaseRequest::new(fake_metadata())
+                .with_registry_token_command(vec![command.to_string()])
+        };
+        let error = request("false").find_registry_token(None).unwrap_err();
+        assert!(error.to_string().contains("failed"), "{error}");
+        let error = request("true").find_registry_token(None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "registry_token_command `true` printed an empty token"
+        );
+    }
+
+    #[test]
+    fn dry_run_events_are_sent() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let request = ReleaseRequest::new(fake_metadata())
+            .with_dry_run(true)
+            .with_progress_sender(sender);
+        let package: Package = fake_package::FakePackage::new("fake_package").into();
+        let release_info = ReleaseInfo {
+            package: &package,
+            git_tag: "v0.1.0",
+            release_name: "v0.1.0",
+            changelog: "",
+            prs: &[],
+            highest_version: None,
+            prev_version: None,
+        };
+        send_dry_run_events(&request, &release_info, true, false, true);
+        drop(request);
+
+        let mut events = vec![];
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        let expected_event = |kind| ReleaseEvent {
+            package_name: "fake_package".to_string(),
+            version: package.version.clone(),
+            kind,
+            dry_run: true,
+        };
+        assert_eq!(
+            events,
+            vec![
+                expected_event(ReleaseEventKind::Published),
+                expected_event(ReleaseEventKind::ReleaseCreated),
+            ]
+        );
+    }
+
+    #[test]
+    fn pr_commit_is_found_in_non_default_base_branch() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let default_branch = repo.original_branch().to_string();
+
+        let base_branch = "release/1.x";
+        repo.checkout_new_branch(base_branch).unwrap();
+        fs_err::write(temp.path().join("fix.txt"), "fix").unwrap();
+        repo.add_all_and_commit("fix: bug").unwrap();
+        let commit = crate::git::forge::PrCommit {
+            author: None,
+            sha: repo.current_commit_hash().unwrap(),
+            commit: crate::git::forge::PrCommitDetails::default(),
+        };
+
+        assert!(is_pr_commit_in_branch(&repo, &commit, base_branch));
+        assert!(!is_pr_commit_in_branch(&repo, &commit, &default_branch));
+    }
+
+    fn release_pr(title: &str) -> GitPr {
+        serde_json::from_value(serde_json::json!({
+            "user": { "id": 1, "login": "bot" },
+            "number": 12,
+            "html_url": "https://github.com/owner/repo/pull/12",
+            "head": { "ref": "release-plz-2024-01-01T00-00-00Z", "sha": "abc" },
+            "title": title,
+            "body": "",
+            "labels": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn released_version_is_read_from_pr_title() {
+        let version = released_version(&release_pr("chore(my_crate): release v1.2.0"));
+        assert_eq!(version, Some(Version::new(1, 2, 0)));
+        assert_eq!(released_version(&release_pr("chore: release")), None);
+    }
+
+    #[test]
+    fn rebased_release_pr_is_released_at_last_pr_commit() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let base_branch = repo.original_branch().to_string();
+        let write_manifest = |version: &str| {
+            fs_err::write(
+                temp.path().join("Cargo.toml"),
+                format!("[package]\nname = \"my_crate\"\nversion = \"{version}\"\n"),
+            )
+            .unwrap();
+        };
+        write_manifest("1.1.0");
+        repo.add_all_and_commit("feat: add my_crate").unwrap();
+
+        // Commits of the release PR after the rebase merge.
+        let pr_subjects = ["chore: release v1.2.0", "docs: fix changelog entry"];
+        write_manifest("1.2.0");
+        repo.add_all_and_commit(pr_subjects[0]).unwrap();
+        let version_commit = repo.current_commit_hash().unwrap();
+        fs_err::write(temp.path().join("CHANGELOG.md"), "## 1.2.0").unwrap();
+        repo.add_all_and_commit(pr_subjects[1]).unwrap();
+        let last_pr_commit = repo.current_commit_hash().unwrap();
+
+        // Another PR merged after the release PR.
+        fs_err::write(temp.path().join("README.md"), "new readme").unwrap();
+        repo.add_all_and_commit("docs: update readme").unwrap();
+        let last_commit = repo.current_commit_hash().unwrap();
+
+        // Sha of the commits before the rebase. They don't exist in the repository.
+        let pr_commits = [
+            "0123456789abcdef0123456789abcdef01234567",
+            "89abcdef0123456789abcdef0123456789abcdef",
+        ]
+        .into_iter()
+        .zip(pr_subjects)
+        .map(|(sha, message)| crate::git::forge::PrCommit {
+            author: None,
+            sha: sha.to_string(),
+            commit: crate::git::forge::PrCommitDetails {
+                message: message.to_string(),
+            },
+        })
+        .collect::<Vec<_>>();
+        let pr = release_pr("chore: release v1.2.0");
+        assert_eq!(
+            find_version_commit(&repo, &Version::new(1, 2, 0)),
+            Some(version_commit)
+        );
+        assert_eq!(
+            release_pr_commit(&repo, &last_commit, &base_branch, &pr, &pr_commits),
+            ShouldRelease::YesWithCommit(last_pr_commit)
+        );
+    }
+
+    #[test]
+    fn squashed_release_pr_is_released_at_current_commit() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let base_branch = repo.original_branch().to_string();
+
+        fs_err::write(temp.path().join("CHANGELOG.md"), "## 1.2.0").unwrap();
+        repo.add_all_and_commit("chore: release v1.2.0 (#12)")
+            .unwrap();
+        let last_commit = repo.current_commit_hash().unwrap();
+
+        let pr_commits = [crate::git::forge::PrCommit {
+            author: None,
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            commit: crate::git::forge::PrCommitDetails {
+                message: "chore: release v1.2.0".to_string(),
+            },
+        }];
+        assert_eq!(
+            release_pr_commit(
+                &repo,
+                &last_commit,
+                &base_branch,
+                &release_pr("chore: release v1.2.0"),
+                &pr_commits
+            ),
+            ShouldRelease::Yes
+        );
+    }
+
+    #[tokio::test]
+    async fn tag_without_message_is_created_as_lightweight_tag_through_the_api() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        fs_err::write(temp.path().join("CHANGELOG.md"), "## 1.0.0").unwrap();
+        repo.add_all_and_commit("chore: release v1.0.0").unwrap();
+        let sha = repo.current_commit_hash().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/repos/owner/repo/git/refs"))
+            .and(matchers::body_json(serde_json::json!({
+                "ref": "refs/tags/v1.0.0",
+                "sha": sha,
+            })))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let github = crate::GitHub::new("owner".to_string(), "repo".to_string(), "token".into())
+            .with_base_url(server.uri().parse().unwrap());
+        let git_client = GitClient::new(crate::GitForge::Github(github)).unwrap();
+
+        let git_identity = GitIdentity {
+            name: Some("release-bot".to_string()),
+            email: None,
+        };
+        create_git_tag(&repo, &git_client, &git_identity, "v1.0.0", None)
+            .await
+            .unwrap();
+        // Lightweight tags have no tagger, so the tag isn't created locally.
+        assert_eq!(repo.git(&["tag", "--list"]).unwrap(), "");
+    }
+
+    #[test]
+    fn release_body_is_rendered_from_commits_without_changelog_update() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let dir = Utf8Path::from_path(temp.path()).unwrap();
+        fs_err::create_dir_all(dir.join("src")).unwrap();
+        fs_err::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"my_crate\"\nversion = \"0.2.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "").unwrap();
+        repo.add_all_and_commit("feat: add my_crate").unwrap();
+        repo.tag("v0.1.0", "v0.1.0").unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "pub fn f() {}").unwrap();
+        repo.add_all_and_commit("fix: add f").unwrap();
+
+        let metadata = cargo_utils::get_manifest_metadata(&dir.join("Cargo.toml")).unwrap();
+        let request = ReleaseRequest::new(metadata).with_default_package_config(
+            ReleaseConfig::default()
+                .with_changelog_update(false)
+                .with_changelog_body_from(ChangelogBodyFrom::Commits),
+        );
+        let project = Project::new(
+            &request.local_manifest(),
+            None,
+            &request.packages_config.overridden_packages(),
+            &request.metadata,
+            &request,
+        )
+        .unwrap();
+        let package = &request.metadata.workspace_packages()[0];
+        let prev_version = Version::new(0, 1, 0);
+
+        let changes = release_changes(&request, &project, &repo, package, Some(&prev_version));
+        // Only the commits after the tag of the previous version are rendered.
+        assert!(changes.contains("add f"), "{changes}");
+        assert!(!changes.contains("add my_crate"), "{changes}");
+    }
+
+    fn package_with_version(name: &str, version: &str) -> Package {
+        let mut package: Package = fake_package::FakePackage::new(name).into();
+        package.version = Version::parse(version).unwrap();
+        package
+    }
+
+    #[test]
+    fn lockstep_version_is_detected() {
+        let a = package_with_version("a", "1.4.0");
+        let b = package_with_version("b", "1.4.0");
+        let version = lockstep_version(&[&a, &b]).unwrap();
+        assert_eq!(version, Some(Version::new(1, 4, 0)));
+    }
+
+    #[test]
+    fn divergent_versions_are_rejected_with_single_tag() {
+        let a = package_with_version("a", "1.4.0");
+        let b = package_with_version("b", "1.3.0");
+        let error = lockstep_version(&[&a, &b]).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "`single_tag` is enabled, but not all packages have version 1.4.0 of package a: b@1.3.0"
+        );
+    }
+
+    #[test]
+    fn existing_remote_tag_is_ignored_with_no_tag() {
+        let package: Package = fake_package::FakePackage::new("fake_package").into();
+        let git_tag = "fake_package-v0.1.0";
+        let remote_tags = HashSet::from([git_tag.to_string()]);
+
+        let request = ReleaseRequest::new(fake_metadata()).with_skip_existing_tags(true);
+        assert!(is_tag_in_remote(&request, &package, git_tag, &remote_tags));
+
+        let request = request.with_no_tag(true);
+        assert!(!is_tag_in_remote(&request, &package, git_tag, &remote_tags));
+    }
+
+    #[test]
+    fn pr_with_marker_is_release_pr_regardless_of_branch() {
+        let pr = |branch: &str, body: &str| -> GitPr {
+            serde_json::from_value(serde_json::json!({
+                "user": { "id": 1, "login": "bot" },
+                "number": 1,
+                "html_url": "https://github.com/owner/repo/pull/1",
+                "head": { "ref": branch, "sha": "abc" },
+                "title": "chore: release",
+                "body": body,
+                "labels": [],
+            }))
+            .unwrap()
+        };
+        let marker = release_pr_marker(Utf8Path::new(""));
+        let request = ReleaseRequest::new(fake_metadata());
+        let body_with_marker = format!("release notes\n\n{marker}");
+
+        assert!(request.is_release_pr(&pr("release-plz-2024-01-01T00-00-00Z", ""), &marker));
+        assert!(request.is_release_pr(&pr("old-prefix-2024-01-01", &body_with_marker), &marker));
+        assert!(!request.is_release_pr(&pr("old-prefix-2024-01-01", "release notes"), &marker));
+        let other_workspace_marker = release_pr_marker(Utf8Path::new("other"));
+        assert!(!request.is_release_pr(
+            &pr("old-prefix-2024-01-01", &body_with_marker),
+            &other_workspace_marker
+        ));
+    }
+
+    #[test]
+    fn combined_git_release_can_replace_package_releases() {
+        let request = ReleaseRequest::new(fake_metadata())
+            .with_combined_git_release(CombinedGitRelease::default());
+        assert!(request.is_git_release_enabled("fake_package"));
+        let request = ReleaseRequest::new(fake_metadata())
+            .with_combined_git_release(CombinedGitRelease::default().with_only(true));
+        assert!(!request.is_git_release_enabled("fake_package"));
+        assert!(request.is_git_tag_enabled("fake_package"));
+    }
+
+    #[test]
+    fn no_tag_disables_git_tags_but_not_git_releases() {
+        let request = ReleaseRequest::new(fake_metadata()).with_no_tag(true);
+        assert!(!request.is_git_tag_enabled("fake_package"));
+        assert!(request.is_git_release_enabled("fake_package"));
+    }
+
+    #[test]
+    fn single_tag_disables_package_tags() {
+        let request = ReleaseRequest::new(fake_metadata()).with_single_tag(true);
+        assert!(!request.is_git_tag_enabled("fake_package"));
+        assert!(!request.is_git_release_enabled("fake_package"));
+        let release_metadata = request.get_release_metadata("fake_package").unwrap();
+        assert_eq!(
+            release_metadata.tag_name_template.as_deref(),
+            Some("v{{ version }}")
+        );
+    }
+
+    #[test]
+    fn release_summary_line_links_tag_and_crates_io() {
+        let release = PackageRelease {
+            package_name: "my_crate".to_string(),
+            prs: vec![],
+            tag: "v1.2.0".to_string(),
+            version: Version::new(1, 2, 0),
+        };
+        assert_eq!(
+            release_summary_line(&release, Some("`v1.2.0`"), true),
+            "- `my_crate` 1.2.0: `v1.2.0` ([crates.io](https://crates.io/crates/my_crate/1.2.0))"
+        );
+        assert_eq!(
+            release_summary_line(&release, None, false),
+            "- `my_crate` 1.2.0"
+        );
+    }
+
+    #[test]
+    fn single_tag_is_created_unless_tags_are_disabled() {
+        let request = ReleaseRequest::new(fake_metadata()).with_single_tag(true);
+        assert!(request.is_tag_created("fake_package"));
+        let request = request.with_no_tag(true);
+        assert!(!request.is_tag_created("fake_package"));
+    }
+
+    #[test]
+    fn registry_description_includes_index_url() {
+        let registry = CargoRegistry {
+            name: Some("my-registry".to_string()),
+            index_url: Some(Url::parse("https://my-registry.example/index").unwrap()),
+        };
+        assert_eq!(
+            registry_description(&registry),
+            "my-registry (https://my-registry.example/index)"
+        );
+        let default_registry = CargoRegistry {
+            name: None,
+            index_url: None,
+        };
+        assert_eq!(registry_description(&default_registry), "crates-io");
+    }
 }
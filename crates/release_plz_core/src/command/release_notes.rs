@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use cargo_metadata::{Metadata, camino::Utf8Path};
+use git_cliff_core::{commit::Commit, config::Config};
+use git_cmd::Repo;
+use tracing::{debug, instrument};
+
+use crate::{
+    ChangelogBuilder, PackagePath as _,
+    changelog::{default_git_cliff_config, entry_config},
+    fs_utils, root_repo_path,
+};
+
+#[derive(Debug)]
+pub struct ReleaseNotesRequest {
+    /// Cargo metadata.
+    metadata: Metadata,
+    /// Name of the package to generate the release notes for.
+    package: String,
+    /// Git ref where the range starts (excluded). E.g. `v1.0.0`.
+    from: String,
+    /// Git ref where the range ends (included). E.g. `HEAD`.
+    to: String,
+    /// git-cliff configuration used to render the notes.
+    /// If unspecified, the default release-plz configuration is used.
+    changelog_config: Option<Config>,
+    /// User-defined variables available in the changelog template.
+    template_context: BTreeMap<String, String>,
+}
+
+impl ReleaseNotesRequest {
+    pub fn new(
+        metadata: Metadata,
+        package: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        Self {
+            metadata,
+            package: package.into(),
+            from: from.into(),
+            to: to.into(),
+            changelog_config: None,
+            template_context: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_changelog_config(mut self, changelog_config: Config) -> Self {
+        self.changelog_config = Some(changelog_config);
+        self
+    }
+
+    pub fn with_template_context(mut self, template_context: BTreeMap<String, String>) -> Self {
+        self.template_context = template_context;
+        self
+    }
+}
+
+/// Render the release notes of a package for the commits between two git refs.
+/// Only the commits that changed files of the package are considered.
+/// The version of the package isn't bumped, and no file is edited.
+#[instrument(skip_all)]
+pub fn release_notes(input: &ReleaseNotesRequest) -> anyhow::Result<String> {
+    let package = input
+        .metadata
+        .workspace_packages()
+        .into_iter()
+        .find(|p| p.name == input.package)
+        .with_context(|| format!("package `{}` not found in the workspace", input.package))?;
+    let repo_root = root_repo_path(&package.manifest_path)?;
+    let repo_root = fs_utils::canonicalize_utf8(&repo_root)?;
+    let package_path = package.canonical_path()?;
+    let package_dir = fs_utils::strip_prefix(&package_path, &repo_root)?;
+    let repo = Repo::new(&repo_root)?;
+    render_release_notes(&repo, package_dir, input)
+}
+
+fn render_release_notes(
+    repo: &Repo,
+    package_dir: &Utf8Path,
+    input: &ReleaseNotesRequest,
+) -> anyhow::Result<String> {
+    let commits = repo.commits_between(&input.from, &input.to, &[package_glob(package_dir)])?;
+    debug!(
+        "{}: found {} commits between `{}` and `{}`",
+        input.package,
+        commits.len(),
+        input.from,
+        input.to
+    );
+    notes_from_commits(
+        commits,
+        &input.package,
+        &input.to,
+        Some(&input.from),
+        input.changelog_config.clone(),
+        input.template_context.clone(),
+    )
+}
+
+/// Glob matching the files of the package in `package_dir`,
+/// which is relative to the repository root.
+pub(crate) fn package_glob(package_dir: &Utf8Path) -> String {
+    if package_dir.as_str().is_empty() {
+        "**".to_string()
+    } else {
+        format!("{package_dir}/**")
+    }
+}
+
+/// Render the notes of the release `version` of `package`,
+/// containing the given `(commit hash, commit message)` pairs.
+pub(crate) fn notes_from_commits(
+    commits: Vec<(String, String)>,
+    package: &str,
+    version: &str,
+    previous_version: Option<&str>,
+    changelog_config: Option<Config>,
+    template_context: BTreeMap<String, String>,
+) -> anyhow::Result<String> {
+    let commits: Vec<Commit> = commits
+        .into_iter()
+        .map(|(hash, message)| Commit::new(hash, message))
+        .collect();
+    let config = changelog_config.unwrap_or_else(default_git_cliff_config);
+    let mut changelog_builder = ChangelogBuilder::new(commits, version, package)
+        .with_config(entry_config(config))
+        .with_template_context(template_context);
+    if let Some(previous_version) = previous_version {
+        changelog_builder = changelog_builder.with_previous_version(previous_version);
+    }
+    let notes = changelog_builder.build().generate()?;
+    Ok(notes.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_of_first_release_have_no_previous_version() {
+        let commits = vec![("a1b2c3".to_string(), "feat: add one".to_string())];
+        let notes =
+            notes_from_commits(commits, "one", "0.1.0", None, None, BTreeMap::new()).unwrap();
+        assert!(notes.contains("add one"), "{notes}");
+    }
+
+    #[test]
+    fn release_notes_contain_only_commits_of_package_in_range() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let repo = Repo::init(root);
+        repo.tag_lightweight("v1.0.0").unwrap();
+        fs_err::create_dir_all(root.join("crates/one")).unwrap();
+        fs_err::write(root.join("crates/one/lib.rs"), "fn one() {}").unwrap();
+        repo.add_all_and_commit("feat: add one").unwrap();
+        fs_err::write(root.join("other.txt"), "other").unwrap();
+        repo.add_all_and_commit("fix: other file").unwrap();
+
+        let request = ReleaseNotesRequest::new(
+            fake_package::metadata::fake_metadata(),
+            "one",
+            "v1.0.0",
+            "HEAD",
+        );
+        let notes = render_release_notes(&repo, Utf8Path::new("crates/one"), &request).unwrap();
+        assert!(notes.contains("add one"), "{notes}");
+        assert!(!notes.contains("other file"), "{notes}");
+    }
+}
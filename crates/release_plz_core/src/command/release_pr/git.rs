@@ -66,15 +66,19 @@ impl GitRepo {
         Ok(tags)
     }
 
-    /// Get the commit and version from within the tag message.
+    /// Get the highest version among the tags matching one of the given regexes.
+    /// If `reachable_only` is `true`, only the tags reachable from `HEAD` are considered,
+    /// so that tags of other branches (e.g. newer releases on `main` when running on a
+    /// maintenance branch) are ignored.
     /// NOTE: This version isn't actually used for anything, we extract the package version from
     /// the Cargo.toml for packages, so if tag "v0.1.5" points to a commit where the Cargo.toml
     /// within that tree that has version 0.1.4, we use 0.1.4 for the package version
-    #[instrument(skip(release_tag_regex, self))]
+    #[instrument(skip(release_tag_regexes, self))]
     pub fn get_release_tag(
         &self,
-        release_tag_regex: &Regex,
+        release_tag_regexes: &[Regex],
         package_name: &str,
+        reachable_only: bool,
     ) -> anyhow::Result<Option<(String, Version)>> {
         // get the tags for this repo
         let tags = self
@@ -86,7 +90,10 @@ impl GitRepo {
         let tag_results: Vec<(String, Result<Version, _>)> = tags
             .iter()
             .filter_map(|tag| {
-                release_tag_regex.captures(tag).map(|captures| {
+                let captures = release_tag_regexes
+                    .iter()
+                    .find_map(|regex| regex.captures(tag));
+                captures.map(|captures| {
                     let version_str = captures
                         .get(1)
                         .expect("capture group 1 must exist in our regex")
@@ -96,7 +103,7 @@ impl GitRepo {
                 })
             })
             .collect();
-        debug!("{} tags matched pattern", tag_results.len());
+        debug!("{} tags matched patterns", tag_results.len());
 
         // Separate valid and invalid tags, logging any parsing errors
         let mut release_tags: Vec<(String, Version)> = Vec::new();
@@ -109,6 +116,19 @@ impl GitRepo {
             }
         }
 
+        if reachable_only {
+            let head = self.repo.head().context("get HEAD")?.peel_to_commit()?.id();
+            let mut reachable_tags = Vec::new();
+            for (tag, version) in release_tags {
+                if self.is_reachable_from(head, &tag)? {
+                    reachable_tags.push((tag, version));
+                } else {
+                    debug!("Ignoring tag `{tag}`: not reachable from HEAD");
+                }
+            }
+            release_tags = reachable_tags;
+        }
+
         // Sort by version (descending) and take the highest.
         // Another possible criteria is getting the latest tag, but we mimic the sorting logic of a
         // cargo registry.
@@ -122,6 +142,21 @@ impl GitRepo {
     /// either through reference in an annotated tag object or just pointing to a commit
     /// (lightweight)
     pub fn get_tag_commit(&self, tag_name: &str) -> anyhow::Result<String> {
+        self.tag_commit_id(tag_name).map(|id| id.to_string())
+    }
+
+    /// Whether the commit of the tag is `commit` or one of its ancestors.
+    fn is_reachable_from(&self, commit: Oid, tag_name: &str) -> anyhow::Result<bool> {
+        let tag_commit = self.tag_commit_id(tag_name)?;
+        let is_ancestor = tag_commit == commit
+            || self
+                .repo
+                .graph_descendant_of(commit, tag_commit)
+                .with_context(|| format!("check if tag '{tag_name}' is reachable"))?;
+        Ok(is_ancestor)
+    }
+
+    fn tag_commit_id(&self, tag_name: &str) -> anyhow::Result<Oid> {
         let tag_ref_name = format!("refs/tags/{tag_name}");
 
         let reference = self.repo.find_reference(&tag_ref_name).with_context(|| {
@@ -139,7 +174,7 @@ impl GitRepo {
         let commit_id = object.id();
         debug!("Found tag '{tag_name}' pointing to {commit_id}");
 
-        Ok(commit_id.to_string())
+        Ok(commit_id)
     }
 
     /// Checkout a particular commit
@@ -286,3 +321,39 @@ impl GitWorkTree {
         self.worktree.path()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cargo_metadata::camino::Utf8Path;
+
+    #[test]
+    fn tags_of_other_branches_are_ignored_if_reachable_only() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let repo = git_cmd::Repo::init(root);
+        repo.tag_lightweight("v1.0.0").unwrap();
+        repo.checkout_new_branch("1.x").unwrap();
+        fs_err::write(root.join("fix.txt"), "fix").unwrap();
+        repo.add_all_and_commit("fix: bug").unwrap();
+        repo.tag("v1.0.1", "v1.0.1").unwrap();
+        repo.checkout("-").unwrap();
+        fs_err::write(root.join("feat.txt"), "feat").unwrap();
+        repo.add_all_and_commit("feat!: breaking").unwrap();
+        repo.tag_lightweight("v2.0.0").unwrap();
+        repo.checkout("1.x").unwrap();
+
+        let regex = Regex::new(r"^v(\d+\.\d+\.\d+)$").unwrap();
+        let git_repo = GitRepo::open(root).unwrap();
+        let (tag, _) = git_repo
+            .get_release_tag(std::slice::from_ref(&regex), "pkg", false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tag, "v2.0.0");
+        let (tag, _) = git_repo
+            .get_release_tag(&[regex], "pkg", true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tag, "v1.0.1");
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::Context;
 use cargo_metadata::{
@@ -7,8 +7,12 @@ use cargo_metadata::{
     semver::Version,
 };
 use cargo_utils::{LocalManifest, canonical_local_manifest, workspace_members};
+use serde::Serialize;
+use tracing::debug;
 
-use crate::{CHANGELOG_FILENAME, PackagePath as _, changelog_parser::last_release_from_str};
+use crate::{
+    PackagePath as _, changelog_parser::last_release_from_str, update_request::DependenciesUpdate,
+};
 
 #[derive(Debug)]
 pub struct SetVersionRequest {
@@ -17,9 +21,85 @@ pub struct SetVersionRequest {
     /// Cargo metadata.
     metadata: Metadata,
     version_changes: SetVersionSpec,
+    /// Allow editing files with uncommitted changes.
+    allow_dirty: bool,
+    /// File names recognized as changelogs, used when the changelog path of a package
+    /// isn't configured.
+    changelog_filenames: Vec<String>,
+    /// New version of the `[workspace.package]` table.
+    workspace_version: Option<Version>,
+    /// Packages that follow the [`Self::workspace_version`] because they inherit it.
+    workspace_version_packages: BTreeSet<String>,
+    /// Command run on the edited manifests. See [`Self::with_format_manifests_command`].
+    format_manifests_command: Vec<String>,
+    /// Run `cargo update --workspace` after editing the manifests.
+    update_cargo_lock: bool,
 }
 
 impl SetVersionRequest {
+    pub fn with_allow_dirty(mut self, allow_dirty: bool) -> Self {
+        self.allow_dirty = allow_dirty;
+        self
+    }
+
+    pub fn with_changelog_filenames(mut self, changelog_filenames: Vec<String>) -> Self {
+        self.changelog_filenames = changelog_filenames;
+        self
+    }
+
+    pub fn with_update_cargo_lock(mut self, update_cargo_lock: bool) -> Self {
+        self.update_cargo_lock = update_cargo_lock;
+        self
+    }
+
+    /// Run the given command in the workspace root after editing the manifests,
+    /// passing the paths of the edited manifests as arguments.
+    pub fn with_format_manifests_command(mut self, format_manifests_command: Vec<String>) -> Self {
+        self.format_manifests_command = format_manifests_command;
+        self
+    }
+
+    /// Set the version of the `[workspace.package]` table.
+    /// The packages that inherit the workspace version follow it, unless
+    /// their version is set explicitly.
+    /// Fail if no package inherits the workspace version.
+    pub fn with_workspace_version(mut self, version: Version) -> anyhow::Result<Self> {
+        let SetVersionSpec::Workspace(changes) = &mut self.version_changes else {
+            anyhow::bail!(
+                "the workspace version can't be set together with the version of a single package"
+            );
+        };
+        for package in workspace_members(&self.metadata)? {
+            let manifest = LocalManifest::try_new(&package.manifest_path)?;
+            if manifest.version_is_inherited() && !changes.contains_key(package.name.as_str()) {
+                changes.insert(
+                    package.name.to_string(),
+                    VersionChange::new(version.clone()),
+                );
+                self.workspace_version_packages
+                    .insert(package.name.to_string());
+            }
+        }
+        anyhow::ensure!(
+            !self.workspace_version_packages.is_empty(),
+            "no package inherits the workspace version. Set the versions of the packages with `<package_name>@<version>`"
+        );
+        self.workspace_version = Some(version);
+        Ok(self)
+    }
+
+    fn changelog_path(&self, pkg: &Package, change: &VersionChange) -> anyhow::Result<Utf8PathBuf> {
+        let changelog_path = match &change.changelog_path {
+            Some(changelog_path) => changelog_path.clone(),
+            None => crate::find_changelog(pkg.package_path()?, &self.changelog_filenames),
+        };
+        Ok(changelog_path)
+    }
+
+    pub fn cargo_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
     pub fn set_changelog_path(&mut self, package: &str, changelog_path: Utf8PathBuf) {
         match &mut self.version_changes {
             SetVersionSpec::Single(change) => {
@@ -65,6 +145,23 @@ impl VersionChange {
     }
 }
 
+/// Outcome of [`set_version`].
+#[derive(Serialize, Default, Debug)]
+pub struct SetVersion {
+    packages: Vec<PackageSetVersion>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageSetVersion {
+    package_name: String,
+    old_version: Version,
+    new_version: Version,
+    /// Manifests rewritten by release-plz.
+    /// Besides the manifest of the package, it contains the manifests
+    /// of the packages depending on it.
+    manifests: Vec<Utf8PathBuf>,
+}
+
 impl SetVersionRequest {
     pub fn new(version_changes: SetVersionSpec, metadata: Metadata) -> anyhow::Result<Self> {
         let manifest = cargo_utils::workspace_manifest(&metadata);
@@ -73,11 +170,20 @@ impl SetVersionRequest {
             version_changes,
             metadata,
             manifest,
+            allow_dirty: false,
+            changelog_filenames: crate::DEFAULT_CHANGELOG_FILENAMES
+                .iter()
+                .map(|f| (*f).to_string())
+                .collect(),
+            workspace_version: None,
+            workspace_version_packages: BTreeSet::new(),
+            format_manifests_command: vec![],
+            update_cargo_lock: true,
         })
     }
 }
 
-pub fn set_version(input: &SetVersionRequest) -> anyhow::Result<()> {
+pub fn set_version(input: &SetVersionRequest) -> anyhow::Result<SetVersion> {
     let workspace_manifest = LocalManifest::try_new(&input.manifest)?;
     let workspace_dir = crate::manifest_dir(&workspace_manifest.path)?;
     let cargo_lock = workspace_dir.join("Cargo.lock");
@@ -88,6 +194,16 @@ pub fn set_version(input: &SetVersionRequest) -> anyhow::Result<()> {
         })
         .collect();
     let all_packages: Vec<&Package> = packages.values().collect();
+    if !input.allow_dirty {
+        let files_to_edit = files_to_edit(input, &packages, &workspace_manifest.path, &cargo_lock)?;
+        ensure_files_are_clean(workspace_dir, &files_to_edit)?;
+    }
+    if let Some(workspace_version) = &input.workspace_version {
+        let mut manifest = LocalManifest::try_new(&workspace_manifest.path)?;
+        manifest.set_workspace_version(workspace_version);
+        manifest.write().context("can't update workspace version")?;
+    }
+    let mut output = SetVersion::default();
     match &input.version_changes {
         SetVersionSpec::Single(change) => {
             anyhow::ensure!(
@@ -95,57 +211,164 @@ pub fn set_version(input: &SetVersionRequest) -> anyhow::Result<()> {
                 "Your workspace contains multiple packages. Please specify which package you want to update."
             );
             let package = packages.keys().next().unwrap();
-            set_version_in_package(
+            let package_set_version = set_version_in_package(
+                input,
                 &packages,
                 package,
                 &all_packages,
                 change,
                 &workspace_manifest,
             )?;
+            output.packages.push(package_set_version);
         }
         SetVersionSpec::Workspace(changes) => {
             for (package, change) in changes {
-                set_version_in_package(
+                let package_set_version = set_version_in_package(
+                    input,
                     &packages,
                     package,
                     &all_packages,
                     change,
                     &workspace_manifest,
                 )?;
+                output.packages.push(package_set_version);
             }
         }
     }
-    if cargo_lock.exists() {
-        super::update::update_cargo_lock(workspace_dir, false)?;
+    let mut edited_manifests: Vec<Utf8PathBuf> = output
+        .packages
+        .iter()
+        .flat_map(|p| p.manifests.iter().cloned())
+        .collect();
+    if input.workspace_version.is_some() {
+        edited_manifests.push(workspace_manifest.path.clone());
+    }
+    edited_manifests.sort();
+    edited_manifests.dedup();
+    super::update::format_manifests(
+        &input.format_manifests_command,
+        workspace_dir,
+        &edited_manifests,
+    )?;
+    if input.update_cargo_lock && cargo_lock.exists() {
+        super::update::update_cargo_lock(workspace_dir, &DependenciesUpdate::Workspace)?;
+    }
+    Ok(output)
+}
+
+/// Files that [`set_version`] might edit.
+fn files_to_edit(
+    input: &SetVersionRequest,
+    packages: &BTreeMap<String, Package>,
+    workspace_manifest: &Utf8Path,
+    cargo_lock: &Utf8Path,
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
+    let changes: Vec<(&Package, &VersionChange)> = match &input.version_changes {
+        SetVersionSpec::Single(change) => packages.values().map(|p| (p, change)).collect(),
+        SetVersionSpec::Workspace(changes) => changes
+            .iter()
+            .filter_map(|(package, change)| packages.get(package).map(|p| (p, change)))
+            .collect(),
+    };
+    let mut files = vec![workspace_manifest.to_path_buf()];
+    if input.update_cargo_lock {
+        files.push(cargo_lock.to_path_buf());
+    }
+    for (pkg, change) in changes {
+        files.push(pkg.manifest_path.clone());
+        files.push(input.changelog_path(pkg, change)?);
+        // Packages depending on the updated package.
+        let dependents = packages.values().filter(|p| {
+            p.dependencies
+                .iter()
+                .any(|d| d.name == *pkg.name && d.path.is_some())
+        });
+        files.extend(dependents.map(|p| p.manifest_path.clone()));
+    }
+    let files = files
+        .into_iter()
+        // Files that don't exist can't have uncommitted changes we care about.
+        .filter_map(|f| crate::fs_utils::canonicalize_utf8(&f).ok())
+        .collect();
+    Ok(files)
+}
+
+/// Fail if any of the given files has uncommitted changes.
+/// If the project isn't in a git repository, there's nothing to check.
+fn ensure_files_are_clean(workspace_dir: &Utf8Path, files: &[Utf8PathBuf]) -> anyhow::Result<()> {
+    if !git_cmd::is_inside_work_tree(workspace_dir) {
+        debug!("{workspace_dir} is not in a git repository, skipping dirty check");
+        return Ok(());
     }
+    let repo_root = crate::root_repo_path_from_manifest_dir(workspace_dir)?;
+    let repo_root = crate::fs_utils::canonicalize_utf8(&repo_root)?;
+    let changes = git_cmd::changes_except_typechanges_in_dir(&repo_root)?;
+    let dirty_files: Vec<&Utf8PathBuf> = files
+        .iter()
+        .filter(|f| {
+            f.strip_prefix(&repo_root).is_ok_and(|f| {
+                // Untracked directories end with `/`, so check the prefix.
+                changes.iter().any(|c| f.starts_with(c))
+            })
+        })
+        .collect();
+    anyhow::ensure!(
+        dirty_files.is_empty(),
+        "the following files have uncommitted changes: {dirty_files:?}. Please commit or stash them, or use `--allow-dirty` to edit them anyway."
+    );
     Ok(())
 }
 
 fn set_version_in_package(
+    input: &SetVersionRequest,
     packages: &BTreeMap<String, Package>,
     package: &String,
     all_packages: &[&Package],
     change: &VersionChange,
     workspace_manifest: &LocalManifest,
-) -> Result<(), anyhow::Error> {
+) -> Result<PackageSetVersion, anyhow::Error> {
     let pkg = packages
         .get(package)
         .with_context(|| format!("package {package} not found"))?;
     let pkg_path = pkg.package_path()?;
-    super::update::set_version(
-        all_packages,
-        pkg_path,
-        &change.version,
-        &workspace_manifest.path,
-    )?;
-    let default_changelog_path = pkg_path.join(CHANGELOG_FILENAME);
-    let changelog_path: &Utf8Path = change
-        .changelog_path
-        .as_deref()
-        .unwrap_or(&default_changelog_path);
-    update_changelog(changelog_path, &pkg.version, &change.version)
-        .with_context(|| format!("failed to update changelog at {changelog_path}"))?;
-    Ok(())
+    let follows_workspace_version = input.workspace_version_packages.contains(package);
+    let manifests = if follows_workspace_version {
+        // The workspace version is already set, so only update the dependents.
+        let package_path = crate::fs_utils::canonicalize_utf8(pkg_path)?;
+        let mut manifests = vec![workspace_manifest.path.clone()];
+        let dependents = super::update::update_dependencies(
+            all_packages,
+            &change.version,
+            &package_path,
+            &workspace_manifest.path,
+        )?;
+        for manifest in dependents {
+            if !manifests.contains(&manifest) {
+                manifests.push(manifest);
+            }
+        }
+        manifests
+    } else {
+        super::update::set_version(
+            all_packages,
+            pkg_path,
+            &change.version,
+            &workspace_manifest.path,
+        )?
+    };
+    let changelog_path = input.changelog_path(pkg, change)?;
+    // Packages following the workspace version weren't listed explicitly,
+    // so they might not have a changelog.
+    if !follows_workspace_version || changelog_path.exists() {
+        update_changelog(&changelog_path, &pkg.version, &change.version)
+            .with_context(|| format!("failed to update changelog at {changelog_path}"))?;
+    }
+    Ok(PackageSetVersion {
+        package_name: package.clone(),
+        old_version: pkg.version.clone(),
+        new_version: change.version.clone(),
+        manifests,
+    })
 }
 
 fn update_changelog(
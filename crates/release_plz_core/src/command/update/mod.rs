@@ -5,18 +5,23 @@ mod update_config;
 pub mod update_request;
 pub mod updater;
 
-use crate::{PackagePath, tmp_repo::TempRepo};
-use crate::{fs_utils, root_repo_path_from_manifest_dir};
+use crate::{ChangelogOutputFormat, PackagePath, changelog_parser, tmp_repo::TempRepo};
+use crate::{
+    copy_to_temp_dir, fs_utils, new_manifest_dir_path, new_project_root,
+    root_repo_path_from_manifest_dir,
+};
 use anyhow::Context;
-use cargo_metadata::camino::Utf8Path;
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
 use cargo_metadata::{Package, semver::Version};
 use cargo_utils::LocalManifest;
 use cargo_utils::{CARGO_TOML, upgrade_requirement};
 use git_cmd::Repo;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::iter;
+use std::process::Command;
 use tracing::{info, warn};
-use update_request::UpdateRequest;
+use update_request::{DependenciesUpdate, UpdateRequest};
 
 use tracing::{debug, instrument};
 
@@ -48,11 +53,17 @@ pub async fn update(input: &UpdateRequest) -> anyhow::Result<(PackagesUpdate, Te
     // workspace dependencies.
     let all_packages: Vec<Package> = cargo_utils::workspace_members(&local_metadata)?.collect();
     let all_packages_ref: Vec<&Package> = all_packages.iter().collect();
-    update_manifests(&packages_to_update, local_manifest_path, &all_packages_ref)?;
+    let edited_manifests =
+        update_manifests(&packages_to_update, local_manifest_path, &all_packages_ref)?;
+    format_manifests(
+        input.format_manifests_command(),
+        input.local_manifest_dir()?,
+        &edited_manifests,
+    )?;
     update_changelogs(input, &packages_to_update)?;
     if !packages_to_update.updates().is_empty() {
         let local_manifest_dir = input.local_manifest_dir()?;
-        update_cargo_lock(local_manifest_dir, input.should_update_dependencies())?;
+        update_lockfile(input, local_manifest_dir)?;
 
         let local_repo_root = root_repo_path_from_manifest_dir(local_manifest_dir)?;
         let there_are_commits_to_push = Repo::new(local_repo_root)?.is_clean().is_err();
@@ -64,11 +75,83 @@ pub async fn update(input: &UpdateRequest) -> anyhow::Result<(PackagesUpdate, Te
     Ok((packages_to_update, repository))
 }
 
+/// Update a copy of the local Rust project and write the files that the update changes
+/// into `output_dir`, mirroring the layout of the repository.
+/// The original project isn't modified.
+#[instrument(skip_all)]
+pub async fn update_to_output_dir(
+    input: &UpdateRequest,
+    output_dir: &Utf8Path,
+) -> anyhow::Result<PackagesUpdate> {
+    let manifest_dir = input.local_manifest_dir()?;
+    let original_project_root = root_repo_path_from_manifest_dir(manifest_dir)?;
+    let tmp_project_root_parent = copy_to_temp_dir(&original_project_root)?;
+    let tmp_project_manifest_dir = new_manifest_dir_path(
+        &original_project_root,
+        manifest_dir,
+        tmp_project_root_parent.path(),
+    )?;
+    let tmp_project_root =
+        new_project_root(&original_project_root, tmp_project_root_parent.path())?;
+
+    let new_update_request = input
+        .clone()
+        .set_local_manifest(tmp_project_manifest_dir.join(CARGO_TOML))
+        .context("can't find temporary project")?;
+    let (packages_to_update, _temp_repository) = update(&new_update_request)
+        .await
+        .context("failed to update packages")?;
+    let written_files = write_changed_files(
+        &Repo::new(&tmp_project_root)?,
+        &original_project_root,
+        output_dir,
+    )?;
+    info!(
+        "wrote {} changed files to {output_dir}",
+        written_files.len()
+    );
+    Ok(packages_to_update)
+}
+
+/// Copy the files of `repo` that differ from the ones of `original_project_root`
+/// into `output_dir`, keeping their path relative to the repository root.
+/// Returns the paths of the files relative to the repository root.
+fn write_changed_files(
+    repo: &Repo,
+    original_project_root: &Utf8Path,
+    output_dir: &Utf8Path,
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
+    let candidates = repo.git(&["ls-files", "--modified", "--others", "--exclude-standard"])?;
+    let mut written_files = vec![];
+    for file in candidates.lines().map(Utf8Path::new) {
+        let updated_file = repo.directory().join(file);
+        // Deleted files are listed as modified.
+        if !updated_file.is_file() {
+            continue;
+        }
+        let updated_content = fs_err::read(&updated_file)?;
+        // Skip uncommitted changes of the original project that the update didn't touch.
+        let original_content = fs_err::read(original_project_root.join(file)).ok();
+        if original_content.as_ref() == Some(&updated_content) {
+            continue;
+        }
+        let destination = output_dir.join(file);
+        if let Some(parent) = destination.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(&destination, updated_content)?;
+        written_files.push(file.to_path_buf());
+    }
+    Ok(written_files)
+}
+
+/// Returns the paths of the manifests that were rewritten.
 fn update_manifests(
     packages_to_update: &PackagesUpdate,
     local_manifest_path: &Utf8Path,
     all_packages: &[&Package],
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
+    let mut edited_manifests = vec![];
     // Distinguish packages type to avoid updating the version of packages that inherit the workspace version
     let (workspace_pkgs, independent_pkgs): (PackagesToUpdate, PackagesToUpdate) =
         packages_to_update
@@ -86,23 +169,61 @@ fn update_manifests(
         local_manifest
             .write()
             .context("can't update workspace version")?;
+        edited_manifests.push(local_manifest.path);
 
         for (pkg, _) in workspace_pkgs {
             let package_path = pkg.package_path()?;
-            update_dependencies(
+            let updated_dependents = update_dependencies(
                 all_packages,
                 new_workspace_version,
                 package_path,
                 local_manifest_path,
             )?;
+            edited_manifests.extend(updated_dependents);
         }
     }
 
-    update_versions(
+    let updated_manifests = update_versions(
         all_packages,
         &PackagesUpdate::new(independent_pkgs),
         local_manifest_path,
     )?;
+    edited_manifests.extend(updated_manifests);
+    edited_manifests.sort();
+    edited_manifests.dedup();
+    Ok(edited_manifests)
+}
+
+/// Run the `format_manifests_command` in the workspace root,
+/// passing the paths of the `manifests` as arguments.
+/// Nothing happens if the command is empty or if there are no manifests to format.
+/// The stdout of the command is redirected to stderr, to keep the stdout of release-plz
+/// (e.g. the JSON output) clean.
+#[instrument(skip_all)]
+pub(crate) fn format_manifests(
+    format_manifests_command: &[String],
+    workspace_root: &Utf8Path,
+    manifests: &[Utf8PathBuf],
+) -> anyhow::Result<()> {
+    let Some((program, args)) = format_manifests_command.split_first() else {
+        return Ok(());
+    };
+    if manifests.is_empty() {
+        return Ok(());
+    }
+    let command_str = format_manifests_command.join(" ");
+    info!("formatting edited manifests with `{command_str}`");
+    let status = Command::new(program)
+        .args(args)
+        .args(manifests)
+        .current_dir(workspace_root)
+        .stdout(std::io::stderr())
+        .status()
+        .with_context(|| format!("failed to run format manifests command `{command_str}`"))?;
+    anyhow::ensure!(
+        status.success(),
+        "format manifests command `{command_str}` failed with {status}"
+    );
     Ok(())
 }
 
@@ -111,17 +232,19 @@ fn update_versions(
     all_packages: &[&Package],
     packages_to_update: &PackagesUpdate,
     workspace_manifest: &Utf8Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
+    let mut updated_manifests = vec![];
     for (package, update) in packages_to_update.updates() {
         let package_path = package.package_path()?;
-        set_version(
+        let manifests = set_version(
             all_packages,
             package_path,
             &update.version,
             workspace_manifest,
         )?;
+        updated_manifests.extend(manifests);
     }
-    Ok(())
+    Ok(updated_manifests)
 }
 
 #[instrument(skip_all)]
@@ -132,21 +255,117 @@ fn update_changelogs(
     for (package, update) in local_packages.updates() {
         if let Some(changelog) = update.changelog.as_ref() {
             let changelog_path = update_request.changelog_path(package);
-            fs_err::write(&changelog_path, changelog).context("cannot write changelog")?;
+            let config = update_request.get_package_config(&package.name).generic;
+            let changelog = match config.changelog_max_entries {
+                Some(max_entries)
+                    if update.changelog_output_format == ChangelogOutputFormat::Markdown =>
+                {
+                    trim_changelog(
+                        changelog,
+                        &changelog_path,
+                        max_entries,
+                        config.changelog_archive,
+                    )?
+                }
+                _ => Cow::Borrowed(changelog.as_str()),
+            };
+            write_changelog_if_changed(&changelog_path, &changelog)?;
         }
     }
     Ok(())
 }
 
+/// Remove the releases exceeding `max_entries` from the changelog.
+/// If `archive` is `true`, move them to the archive file next to the changelog.
+fn trim_changelog<'a>(
+    changelog: &'a str,
+    changelog_path: &Utf8Path,
+    max_entries: usize,
+    archive: bool,
+) -> anyhow::Result<Cow<'a, str>> {
+    let Some((recent_releases, old_releases)) =
+        changelog_parser::split_old_releases(changelog, max_entries)
+            .with_context(|| format!("cannot trim changelog {changelog_path}"))?
+    else {
+        return Ok(Cow::Borrowed(changelog));
+    };
+    if archive {
+        let archive_path = changelog_archive_path(changelog_path);
+        let old_archive = fs_err::read_to_string(&archive_path).ok();
+        let new_archive = prepend_to_archive(old_archive.as_deref(), old_releases)
+            .with_context(|| format!("cannot update changelog archive {archive_path}"))?;
+        fs_err::write(&archive_path, new_archive).context("cannot write changelog archive")?;
+    }
+    Ok(Cow::Owned(format!("{}\n", recent_releases.trim_end())))
+}
+
+/// Archive of the changelog, e.g. `CHANGELOG-archive.md` for `CHANGELOG.md`.
+fn changelog_archive_path(changelog_path: &Utf8Path) -> Utf8PathBuf {
+    let file_stem = changelog_path.file_stem().unwrap_or("CHANGELOG");
+    let file_name = match changelog_path.extension() {
+        Some(extension) => format!("{file_stem}-archive.{extension}"),
+        None => format!("{file_stem}-archive"),
+    };
+    changelog_path.with_file_name(file_name)
+}
+
+/// Add the `releases` removed from the changelog before the releases of the archive,
+/// because they are more recent.
+fn prepend_to_archive(archive: Option<&str>, releases: &str) -> anyhow::Result<String> {
+    let releases = releases.trim_end();
+    let (header, archived_releases) = match archive {
+        Some(archive) => changelog_parser::split_old_releases(archive, 0)?.unwrap_or((archive, "")),
+        None => (CHANGELOG_ARCHIVE_HEADER, ""),
+    };
+    let new_archive = if archived_releases.is_empty() {
+        format!("{header}{releases}\n")
+    } else {
+        format!("{header}{releases}\n\n{archived_releases}")
+    };
+    Ok(new_archive)
+}
+
+const CHANGELOG_ARCHIVE_HEADER: &str = "# Changelog archive\n\n";
+
+/// Write the changelog, unless the file already has the same content.
+/// Skipping the write keeps the file untouched when running the update again.
+/// Returns `true` if the file was written.
+fn write_changelog_if_changed(changelog_path: &Utf8Path, changelog: &str) -> anyhow::Result<bool> {
+    if let Ok(existing_changelog) = fs_err::read_to_string(changelog_path)
+        && normalize_line_endings(&existing_changelog) == normalize_line_endings(changelog)
+    {
+        debug!("changelog {changelog_path} is up-to-date");
+        return Ok(false);
+    }
+    fs_err::write(changelog_path, changelog).context("cannot write changelog")?;
+    Ok(true)
+}
+
+fn normalize_line_endings(text: &str) -> std::borrow::Cow<'_, str> {
+    if text.contains("\r\n") {
+        text.replace("\r\n", "\n").into()
+    } else {
+        text.into()
+    }
+}
+
+/// Run `cargo update`, unless the user disabled it.
+fn update_lockfile(input: &UpdateRequest, root: &Utf8Path) -> anyhow::Result<()> {
+    if !input.should_update_cargo_lock() {
+        info!(
+            "skipping `cargo update`: the Cargo.lock file might be out of date until the next build"
+        );
+        return Ok(());
+    }
+    update_cargo_lock(root, input.dependencies_update())
+}
+
 #[instrument(skip_all)]
 pub(crate) fn update_cargo_lock(
     root: &Utf8Path,
-    update_all_dependencies: bool,
+    dependencies_update: &DependenciesUpdate,
 ) -> anyhow::Result<()> {
-    let mut args = vec!["update"];
-    if !update_all_dependencies {
-        args.push("--workspace");
-    }
+    let args = dependencies_update.cargo_update_args();
     let output = crate::cargo::run_cargo(root, &args)
         .context("error while running cargo to update the Cargo.lock file")?;
 
@@ -160,13 +379,15 @@ pub(crate) fn update_cargo_lock(
     Ok(())
 }
 
+/// Set the version of the package and update it in the dependencies of the other packages.
+/// Returns the paths of the manifests that were rewritten.
 #[instrument(skip(all_packages))]
 pub fn set_version(
     all_packages: &[&Package],
     package_path: &Utf8Path,
     version: &Version,
     workspace_manifest: &Utf8Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
     debug!("updating version");
     let mut local_manifest =
         LocalManifest::try_new(&package_path.join("Cargo.toml")).context("cannot read manifest")?;
@@ -176,8 +397,15 @@ pub fn set_version(
         .with_context(|| format!("cannot update manifest {:?}", &local_manifest.path))?;
 
     let package_path = fs_utils::canonicalize_utf8(crate::manifest_dir(&local_manifest.path)?)?;
-    update_dependencies(all_packages, version, &package_path, workspace_manifest)?;
-    Ok(())
+    let updated_dependents =
+        update_dependencies(all_packages, version, &package_path, workspace_manifest)?;
+    let mut updated_manifests = vec![local_manifest.path];
+    for manifest in updated_dependents {
+        if !updated_manifests.contains(&manifest) {
+            updated_manifests.push(manifest);
+        }
+    }
+    Ok(updated_manifests)
 }
 
 /// Update the package version in the dependencies of the other packages.
@@ -202,12 +430,14 @@ pub fn set_version(
 /// pkg1 = { path = "../pkg1", version = "1.2.4" }
 /// ```
 ///
-fn update_dependencies(
+/// Returns the paths of the manifests where the dependency version changed.
+pub(crate) fn update_dependencies(
     all_packages: &[&Package],
     version: &Version,
     package_path: &Utf8Path,
     workspace_manifest: &Utf8Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
+    let mut updated_manifests = vec![];
     let all_manifests = iter::once(workspace_manifest)
         .chain(all_packages.iter().map(|pkg| pkg.manifest_path.as_path()));
     for manifest in all_manifests {
@@ -219,6 +449,7 @@ fn update_dependencies(
             .filter(|d| d.contains_key("version"))
             .filter(|d| crate::is_dependency_referred_to_package(*d, &manifest_dir, package_path));
 
+        let mut is_updated = false;
         for dep in deps_to_update {
             let old_req = dep
                 .get("version")
@@ -227,9 +458,198 @@ fn update_dependencies(
                 .unwrap_or("*");
             if let Some(new_req) = upgrade_requirement(old_req, version)? {
                 dep.insert("version", toml_edit::value(new_req));
+                is_updated = true;
             }
         }
         local_manifest.write()?;
+        if is_updated {
+            updated_manifests.push(local_manifest.path);
+        }
+    }
+    Ok(updated_manifests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_update_is_skipped_if_disabled() {
+        // `cargo update` fails in a directory without a manifest,
+        // so the function succeeds only if cargo doesn't run.
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let request = UpdateRequest::new(fake_package::metadata::fake_metadata()).unwrap();
+        update_lockfile(&request, root).unwrap_err();
+        let request = request.with_update_cargo_lock(false);
+        update_lockfile(&request, root).unwrap();
+    }
+
+    #[test]
+    fn old_changelog_releases_are_archived() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let changelog_path = Utf8Path::from_path(tmp_dir.path())
+            .unwrap()
+            .join("CHANGELOG.md");
+        let changelog = "\
+# Changelog
+
+## [0.3.0] - 2024-03-01
+
+- feature
+
+## [0.2.0] - 2024-02-01
+
+- fix
+";
+        let trimmed = trim_changelog(changelog, &changelog_path, 1, true).unwrap();
+        assert_eq!(
+            trimmed,
+            "# Changelog\n\n## [0.3.0] - 2024-03-01\n\n- feature\n"
+        );
+
+        let archive_path = changelog_archive_path(&changelog_path);
+        assert_eq!(archive_path.file_name(), Some("CHANGELOG-archive.md"));
+        assert_eq!(
+            fs_err::read_to_string(&archive_path).unwrap(),
+            "# Changelog archive\n\n## [0.2.0] - 2024-02-01\n\n- fix\n"
+        );
+
+        // Releases removed later are added before the archived ones.
+        let changelog = "\
+# Changelog
+
+## [0.4.0] - 2024-04-01
+
+- docs
+
+## [0.3.0] - 2024-03-01
+
+- feature
+";
+        trim_changelog(changelog, &changelog_path, 1, true).unwrap();
+        assert_eq!(
+            fs_err::read_to_string(&archive_path).unwrap(),
+            "\
+# Changelog archive
+
+## [0.3.0] - 2024-03-01
+
+- feature
+
+## [0.2.0] - 2024-02-01
+
+- fix
+"
+        );
+    }
+
+    #[test]
+    fn changelog_without_old_releases_is_not_trimmed() {
+        let changelog = "# Changelog\n\n## [0.1.0] - 2024-01-01\n\n- fix\n";
+        let trimmed = trim_changelog(changelog, Utf8Path::new("CHANGELOG.md"), 1, true).unwrap();
+        assert!(matches!(trimmed, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn identical_changelog_is_not_rewritten() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let changelog_path = Utf8Path::from_path(tmp_dir.path())
+            .unwrap()
+            .join("CHANGELOG.md");
+        assert!(write_changelog_if_changed(&changelog_path, "# Changelog\n\n- fix\n").unwrap());
+        assert!(!write_changelog_if_changed(&changelog_path, "# Changelog\n\n- fix\n").unwrap());
+
+        // Line endings are ignored.
+        fs_err::write(&changelog_path, "# Changelog\r\n\r\n- fix\r\n").unwrap();
+        assert!(!write_changelog_if_changed(&changelog_path, "# Changelog\n\n- fix\n").unwrap());
+        assert_eq!(
+            fs_err::read_to_string(&changelog_path).unwrap(),
+            "# Changelog\r\n\r\n- fix\r\n"
+        );
+
+        assert!(write_changelog_if_changed(&changelog_path, "# Changelog\n\n- feat\n").unwrap());
+        assert_eq!(
+            fs_err::read_to_string(&changelog_path).unwrap(),
+            "# Changelog\n\n- feat\n"
+        );
+    }
+
+    #[test]
+    fn only_files_changed_by_update_are_written_to_output_dir() {
+        let original = tempfile::tempdir().unwrap();
+        let original_root = Utf8Path::from_path(original.path()).unwrap();
+        Repo::init(original_root);
+        fs_err::create_dir_all(original_root.join("crates/one")).unwrap();
+        fs_err::write(
+            original_root.join("crates/one/Cargo.toml"),
+            "version = \"0.1.0\"",
+        )
+        .unwrap();
+        fs_err::write(original_root.join("notes.txt"), "uncommitted").unwrap();
+
+        let updated = copy_to_temp_dir(original_root).unwrap();
+        let updated_root = new_project_root(original_root, updated.path()).unwrap();
+        fs_err::write(
+            updated_root.join("crates/one/Cargo.toml"),
+            "version = \"0.2.0\"",
+        )
+        .unwrap();
+        fs_err::write(updated_root.join("crates/one/CHANGELOG.md"), "# Changelog").unwrap();
+        fs_err::write(updated_root.join("README.md"), "# updated").unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        let output_dir = Utf8Path::from_path(output.path()).unwrap();
+        let repo = Repo::new(&updated_root).unwrap();
+        let mut written_files = write_changed_files(&repo, original_root, output_dir).unwrap();
+        written_files.sort();
+
+        assert_eq!(
+            written_files,
+            [
+                "README.md",
+                "crates/one/CHANGELOG.md",
+                "crates/one/Cargo.toml"
+            ]
+        );
+        assert_eq!(
+            fs_err::read_to_string(output_dir.join("crates/one/Cargo.toml")).unwrap(),
+            "version = \"0.2.0\""
+        );
+        assert!(!output_dir.join("notes.txt").exists());
+        assert_eq!(
+            fs_err::read_to_string(original_root.join("README.md")).unwrap(),
+            "# my awesome project"
+        );
+    }
+
+    #[test]
+    fn edited_manifests_are_passed_to_format_command() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let workspace_root = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let manifests = [
+            workspace_root.join("Cargo.toml"),
+            workspace_root.join("crates/one/Cargo.toml"),
+        ];
+        let command = ["sh", "-c", r#"printf '%s\n' "$@" > args.txt"#, "sh"].map(String::from);
+        format_manifests(&command, workspace_root, &manifests).unwrap();
+
+        let args = fs_err::read_to_string(workspace_root.join("args.txt")).unwrap();
+        assert_eq!(args, format!("{}\n{}\n", manifests[0], manifests[1]));
+    }
+
+    #[test]
+    fn failing_format_command_returns_error() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let workspace_root = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let manifests = [workspace_root.join("Cargo.toml")];
+        let error =
+            format_manifests(&["false".to_string()], workspace_root, &manifests).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("format manifests command `false` failed"),
+            "{error}"
+        );
     }
-    Ok(())
 }
@@ -1,12 +1,27 @@
 use cargo_metadata::{Package, semver::Version};
+use serde::Serialize;
 use tracing::warn;
 
-use crate::{UpdateResult, semver_check::SemverCheck};
+use crate::{UpdateResult, diff::CommitSummary, semver_check::SemverCheck};
 
 use super::ReleaseInfo;
 
 pub type PackagesToUpdate = Vec<(Package, UpdateResult)>;
 
+#[derive(Serialize, Debug)]
+pub struct UpdateOutput {
+    packages: Vec<PackageUpdateOutput>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackageUpdateOutput {
+    package_name: String,
+    previous_version: Version,
+    version: Version,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commits: Option<Vec<CommitSummary>>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct PackagesUpdate {
     updates: PackagesToUpdate,
@@ -43,6 +58,25 @@ impl PackagesUpdate {
         self.workspace_version.as_ref()
     }
 
+    /// Json output of the `update` command.
+    /// If `include_commits` is true, each package lists the commits that justify the update.
+    pub fn output(&self, include_commits: bool) -> UpdateOutput {
+        let packages = self
+            .updates
+            .iter()
+            .map(|(package, update)| PackageUpdateOutput {
+                package_name: package.name.to_string(),
+                previous_version: update
+                    .registry_version
+                    .clone()
+                    .unwrap_or_else(|| package.version.clone()),
+                version: update.version.clone(),
+                commits: include_commits.then(|| update.commits.clone()),
+            })
+            .collect();
+        UpdateOutput { packages }
+    }
+
     pub fn summary(&self) -> String {
         let updates = self.updates_summary();
         let breaking_changes = self.breaking_changes();
@@ -71,6 +105,30 @@ impl PackagesUpdate {
             .collect()
     }
 
+    /// New changelog entries of the updated packages.
+    /// If multiple packages are updated, each entry is preceded by a heading
+    /// with the package name.
+    pub fn changelog_entries(&self) -> String {
+        let entries: Vec<(&Package, &str)> = self
+            .updates
+            .iter()
+            .filter_map(|(package, update)| {
+                update
+                    .new_changelog_entry
+                    .as_deref()
+                    .map(|entry| (package, entry.trim()))
+            })
+            .collect();
+        if let [(_, entry)] = entries.as_slice() {
+            return entry.to_string();
+        }
+        entries
+            .iter()
+            .map(|(package, entry)| format!("# `{}`\n\n{entry}", package.name))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
     pub fn breaking_changes(&self) -> String {
         self.updates
             .iter()
@@ -81,7 +139,9 @@ impl PackagesUpdate {
                         package.name, incompatibilities
                     )
                 }
-                SemverCheck::Compatible | SemverCheck::Skipped => String::new(),
+                SemverCheck::Compatible | SemverCheck::MinorRequired(_) | SemverCheck::Skipped => {
+                    String::new()
+                }
             })
             .collect()
     }
@@ -117,7 +177,7 @@ impl PackagesUpdate {
                     SemverCheck::Incompatible(incompatibilities) => {
                         ("incompatible", Some(incompatibilities.clone()))
                     }
-                    SemverCheck::Compatible => ("compatible", None),
+                    SemverCheck::Compatible | SemverCheck::MinorRequired(_) => ("compatible", None),
                     SemverCheck::Skipped => ("skipped", None),
                 };
 
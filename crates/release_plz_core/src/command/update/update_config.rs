@@ -1,5 +1,5 @@
-use cargo_metadata::camino::Utf8PathBuf;
-use next_version::VersionUpdater;
+use cargo_metadata::{camino::Utf8PathBuf, semver::Version};
+use next_version::{VersionIncrement, VersionUpdater};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateConfig {
@@ -10,9 +10,19 @@ pub struct UpdateConfig {
     /// Note: You can only run cargo-semver-checks if the package contains a library.
     ///       For example, if it has a `lib.rs` file.
     pub semver_check: bool,
+    /// Skip cargo-semver-checks if all the commits of the package are of these
+    /// conventional commit types (e.g. `docs`, `chore`).
+    pub semver_check_skip_if_only: Vec<String>,
+    /// Skip cargo-semver-checks if the current version of the package is lower than `1.0.0`.
+    pub semver_check_skip_pre_1_0: bool,
+    /// If set, pass the planned release type to cargo-semver-checks and act when
+    /// the API changes require a bigger version bump.
+    pub semver_check_enforce: Option<SemverCheckEnforce>,
     /// Whether to create/update changelog or not.
     /// Default: `true`.
     pub changelog_update: bool,
+    /// What the link of the version heading of the changelog points to.
+    pub changelog_link_type: ChangelogLinkType,
     /// High-level toggle to process this package or ignore it.
     pub release: bool,
     /// Whether to publish this package to a registry.
@@ -24,23 +34,64 @@ pub struct UpdateConfig {
     pub features_always_increment_minor: bool,
     /// Template for the git tag created by release-plz.
     pub tag_name_template: Option<String>,
+    /// Prefix prepended to the template of the git tag.
+    pub tag_prefix: String,
     /// Custom regex to match commit types that should trigger a minor version increment.
     pub custom_minor_increment_regex: Option<String>,
     /// Custom regex to match commit types that should trigger a major version increment.
     pub custom_major_increment_regex: Option<String>,
+    /// Tera template of the build metadata appended to the next version, e.g. `{{ date }}`.
+    pub version_metadata_template: Option<String>,
     /// Whether to use git tags instead of registry for determining package versions.
     pub git_only: Option<bool>,
+    /// Templates of the git tags created before adopting release-plz.
+    /// Used in `git_only` mode to find the latest release.
+    pub previous_tag_patterns: Vec<String>,
+    /// In `git_only` mode, only consider the tags reachable from the current commit
+    /// to find the latest release.
+    /// Default: `false`.
+    pub git_only_reachable_tags_only: bool,
+    /// Globs of the files to ignore when comparing the local package with the released one.
+    /// Paths are relative to the package root.
+    pub compare_ignore_files: Vec<String>,
+    /// Features to enable when running `cargo package` and cargo-semver-checks.
+    /// If non-empty, pass the `--features` flag.
+    pub features: Vec<String>,
+    /// Enable all features when running `cargo package` and cargo-semver-checks.
+    /// If true, pass the `--all-features` flag.
+    pub all_features: bool,
+    /// If true, fail when the changelog already contains a manually edited entry
+    /// for the next version that misses some of the new changes.
+    /// Default: `false`.
+    pub changelog_protect_manual_edits: bool,
+    /// If set, keep only this number of releases in the changelog.
+    pub changelog_max_entries: Option<usize>,
+    /// If `true`, the releases removed from the changelog because of
+    /// `changelog_max_entries` are moved to an archive file next to the changelog.
+    /// Default: `false`.
+    pub changelog_archive: bool,
 }
 
 /// Package-specific config
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageUpdateConfig {
     /// config that can be applied by default to all packages.
     pub generic: UpdateConfig,
     /// List of package names.
     /// Include the changelogs of these packages in the changelog of the current package.
     pub changelog_include: Vec<String>,
+    /// Globs of paths, relative to the repository root.
+    /// Include the commits that changed these paths in the changelog of the current package.
+    pub changelog_include_paths: Vec<String>,
     pub version_group: Option<String>,
+    /// If set, increment this part of the version instead of the one derived from the commits.
+    pub force_bump: Option<ForceBump>,
+    /// If set, the next version derived from the commits is raised to this version
+    /// when it's lower.
+    pub min_version: Option<Version>,
+    /// - If `true` (default), release the package when one of its local dependencies is released.
+    /// - If `false`, release the package only if it contains new commits.
+    pub release_on_dependency_change: bool,
 }
 
 impl From<UpdateConfig> for PackageUpdateConfig {
@@ -48,11 +99,67 @@ impl From<UpdateConfig> for PackageUpdateConfig {
         Self {
             generic: config,
             changelog_include: vec![],
+            changelog_include_paths: vec![],
             version_group: None,
+            force_bump: None,
+            min_version: None,
+            release_on_dependency_change: true,
         }
     }
 }
 
+/// Target of the link of the version heading of the changelog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangelogLinkType {
+    /// Compare the previous tag with the new one.
+    /// For the first release, link to the page of the new tag.
+    #[default]
+    Compare,
+    /// Link to the page of the new tag.
+    Tag,
+    /// Don't link the version heading.
+    None,
+}
+
+/// What to do when cargo-semver-checks finds that the planned version bump is too small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemverCheckEnforce {
+    /// Return an error.
+    Fail,
+    /// Increment the version as required by the API changes.
+    Bump,
+}
+
+/// Part of the version to increment when releasing a package,
+/// regardless of the commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForceBump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl ForceBump {
+    /// Increment the given part of the version.
+    /// Unlike the increment derived from the commits, this doesn't take into account
+    /// that `0.x` versions use the minor version for breaking changes.
+    /// E.g. `Major` bumps `0.3.1` to `1.0.0`.
+    pub fn bump(self, version: &Version) -> Version {
+        let increment = match self {
+            Self::Major => VersionIncrement::Major,
+            Self::Minor => VersionIncrement::Minor,
+            Self::Patch => VersionIncrement::Patch,
+        };
+        increment.bump(version)
+    }
+}
+
+impl Default for PackageUpdateConfig {
+    fn default() -> Self {
+        UpdateConfig::default().into()
+    }
+}
+
 impl PackageUpdateConfig {
     pub fn semver_check(&self) -> bool {
         self.generic.semver_check
@@ -75,15 +182,29 @@ impl Default for UpdateConfig {
     fn default() -> Self {
         Self {
             semver_check: true,
+            semver_check_skip_if_only: vec![],
+            semver_check_skip_pre_1_0: false,
+            semver_check_enforce: None,
             changelog_update: true,
+            changelog_link_type: ChangelogLinkType::default(),
             release: true,
             publish: true,
             features_always_increment_minor: false,
             git_only: None,
+            previous_tag_patterns: vec![],
+            git_only_reachable_tags_only: false,
+            compare_ignore_files: vec![],
+            features: vec![],
+            all_features: false,
+            changelog_protect_manual_edits: false,
+            changelog_max_entries: None,
+            changelog_archive: false,
             tag_name_template: None,
+            tag_prefix: String::new(),
             changelog_path: None,
             custom_minor_increment_regex: None,
             custom_major_increment_regex: None,
+            version_metadata_template: None,
         }
     }
 }
@@ -117,6 +238,34 @@ impl UpdateConfig {
         Self { publish, ..self }
     }
 
+    pub fn with_tag_prefix(self, tag_prefix: String) -> Self {
+        Self { tag_prefix, ..self }
+    }
+
+    pub fn with_features(self, features: Vec<String>) -> Self {
+        Self { features, ..self }
+    }
+
+    pub fn with_all_features(self, all_features: bool) -> Self {
+        Self {
+            all_features,
+            ..self
+        }
+    }
+
+    /// Cargo arguments to enable the configured features.
+    pub fn features_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
+        args
+    }
+
     pub fn version_updater(&self) -> Result<VersionUpdater, regex::Error> {
         let mut updater = VersionUpdater::default()
             .with_features_always_increment_minor(self.features_always_increment_minor);
@@ -133,7 +282,6 @@ impl UpdateConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cargo_metadata::semver::Version;
 
     #[test]
     fn version_updater_with_custom_minor_regex() {
@@ -169,6 +317,22 @@ mod tests {
         assert_eq!(new_version, Version::new(1, 2, 4));
     }
 
+    #[test]
+    fn default_features_pass_no_args() {
+        assert!(UpdateConfig::default().features_args().is_empty());
+    }
+
+    #[test]
+    fn features_are_passed_as_args() {
+        let config = UpdateConfig::default()
+            .with_features(vec!["a".to_string(), "b".to_string()])
+            .with_all_features(true);
+        assert_eq!(
+            config.features_args(),
+            ["--features", "a,b", "--all-features"]
+        );
+    }
+
     #[test]
     fn version_updater_with_custom_major_regex() {
         let config = UpdateConfig {
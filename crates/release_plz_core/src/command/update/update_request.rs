@@ -1,5 +1,6 @@
 use std::{
     collections::{BTreeMap, HashSet},
+    num::NonZeroUsize,
     path::Path,
 };
 
@@ -10,12 +11,44 @@ use cargo_metadata::{
 };
 use regex::Regex;
 
-use crate::{ChangelogRequest, GitClient, GitForge, PackagePath as _, RepoUrl, fs_utils};
+use crate::{
+    ChangelogRequest, GitClient, GitForge, PackagePath as _, RepoUrl, fs_utils,
+    tera::single_tag_name_template,
+};
 
 use super::update_config::{PackageUpdateConfig, UpdateConfig};
 
 pub const DEFAULT_MAX_ANALYZE_COMMITS: u32 = 1000;
 
+/// Dependencies that release-plz updates in Cargo.lock.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DependenciesUpdate {
+    /// Update the workspace packages by running `cargo update --workspace`.
+    #[default]
+    Workspace,
+    /// Update all the dependencies by running `cargo update`.
+    All,
+    /// Update the given packages by running `cargo update -p <package>...`.
+    Packages(Vec<String>),
+}
+
+impl DependenciesUpdate {
+    /// Arguments of the `cargo update` command.
+    pub fn cargo_update_args(&self) -> Vec<&str> {
+        let mut args = vec!["update"];
+        match self {
+            Self::Workspace => args.push("--workspace"),
+            Self::All => {}
+            Self::Packages(packages) => {
+                for package in packages {
+                    args.extend(["-p", package]);
+                }
+            }
+        }
+        args
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UpdateRequest {
     /// The manifest of the project you want to update.
@@ -32,9 +65,10 @@ pub struct UpdateRequest {
     /// The registry name needs to be present in the Cargo config.
     /// If unspecified, crates.io is used.
     registry: Option<String>,
-    /// - If true, update all the dependencies in Cargo.lock by running `cargo update`.
-    /// - If false, updates the workspace packages in Cargo.lock by running `cargo update --workspace`.
-    dependencies_update: bool,
+    /// Dependencies to update in Cargo.lock.
+    dependencies_update: DependenciesUpdate,
+    /// If false, don't run `cargo update` after updating the manifests.
+    update_cargo_lock: bool,
     /// Allow dirty working directories to be updated.
     /// The uncommitted changes will be part of the update.
     allow_dirty: bool,
@@ -48,6 +82,31 @@ pub struct UpdateRequest {
     release_commits: Option<Regex>,
     git: Option<GitForge>,
     max_analyze_commits: Option<u32>,
+    /// If true, a single git tag covers the release of all the packages.
+    single_tag: bool,
+    /// Number of threads used to process the packages in parallel.
+    /// If unspecified, the number of CPUs is used.
+    jobs: Option<NonZeroUsize>,
+    /// Tera template of the message of the commit added to the changelog
+    /// when only the local dependencies of a package changed.
+    /// If unspecified, `chore: updated the following local packages: {{ packages }}` is used.
+    dependency_update_message_template: Option<String>,
+    /// File names recognized as changelogs, used when the changelog path of a package
+    /// isn't configured.
+    changelog_filenames: Vec<String>,
+    /// Command run on the manifests edited by release-plz, e.g. `["taplo", "fmt"]`.
+    /// The paths of the edited manifests are appended to the command.
+    /// If empty, the manifests aren't formatted.
+    format_manifests_command: Vec<String>,
+    /// If true, packages that aren't in the registry and don't have a git tag
+    /// use the commit that added their current version to the `Cargo.lock` as baseline.
+    infer_baseline_from_lock: bool,
+    /// If true, commits with more than one parent are ignored when analyzing the
+    /// history of the packages.
+    changelog_exclude_merge_commits: bool,
+    /// If false, don't query the git forge to find the usernames and the PRs
+    /// of the commits in the changelog.
+    fetch_contributors: bool,
 }
 
 impl UpdateRequest {
@@ -61,28 +120,43 @@ impl UpdateRequest {
             single_package: None,
             changelog_req: ChangelogRequest::default(),
             registry: None,
-            dependencies_update: false,
+            dependencies_update: DependenciesUpdate::default(),
+            update_cargo_lock: true,
             allow_dirty: false,
             repo_url: None,
             packages_config: PackagesConfig::default(),
             release_commits: None,
             git: None,
             max_analyze_commits: None,
+            jobs: None,
+            single_tag: false,
+            dependency_update_message_template: None,
+            changelog_filenames: crate::DEFAULT_CHANGELOG_FILENAMES
+                .iter()
+                .map(|f| (*f).to_string())
+                .collect(),
+            format_manifests_command: vec![],
+            infer_baseline_from_lock: false,
+            changelog_exclude_merge_commits: false,
+            fetch_contributors: true,
         })
     }
 
     pub fn changelog_path(&self, package: &Package) -> Utf8PathBuf {
         let config = self.get_package_config(&package.name);
-        config
+        let changelog_path = config
             .generic
             .changelog_path
             .map(|p| self.local_manifest.parent().unwrap().join(p))
             .unwrap_or_else(|| {
-                package
+                let package_path = package
                     .package_path()
-                    .expect("can't determine package path")
-                    .join(crate::CHANGELOG_FILENAME)
-            })
+                    .expect("can't determine package path");
+                crate::find_changelog(package_path, &self.changelog_filenames)
+            });
+        self.changelog_req
+            .output_format
+            .changelog_path(changelog_path)
     }
 
     pub fn git_client(&self) -> anyhow::Result<Option<GitClient>> {
@@ -122,6 +196,82 @@ impl UpdateRequest {
         }
     }
 
+    pub fn with_single_tag(self, single_tag: bool) -> Self {
+        Self { single_tag, ..self }
+    }
+
+    pub fn with_dependency_update_message_template(self, template: Option<String>) -> Self {
+        Self {
+            dependency_update_message_template: template,
+            ..self
+        }
+    }
+
+    pub fn dependency_update_message_template(&self) -> Option<&str> {
+        self.dependency_update_message_template.as_deref()
+    }
+
+    /// Set the file names recognized as changelogs, in order of preference.
+    /// They are used when the changelog path of a package isn't configured.
+    pub fn with_changelog_filenames(self, changelog_filenames: Vec<String>) -> Self {
+        Self {
+            changelog_filenames,
+            ..self
+        }
+    }
+
+    pub fn with_format_manifests_command(self, format_manifests_command: Vec<String>) -> Self {
+        Self {
+            format_manifests_command,
+            ..self
+        }
+    }
+
+    pub fn format_manifests_command(&self) -> &[String] {
+        &self.format_manifests_command
+    }
+
+    pub fn with_infer_baseline_from_lock(self, infer_baseline_from_lock: bool) -> Self {
+        Self {
+            infer_baseline_from_lock,
+            ..self
+        }
+    }
+
+    pub fn infer_baseline_from_lock(&self) -> bool {
+        self.infer_baseline_from_lock
+    }
+
+    pub fn with_changelog_exclude_merge_commits(self, exclude_merge_commits: bool) -> Self {
+        Self {
+            changelog_exclude_merge_commits: exclude_merge_commits,
+            ..self
+        }
+    }
+
+    pub fn changelog_exclude_merge_commits(&self) -> bool {
+        self.changelog_exclude_merge_commits
+    }
+
+    pub fn with_fetch_contributors(self, fetch_contributors: bool) -> Self {
+        Self {
+            fetch_contributors,
+            ..self
+        }
+    }
+
+    pub fn fetch_contributors(&self) -> bool {
+        self.fetch_contributors
+    }
+
+    pub fn with_jobs(self, jobs: Option<NonZeroUsize>) -> Self {
+        Self { jobs, ..self }
+    }
+
+    pub fn jobs(&self) -> Option<NonZeroUsize> {
+        self.jobs
+    }
+
     pub fn with_registry_manifest_path(self, registry_manifest: &Utf8Path) -> anyhow::Result<Self> {
         let registry_manifest = fs_utils::canonicalize_utf8(registry_manifest)?;
         Ok(Self {
@@ -205,15 +355,26 @@ impl UpdateRequest {
         self.registry_manifest.as_deref()
     }
 
-    pub fn with_dependencies_update(self, dependencies_update: bool) -> Self {
+    pub fn with_dependencies_update(self, dependencies_update: DependenciesUpdate) -> Self {
         Self {
             dependencies_update,
             ..self
         }
     }
 
-    pub fn should_update_dependencies(&self) -> bool {
-        self.dependencies_update
+    pub fn dependencies_update(&self) -> &DependenciesUpdate {
+        &self.dependencies_update
+    }
+
+    pub fn with_update_cargo_lock(self, update_cargo_lock: bool) -> Self {
+        Self {
+            update_cargo_lock,
+            ..self
+        }
+    }
+
+    pub fn should_update_cargo_lock(&self) -> bool {
+        self.update_cargo_lock
     }
 
     pub fn with_allow_dirty(self, allow_dirty: bool) -> Self {
@@ -255,7 +416,11 @@ impl UpdateRequest {
 
     /// Get the release tag name template for a specific package.
     /// Package-level config overrides workspace-level config.
+    /// If `single_tag` is enabled, all packages share the same tag.
     pub fn get_package_tag_name(&self, package_name: &str) -> Option<String> {
+        if self.single_tag {
+            return Some(single_tag_name_template());
+        }
         let pkg_config = self.get_package_config(package_name);
         pkg_config.generic.tag_name_template.clone()
     }
@@ -290,3 +455,22 @@ impl PackagesConfig {
         self.overrides.keys().map(|s| s.as_str()).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_update_args_depend_on_dependencies_update() {
+        assert_eq!(
+            DependenciesUpdate::Workspace.cargo_update_args(),
+            ["update", "--workspace"]
+        );
+        assert_eq!(DependenciesUpdate::All.cargo_update_args(), ["update"]);
+        let packages = DependenciesUpdate::Packages(vec!["serde".to_string(), "tokio".to_string()]);
+        assert_eq!(
+            packages.cargo_update_args(),
+            ["update", "-p", "serde", "-p", "tokio"]
+        );
+    }
+}
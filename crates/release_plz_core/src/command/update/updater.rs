@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    num::NonZeroUsize,
     path::Path,
 };
 
@@ -8,13 +9,10 @@ use cargo::util::VersionExt as _;
 use cargo_metadata::{
     Package, TargetKind,
     camino::{Utf8Path, Utf8PathBuf},
-    semver::Version,
+    semver::{BuildMetadata, Version},
 };
 use cargo_utils::{CARGO_TOML, LocalManifest};
-use git_cliff_core::{
-    config::{ChangelogConfig, Config},
-    contributor::RemoteContributor,
-};
+use git_cliff_core::{config::GitConfig, contributor::RemoteContributor};
 use git_cmd::Repo;
 use next_version::NextVersion as _;
 use rayon::iter::{IntoParallelRefMutIterator as _, ParallelIterator as _};
@@ -22,15 +20,18 @@ use std::sync::Once;
 use tracing::{debug, info, instrument, warn};
 
 use crate::{
-    ChangelogBuilder, ChangelogRequest, NO_COMMIT_ID, PackagePath as _, Project, Remote, RepoUrl,
+    ChangelogBuilder, ChangelogLinkType, ChangelogOutputFormat, ChangelogRequest, NO_COMMIT_ID,
+    PackagePath as _, PackageUpdateConfig, Project, Remote, RepoUrl, SemverCheckEnforce,
     UpdateResult,
+    changelog::last_version_from_json,
     changelog_filler::{fill_commit, get_required_info},
     changelog_parser,
     command::update::changelog_update::OldChangelogs,
     diff::{Commit, Diff},
     fs_utils, lock_compare,
     registry_packages::{PackagesCollection, RegistryPackage},
-    semver_check::{self, SemverCheck},
+    semver_check::{self, ReleaseType, SemverCheck},
+    tera::{PACKAGES_VAR, render_template, version_metadata},
     toml_compare,
     version::NextVersionFromDiff as _,
 };
@@ -61,8 +62,12 @@ impl Updater<'_> {
         let packages_diffs = self
             .get_packages_diffs(registry_packages, repository)
             .await?;
+        if let Some(summary) = unpublished_versions_summary(&packages_diffs) {
+            warn!("{summary}");
+        }
         let version_groups = self.get_version_groups(&packages_diffs)?;
         debug!("version groups: {:?}", version_groups);
+        let head_commit = repository.current_commit_hash()?;
 
         let mut packages_to_check_for_deps: Vec<&Package> = vec![];
         let mut packages_to_update = PackagesUpdate::default();
@@ -103,6 +108,7 @@ impl Updater<'_> {
                 &version_groups,
                 &diff,
             )?;
+            let next_version = self.with_version_metadata(p, next_version, &head_commit)?;
             debug!(
                 "package: {}, diff: {diff:?}, next_version: {next_version}",
                 p.name,
@@ -152,8 +158,11 @@ impl Updater<'_> {
             .iter()
             .map(|(p, u)| (p, u.version.clone()))
             .collect();
-        let dependent_packages =
-            self.dependent_packages_update(&packages_to_check_for_deps, &changed_packages)?;
+        let dependent_packages = self.dependent_packages_update(
+            &packages_to_check_for_deps,
+            &changed_packages,
+            &head_commit,
+        )?;
         packages_to_update.updates_mut().extend(dependent_packages);
         Ok(packages_to_update)
     }
@@ -167,9 +176,8 @@ impl Updater<'_> {
 
         for (pkg, diff) in packages_diffs {
             let pkg_config = self.req.get_package_config(&pkg.name);
-            let version_updater = pkg_config.generic.version_updater()?;
-            if let Some(version_group) = pkg_config.version_group {
-                let next_pkg_ver = pkg.version.next_from_diff(diff, version_updater);
+            if let Some(version_group) = pkg_config.version_group.clone() {
+                let next_pkg_ver = next_package_version(pkg, diff, &pkg_config)?;
                 match version_groups.entry(version_group.clone()) {
                     std::collections::hash_map::Entry::Occupied(v) => {
                         // maximum version of the group until now
@@ -243,7 +251,8 @@ impl Updater<'_> {
             .map(|(p, d)| (p.name.to_string(), d.commits.clone()))
             .collect();
 
-        let semver_check_result: anyhow::Result<()> =
+        let thread_pool = thread_pool(self.req.jobs())?;
+        let semver_check_result: anyhow::Result<()> = thread_pool.install(|| {
             packages_diffs.par_iter_mut().try_for_each(|(p, diff)| {
                 let registry_package = registry_packages.get_package(&p.name);
                 if let Some(registry_package) = registry_package {
@@ -255,7 +264,21 @@ impl Updater<'_> {
                             diff.add_commits(commits);
                         }
                     }
-                    if should_check_semver(p, package_config.semver_check())
+                    let skip_semver_check = diff.only_contains_commit_types(
+                        &package_config.generic.semver_check_skip_if_only,
+                    );
+                    if skip_semver_check {
+                        debug!(
+                            "{}: skipping semver check because commits only contain the types {:?}",
+                            p.name, package_config.generic.semver_check_skip_if_only
+                        );
+                    }
+                    if !skip_semver_check
+                        && should_check_semver(
+                            p,
+                            package_config.semver_check(),
+                            package_config.generic.semver_check_skip_pre_1_0,
+                        )
                         && diff.should_update_version()
                     {
                         let registry_package_path = registry_package
@@ -267,14 +290,36 @@ impl Updater<'_> {
                                 "Checking API compatibility with cargo-semver-checks..."
                             );
                         });
-                        let semver_check =
-                            semver_check::run_semver_check(&package_path, registry_package_path)
-                                .context("error while running cargo-semver-checks")?;
+                        let enforce = package_config.generic.semver_check_enforce;
+                        let planned_version = enforce
+                            .map(|_| next_package_version(p, diff, &package_config))
+                            .transpose()?;
+                        let release_type = planned_version
+                            .as_ref()
+                            .map(|next| ReleaseType::from_versions(&p.version, next));
+                        let semver_check = semver_check::run_semver_check(
+                            &package_path,
+                            registry_package_path,
+                            &package_config.generic.features_args(),
+                            release_type,
+                        )
+                        .context("error while running cargo-semver-checks")?;
+                        if let (Some(SemverCheckEnforce::Fail), Some(planned_version)) =
+                            (enforce, &planned_version)
+                        {
+                            check_planned_version(
+                                &p.name,
+                                &p.version,
+                                planned_version,
+                                &semver_check,
+                            )?;
+                        }
                         diff.set_semver_check(semver_check);
                     }
                 }
                 Ok(())
-            });
+            })
+        });
         semver_check_result?;
 
         Ok(packages_diffs)
@@ -311,7 +356,10 @@ impl Updater<'_> {
         let mut all_commits: HashMap<String, &Commit> = HashMap::new();
         let mut packages_diffs = packages_diffs.to_owned();
         if let Some(changelog_config) = changelog_request.changelog_config.as_ref() {
-            let required_info = get_required_info(&changelog_config.changelog);
+            let mut required_info = get_required_info(&changelog_config.changelog);
+            if !self.req.fetch_contributors() {
+                required_info.skip_remote();
+            }
             for (_package, diff) in &mut packages_diffs {
                 for commit in &mut diff.commits {
                     fill_commit(
@@ -340,10 +388,12 @@ impl Updater<'_> {
     ///   If they don't depend on any of the `changed_packages`, they are not updated
     ///   because they don't contain any new commits.
     /// - `initial_changed_packages`: The packages that have changed (i.e. contains commits).
+    /// - `head_commit`: Hash of the current commit, used to render the version metadata.
     fn dependent_packages_update(
         &self,
         packages_to_check_for_deps: &[&Package],
         initial_changed_packages: &[(&Package, Version)],
+        head_commit: &str,
     ) -> anyhow::Result<PackagesToUpdate> {
         let workspace_manifest = LocalManifest::try_new(self.req.local_manifest())?;
         let workspace_dependencies = workspace_manifest.get_workspace_dependency_table();
@@ -371,6 +421,16 @@ impl Updater<'_> {
                 if processed.contains(p.name.as_ref()) {
                     continue;
                 }
+                // Skip packages that don't want to be released only because of
+                // their dependencies. Their manifests are still updated to depend
+                // on the new versions of the changed packages.
+                if !self
+                    .req
+                    .get_package_config(&p.name)
+                    .release_on_dependency_change
+                {
+                    continue;
+                }
 
                 // Check if this package depends on any changed package
                 if let Ok(deps) = p.dependencies_to_update(
@@ -380,8 +440,12 @@ impl Updater<'_> {
                 ) && !deps.is_empty()
                 {
                     // This package depends on changed packages, so it needs to be updated
-                    let update =
-                        self.calculate_package_update_result(&deps, p, &mut old_changelogs)?;
+                    let update = self.calculate_package_update_result(
+                        &deps,
+                        p,
+                        head_commit,
+                        &mut old_changelogs,
+                    )?;
 
                     result.push(update.clone());
 
@@ -405,14 +469,16 @@ impl Updater<'_> {
         &self,
         deps: &[&Package],
         p: &Package,
+        head_commit: &str,
         old_changelogs: &mut OldChangelogs,
     ) -> anyhow::Result<(Package, UpdateResult)> {
         let deps: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
         let commits = {
-            let change = format!(
-                "chore: updated the following local packages: {}",
-                deps.join(", ")
-            );
+            let change =
+                dependency_update_message(self.req.dependency_update_message_template(), &deps)
+                    .with_context(|| {
+                        format!("cannot render dependency update message of {}", p.name)
+                    })?;
             vec![Commit::new(NO_COMMIT_ID.to_string(), change)]
         };
         let next_version = if p.version.is_prerelease() {
@@ -420,6 +486,7 @@ impl Updater<'_> {
         } else {
             p.version.increment_patch()
         };
+        let next_version = self.with_version_metadata(p, next_version, head_commit)?;
         info!(
             "{}: dependencies changed. Next version is {next_version}",
             p.name
@@ -471,6 +538,7 @@ impl Updater<'_> {
         registry_version: Option<Version>,
         old_changelog: Option<&str>,
     ) -> anyhow::Result<UpdateResult> {
+        let commit_summaries = commits.iter().map(Commit::summary).collect();
         let repo_url = self.req.repo_url();
         let release_link = {
             // Use registry_version for prev_tag when available (version already bumped case),
@@ -481,14 +549,29 @@ impl Updater<'_> {
                 .to_string();
             let prev_tag = self.project.git_tag(&package.name, &prev_version)?;
             let next_tag = self.project.git_tag(&package.name, &version.to_string())?;
-            repo_url.map(|r| r.git_release_link(&prev_tag, &next_tag))
+            let link_type = self
+                .req
+                .get_package_config(package.name.as_str())
+                .generic
+                .changelog_link_type;
+            repo_url.and_then(|r| release_link(r, link_type, &prev_tag, &next_tag))
         };
 
         let changelog_outcome = {
             let cfg = self.req.get_package_config(package.name.as_str());
-            let changelog_req = cfg
-                .should_update_changelog()
-                .then_some(self.req.changelog_req().clone());
+            let changelog_req = cfg.should_update_changelog().then(|| ChangelogRequest {
+                protect_manual_edits: cfg.generic.changelog_protect_manual_edits,
+                ..self.req.changelog_req().clone()
+            });
+            let require_conventional = self
+                .req
+                .changelog_req()
+                .changelog_config
+                .as_ref()
+                .is_some_and(|c| c.git.require_conventional);
+            if require_conventional {
+                ensure_conventional_commits(package.name.as_str(), &commits)?;
+            }
             let commits: Vec<Commit> = commits
                 .into_iter()
                 // If not conventional commit, only consider the first line of the commit message.
@@ -526,9 +609,11 @@ impl Updater<'_> {
         Ok(UpdateResult {
             version,
             changelog,
+            changelog_output_format: self.req.changelog_req().output_format,
             semver_check,
             new_changelog_entry,
             registry_version,
+            commits: commit_summaries,
         })
     }
 
@@ -593,21 +678,101 @@ impl Updater<'_> {
                 );
             }
         }
-        self.get_package_diff(
+        let lock_baseline_commit = if registry_package.is_none()
+            && tag_commit.is_none()
+            && self.req.infer_baseline_from_lock()
+        {
+            self.lock_baseline_commit(package, repository)?
+        } else {
+            None
+        };
+        let release_commit = self.get_package_diff(
             &package_path,
             package,
             registry_package,
             repository,
             tag_commit.as_deref(),
+            lock_baseline_commit.as_deref(),
             &mut diff,
         )?;
 
         repository
             .checkout_head()
             .context("can't checkout to head after calculating diff")?;
+        self.add_commits_at_include_paths(
+            package,
+            repository,
+            release_commit.as_deref(),
+            &mut diff,
+        )
+        .context("can't retrieve the commits of `changelog_include_paths`")?;
         Ok(diff)
     }
 
+    /// Add the commits that changed the `changelog_include_paths` of the package
+    /// after `release_commit`.
+    fn add_commits_at_include_paths(
+        &self,
+        package: &Package,
+        repository: &Repo,
+        release_commit: Option<&str>,
+        diff: &mut Diff,
+    ) -> anyhow::Result<()> {
+        let include_paths = self
+            .req
+            .get_package_config(&package.name)
+            .changelog_include_paths;
+        if include_paths.is_empty() {
+            return Ok(());
+        }
+        // If the package was never released, limit the analyzed commits like in `get_package_diff`.
+        let max_count = match (release_commit, self.req.max_analyze_commits()) {
+            (None, n) if n > 0 => n,
+            _ => u32::MAX,
+        };
+        let mut commits: Vec<Commit> = vec![];
+        for (hash, message) in
+            repository.commits_at_globs(release_commit, max_count, &include_paths)?
+        {
+            if diff.commits.iter().any(|c| c.id == hash)
+                || self.is_excluded_merge_commit(repository, &hash)?
+            {
+                continue;
+            }
+            commits.push(Commit::new(hash, message));
+        }
+        debug!(
+            "{}: adding {} commits of paths {include_paths:?}",
+            package.name,
+            commits.len()
+        );
+        diff.add_commits(&commits);
+        Ok(())
+    }
+
+    /// Commit that added the current version of the package to the `Cargo.lock`.
+    /// It's used as baseline for packages that were never released.
+    fn lock_baseline_commit(
+        &self,
+        package: &Package,
+        repository: &Repo,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(cargo_lock) = self.get_cargo_lock_path(repository)? else {
+            return Ok(None);
+        };
+        let baseline = commit_adding_lock_entry(repository, &cargo_lock, package)?;
+        if let Some(baseline) = &baseline {
+            info!(
+                "{}: version {} not found in the registry or in git tags. Using commit {baseline}, which added it to the Cargo.lock, as baseline",
+                package.name, package.version
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Fill the diff with the commits of the package.
+    /// Returns the commit where the package was released, if found.
+    #[allow(clippy::too_many_arguments)]
     fn get_package_diff(
         &self,
         package_path: &Utf8Path,
@@ -615,8 +780,9 @@ impl Updater<'_> {
         registry_package: Option<&RegistryPackage>,
         repository: &Repo,
         tag_commit: Option<&str>,
+        lock_baseline_commit: Option<&str>,
         diff: &mut Diff,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Option<String>> {
         let pathbufs_to_check = pathbufs_to_check(package_path, package)?;
         let paths_to_check: Vec<&Path> = pathbufs_to_check.iter().map(|p| p.as_ref()).collect();
         let max_analyze_commits = if registry_package.is_none() {
@@ -635,7 +801,12 @@ impl Updater<'_> {
             // Check if files changed in git commit belong to the current package.
             // This is required because a package can contain another package in a subdirectory.
             let are_changed_files_in_pkg = || {
-                self.are_changed_files_in_package(package_path, repository, &current_commit_hash)
+                self.are_changed_files_in_package(
+                    package,
+                    package_path,
+                    repository,
+                    &current_commit_hash,
+                )
             };
 
             if let Some(registry_package) = registry_package {
@@ -678,11 +849,14 @@ impl Updater<'_> {
                     // the package was published at this commit, so we will not count this commit
                     // as part of the release.
                     // We can process the next package.
-                    break;
+                    return Ok(Some(current_commit_hash));
                 } else {
                     // When version is already bumped, we still collect commits to update the changelog,
                     // but mark that version should not be bumped further.
-                    if package.version > registry_package.package.version
+                    if package
+                        .version
+                        .cmp_precedence(&registry_package.package.version)
+                        .is_gt()
                         && diff.is_version_published
                     {
                         info!(
@@ -691,7 +865,9 @@ impl Updater<'_> {
                         );
                         diff.set_version_unpublished(registry_package.package.version.clone());
                     }
-                    if are_changed_files_in_pkg()? {
+                    if are_changed_files_in_pkg()?
+                        && !self.is_excluded_merge_commit(repository, &current_commit_hash)?
+                    {
                         debug!("packages contain different files");
                         // At this point of the git history, the two packages are different,
                         // which means that this commit is not present in the published package.
@@ -701,7 +877,16 @@ impl Updater<'_> {
                         ));
                     }
                 }
-            } else if are_changed_files_in_pkg()? {
+            } else if let Some(baseline) = lock_baseline_commit
+                && repository.is_ancestor(&current_commit_hash, baseline)
+            {
+                debug!(
+                    "stopping looking at git history because the current commit ({current_commit_hash}) is an ancestor of the commit ({baseline}) that added the package version to the Cargo.lock"
+                );
+                return Ok(Some(current_commit_hash));
+            } else if are_changed_files_in_pkg()?
+                && !self.is_excluded_merge_commit(repository, &current_commit_hash)?
+            {
                 diff.commits.push(Commit::new(
                     current_commit_hash,
                     current_commit_message.clone(),
@@ -715,7 +900,23 @@ impl Updater<'_> {
                 break;
             }
         }
-        Ok(())
+        Ok(None)
+    }
+
+    /// Merge commits are excluded if `changelog_exclude_merge_commits` is enabled.
+    fn is_excluded_merge_commit(
+        &self,
+        repository: &Repo,
+        commit_hash: &str,
+    ) -> anyhow::Result<bool> {
+        if !self.req.changelog_exclude_merge_commits() {
+            return Ok(false);
+        }
+        let is_merge_commit = repository.is_merge_commit(commit_hash)?;
+        if is_merge_commit {
+            debug!("skipping merge commit {commit_hash}");
+        }
+        Ok(is_merge_commit)
     }
 
     fn check_package_equality(
@@ -725,7 +926,15 @@ impl Updater<'_> {
         package_path: &Utf8Path,
         registry_package_path: &Utf8Path,
     ) -> anyhow::Result<bool> {
-        if crate::is_readme_updated(&package.name, package_path, registry_package_path)? {
+        let package_config = self.req.get_package_config(&package.name).generic;
+        let ignored_files = crate::compare_ignore_files(&package_config.compare_ignore_files)
+            .context("invalid `compare_ignore_files`")?;
+        if crate::is_readme_updated(
+            &package.name,
+            package_path,
+            registry_package_path,
+            &ignored_files,
+        )? {
             debug!("{}: README updated", package.name);
             return Ok(false);
         }
@@ -734,8 +943,13 @@ impl Updater<'_> {
         let cargo_lock_path = self
             .get_cargo_lock_path(repository)
             .context("failed to determine Cargo.lock path")?;
-        let are_packages_equal = crate::are_packages_equal(package_path, registry_package_path)
-            .context("cannot compare packages")?;
+        let are_packages_equal = crate::are_packages_equal(
+            package_path,
+            registry_package_path,
+            &ignored_files,
+            &package_config.features_args(),
+        )
+        .context("cannot compare packages")?;
         if let Some(cargo_lock_path) = cargo_lock_path.as_deref() {
             // Revert any changes to `Cargo.lock`
             repository
@@ -819,17 +1033,52 @@ impl Updater<'_> {
                         })?
                         .clone()
                 } else {
-                    let version_updater = pkg_config.generic.version_updater()?;
-                    p.version.next_from_diff(diff, version_updater)
+                    next_package_version(p, diff, &pkg_config)?
                 }
             }
         };
         Ok(next_version)
     }
 
+    /// Append the build metadata rendered from `version_metadata_template` to `next_version`.
+    /// If the version isn't bumped, the metadata of the current version is kept.
+    fn with_version_metadata(
+        &self,
+        package: &Package,
+        mut next_version: Version,
+        head_commit: &str,
+    ) -> anyhow::Result<Version> {
+        let pkg_config = self.req.get_package_config(&package.name);
+        let Some(template) = pkg_config.generic.version_metadata_template.as_deref() else {
+            return Ok(next_version);
+        };
+        // Build metadata is ignored when comparing versions.
+        if next_version.cmp_precedence(&package.version).is_eq() {
+            return Ok(next_version);
+        }
+        if publishes_to_crates_io(package, self.req.registry()) {
+            warn!(
+                "{}: `version_metadata_template` is set, but crates.io rejects versions with build metadata. Use it only with private registries",
+                package.name
+            );
+        }
+        next_version.build = BuildMetadata::EMPTY;
+        let date = chrono::Utc::now().format("%Y%m%d").to_string();
+        next_version.build = version_metadata(
+            template,
+            &package.name,
+            &next_version.to_string(),
+            &date,
+            head_commit,
+        )
+        .with_context(|| format!("failed to render the version metadata of {}", package.name))?;
+        Ok(next_version)
+    }
+
     /// `hash` is only used for logging purposes.
     fn are_changed_files_in_package(
         &self,
+        package: &Package,
         package_path: &Utf8Path,
         repository: &Repo,
         hash: &str,
@@ -839,7 +1088,12 @@ impl Updater<'_> {
         let cargo_lock_path = self
             .get_cargo_lock_path(repository)
             .context("failed to determine Cargo.lock path")?;
-        let package_files_res = get_package_files(package_path, repository);
+        let features_args = self
+            .req
+            .get_package_config(&package.name)
+            .generic
+            .features_args();
+        let package_files_res = get_package_files(package_path, repository, &features_args);
         if let Some(cargo_lock_path) = cargo_lock_path.as_deref() {
             // Revert any changes to `Cargo.lock`
             repository
@@ -858,13 +1112,124 @@ impl Updater<'_> {
             // Assume that this commit contains changes to the package.
             return Ok(true);
         };
-        Ok(!package_files.is_disjoint(&changed_files))
+        let git_config = self
+            .req
+            .changelog_req()
+            .changelog_config
+            .as_ref()
+            .map(|c| &c.git);
+        Ok(changed_files
+            .iter()
+            .filter(|file| git_config.is_none_or(|git_config| is_path_included(file, git_config)))
+            .any(|file| package_files.contains(file)))
+    }
+}
+
+/// Next version of the package, based on its diff, its [`PackageUpdateConfig::force_bump`]
+/// and its [`PackageUpdateConfig::min_version`].
+fn next_package_version(
+    package: &Package,
+    diff: &Diff,
+    pkg_config: &PackageUpdateConfig,
+) -> anyhow::Result<Version> {
+    let next_version = match pkg_config.force_bump {
+        // Like the increment derived from the commits, the forced bump
+        // applies only if the package needs to be released.
+        Some(force_bump) if diff.should_update_version() => force_bump.bump(&package.version),
+        _ => {
+            let version_updater = pkg_config.generic.version_updater()?;
+            package.version.next_from_diff(diff, version_updater)
+        }
+    };
+    match &pkg_config.min_version {
+        Some(min_version) if diff.should_update_version() && next_version < *min_version => {
+            debug!(
+                "next version of {} ({next_version}) is lower than `min_version`: using {min_version}",
+                package.name
+            );
+            Ok(min_version.clone())
+        }
+        _ => Ok(next_version),
+    }
+}
+
+/// Fail if cargo-semver-checks found API changes that require a bigger bump than the
+/// planned version.
+fn check_planned_version(
+    package_name: &str,
+    current_version: &Version,
+    planned_version: &Version,
+    semver_check: &SemverCheck,
+) -> anyhow::Result<()> {
+    let planned_release_type = ReleaseType::from_versions(current_version, planned_version);
+    let (required, changes) = match semver_check {
+        SemverCheck::Incompatible(_) if planned_release_type == ReleaseType::Major => {
+            return Ok(());
+        }
+        SemverCheck::MinorRequired(_) if planned_release_type != ReleaseType::Patch => {
+            return Ok(());
+        }
+        SemverCheck::Incompatible(changes) => ("a breaking", changes),
+        SemverCheck::MinorRequired(changes) => ("a minor", changes),
+        SemverCheck::Compatible | SemverCheck::Skipped => return Ok(()),
+    };
+    anyhow::bail!(
+        "the API changes of package `{package_name}` require {required} version bump, but the planned version is {planned_version}. Fix the commit messages or set `semver_check_enforce = \"bump\"`.\n{changes}"
+    )
+}
+
+/// Check if `path` passes the `include_paths` and `exclude_paths` filters of the
+/// git-cliff configuration.
+/// `path` is relative to the repository root.
+fn is_path_included(path: &Utf8Path, git_config: &GitConfig) -> bool {
+    let included = git_config.include_paths.is_empty()
+        || git_config
+            .include_paths
+            .iter()
+            .any(|p| p.matches(path.as_str()));
+    let excluded = git_config
+        .exclude_paths
+        .iter()
+        .any(|p| p.matches(path.as_str()));
+    included && !excluded
+}
+
+/// Message of the commit added to the changelog when only the local dependencies
+/// of a package changed.
+fn dependency_update_message(template: Option<&str>, deps: &[&str]) -> anyhow::Result<String> {
+    let packages = deps.join(", ");
+    match template {
+        Some(template) => {
+            let mut context = tera::Context::new();
+            context.insert(PACKAGES_VAR, &packages);
+            render_template(template, &context, "dependency_update_message_template")
+        }
+        None => Ok(format!(
+            "chore: updated the following local packages: {packages}"
+        )),
+    }
+}
+
+/// Build the thread pool used for the parallel sections of the update.
+/// If `jobs` is `None`, the number of CPUs is used.
+fn thread_pool(jobs: Option<NonZeroUsize>) -> anyhow::Result<rayon::ThreadPool> {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(jobs) = jobs {
+        builder = builder.num_threads(jobs.get());
     }
+    builder.build().context("failed to build thread pool")
 }
 
 /// Check if release-plz should check the semver compatibility of the package.
 /// - `run_semver_check` is true if the user wants to run the semver check.
-fn should_check_semver(package: &Package, run_semver_check: bool) -> bool {
+fn should_check_semver(package: &Package, run_semver_check: bool, skip_pre_1_0: bool) -> bool {
+    if skip_pre_1_0 && package.version.major == 0 {
+        debug!(
+            "{}: skipping semver check because version {} is lower than 1.0.0",
+            package.name, package.version
+        );
+        return false;
+    }
     if run_semver_check && contains_library(package) {
         let is_cargo_semver_checks_installed = semver_check::is_cargo_semver_checks_installed();
         if !is_cargo_semver_checks_installed {
@@ -895,12 +1260,13 @@ fn contains_target_kind(package: &Package, target_kind: &TargetKind) -> bool {
 fn get_package_files(
     package_path: &Utf8Path,
     repository: &Repo,
+    features_args: &[String],
 ) -> anyhow::Result<HashSet<Utf8PathBuf>> {
     // Get relative path of the crate with respect to the repository because we need to compare
     // files with the git output.
     let repository_dir = repository.directory();
 
-    crate::get_cargo_package_files(package_path)?
+    crate::get_cargo_package_files(package_path, features_args)?
         .into_iter()
         // filter file generated by `cargo package` that isn't in git.
         .filter(|file| file != "Cargo.toml.orig" && file != ".cargo_vcs_info.json")
@@ -947,6 +1313,31 @@ fn is_commit_too_old(
     false
 }
 
+/// Most recent commit that added the entry of the current version of `package`
+/// to `cargo_lock`. I.e. the commit where the version appeared in the `Cargo.lock`.
+fn commit_adding_lock_entry(
+    repository: &Repo,
+    cargo_lock: &str,
+    package: &Package,
+) -> anyhow::Result<Option<String>> {
+    let lock_entry = format!(
+        "name = \"{}\"\nversion = \"{}\"\n",
+        package.name, package.version
+    );
+    let lock_content = fs_err::read_to_string(cargo_lock)?;
+    if !lock_content.contains(&lock_entry) {
+        return Ok(None);
+    }
+    // `-S` lists the commits that changed the number of occurrences of the entry.
+    // The entry exists in the current `Cargo.lock`, so the most recent of them added it.
+    let pickaxe = format!("-S{lock_entry}");
+    let output = repository
+        .git(&["log", "-n1", "--format=%H", &pickaxe, "--", cargo_lock])
+        .context("can't find the commit that added the package to the Cargo.lock")?;
+    let commit = output.trim();
+    Ok((!commit.is_empty()).then(|| commit.to_string()))
+}
+
 fn pathbufs_to_check(
     package_path: &Utf8Path,
     package: &Package,
@@ -958,6 +1349,68 @@ fn pathbufs_to_check(
     Ok(paths)
 }
 
+/// Fail if any of the commits doesn't follow the conventional commits specification.
+fn ensure_conventional_commits(package_name: &str, commits: &[Commit]) -> anyhow::Result<()> {
+    let unconventional: Vec<String> = commits
+        .iter()
+        .filter(|c| !c.is_conventional())
+        .map(|c| {
+            let summary = c.message.lines().next().unwrap_or_default();
+            format!("- {}: {summary}", c.id)
+        })
+        .collect();
+    anyhow::ensure!(
+        unconventional.is_empty(),
+        "package `{package_name}` contains commits that don't follow the conventional commits specification, but `require_conventional` is enabled:\n{}",
+        unconventional.join("\n")
+    );
+    Ok(())
+}
+
+/// Whether the package is published to crates.io.
+/// `registry` is the registry passed by the user, which overrides the `publish` field of the manifest.
+pub(crate) fn publishes_to_crates_io(package: &Package, registry: Option<&str>) -> bool {
+    match (registry, &package.publish) {
+        (Some(registry), _) => registry == "crates-io",
+        (None, None) => true,
+        (None, Some(registries)) => registries.iter().any(|r| r == "crates-io"),
+    }
+}
+
+/// Summary of the packages whose local version is greater than the registry version.
+/// Release-plz doesn't bump the version of these packages, so we explain why.
+fn unpublished_versions_summary(packages_diffs: &[(&Package, Diff)]) -> Option<String> {
+    let packages: Vec<String> = packages_diffs
+        .iter()
+        .filter_map(|(p, diff)| {
+            let registry_version = diff.registry_version.as_ref()?;
+            (!diff.is_version_published)
+                .then(|| format!("- {}: {} (registry: {registry_version})", p.name, p.version))
+        })
+        .collect();
+    if packages.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "the local version of these packages is greater than the one in the registry, so release-plz doesn't bump them:\n{}\nRun `release-plz release` to publish them.",
+        packages.join("\n")
+    ))
+}
+
+/// Link of the version heading of the changelog.
+fn release_link(
+    repo_url: &RepoUrl,
+    link_type: ChangelogLinkType,
+    prev_tag: &str,
+    next_tag: &str,
+) -> Option<String> {
+    match link_type {
+        ChangelogLinkType::Compare => Some(repo_url.git_release_link(prev_tag, next_tag)),
+        ChangelogLinkType::Tag => Some(repo_url.git_release_link(next_tag, next_tag)),
+        ChangelogLinkType::None => None,
+    }
+}
+
 /// Return the following tuple:
 /// - the entire changelog (with the new entries);
 /// - the new changelog entry alone
@@ -973,6 +1426,10 @@ fn get_changelog(
 ) -> anyhow::Result<(String, String)> {
     let commits: Vec<git_cliff_core::commit::Commit> =
         commits.iter().map(|c| c.to_cliff_commit()).collect();
+    let output_format = changelog_req
+        .as_ref()
+        .map(|r| r.output_format)
+        .unwrap_or_default();
     let mut changelog_builder = ChangelogBuilder::new(
         commits.clone(),
         next_version.to_string(),
@@ -985,6 +1442,7 @@ fn get_changelog(
         if let Some(config) = changelog_req.changelog_config {
             changelog_builder = changelog_builder.with_config(config);
         }
+        changelog_builder = changelog_builder.with_template_context(changelog_req.template_context);
         if let Some(link) = release_link {
             changelog_builder = changelog_builder.with_release_link(link);
         }
@@ -1002,11 +1460,27 @@ fn get_changelog(
         }
         let is_package_published = next_version != &package.version;
 
-        let last_version = old_changelog.and_then(|old_changelog| {
-            changelog_parser::last_version_from_str(old_changelog)
-                .ok()
-                .flatten()
+        let last_version = old_changelog.and_then(|old_changelog| match output_format {
+            ChangelogOutputFormat::Markdown => {
+                changelog_parser::last_version_from_str(old_changelog)
+                    .ok()
+                    .flatten()
+            }
+            ChangelogOutputFormat::Json => last_version_from_json(old_changelog),
         });
+        // Entries of JSON changelogs aren't meant to be edited manually.
+        if changelog_req.protect_manual_edits
+            && output_format == ChangelogOutputFormat::Markdown
+            && let Some(old_changelog) = old_changelog
+            && last_version.as_deref() == Some(next_version.to_string().as_str())
+        {
+            ensure_no_missing_changes(
+                old_changelog,
+                changelog_builder.clone(),
+                package,
+                next_version,
+            )?;
+        }
         if is_package_published {
             let last_version = last_version.unwrap_or(package.version.to_string());
             changelog_builder = changelog_builder.with_previous_version(last_version);
@@ -1023,31 +1497,63 @@ fn get_changelog(
         }
     }
     let new_changelog = changelog_builder.build();
-    let changelog = match old_changelog {
-        Some(old_changelog) => new_changelog.prepend(old_changelog)?,
-        None => new_changelog.generate()?, // Old changelog doesn't exist.
+    let changelog = match (old_changelog, output_format) {
+        (Some(old_changelog), ChangelogOutputFormat::Markdown) => {
+            new_changelog.prepend(old_changelog)?
+        }
+        (Some(old_changelog), ChangelogOutputFormat::Json) => {
+            new_changelog.prepend_json(old_changelog)?
+        }
+        // Old changelog doesn't exist.
+        (None, ChangelogOutputFormat::Markdown) => new_changelog.generate()?,
+        (None, ChangelogOutputFormat::Json) => new_changelog.generate_json()?,
     };
     let body_only =
         new_changelog_entry(changelog_builder).context("can't determine changelog body")?;
     Ok((changelog, body_only.unwrap_or_default()))
 }
 
+/// The changelog already contains an entry for `next_version`, so release-plz doesn't update it.
+/// Fail if the entry misses some changes that release-plz would add to it, because
+/// the entry was probably edited manually and the new changes would be lost.
+fn ensure_no_missing_changes(
+    old_changelog: &str,
+    changelog_builder: ChangelogBuilder,
+    package: &Package,
+    next_version: &Version,
+) -> anyhow::Result<()> {
+    let existing_entry =
+        changelog_parser::last_changes_from_str(old_changelog)?.unwrap_or_default();
+    let generated_entry = new_changelog_entry(changelog_builder)?.unwrap_or_default();
+    let missing_changes = missing_changes(&existing_entry, &generated_entry);
+    anyhow::ensure!(
+        missing_changes.is_empty(),
+        "the changelog entry of version {next_version} of package `{}` looks manually edited and doesn't contain the following changes:\n{}\n\nAdd them to the changelog manually, or remove the entry to let release-plz regenerate it. To ignore the new changes instead, disable `changelog_protect_manual_edits`.",
+        package.name,
+        missing_changes.join("\n")
+    );
+    Ok(())
+}
+
+/// List items of the generated changelog entry that aren't in the existing one.
+fn missing_changes<'a>(existing_entry: &str, generated_entry: &'a str) -> Vec<&'a str> {
+    let existing_lines: HashSet<&str> = existing_entry.lines().map(str::trim).collect();
+    generated_entry
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("- ") || line.starts_with("* "))
+        .filter(|line| !existing_lines.contains(line))
+        .collect()
+}
+
 fn new_changelog_entry(changelog_builder: ChangelogBuilder) -> anyhow::Result<Option<String>> {
     changelog_builder
         .config()
         .cloned()
         .map(|c| {
-            let new_config = Config {
-                changelog: ChangelogConfig {
-                    // If we set None, later this will be overriden with the defaults.
-                    // Instead we just want the body.
-                    header: Some(String::new()),
-                    footer: Some(String::new()),
-                    ..c.changelog
-                },
-                ..c
-            };
-            let changelog = changelog_builder.with_config(new_config).build();
+            let changelog = changelog_builder
+                .with_config(crate::changelog::entry_config(c))
+                .build();
             changelog.generate().map(|entry| entry.trim().to_string())
         })
         .transpose()
@@ -1087,8 +1593,210 @@ fn get_repo_path(
 
 #[cfg(test)]
 mod tests {
+    use crate::ForceBump;
+
     use super::*;
 
+    #[test]
+    fn breaking_changes_are_accepted_with_planned_breaking_bump() {
+        let breaking = SemverCheck::Incompatible("removed fn".to_string());
+        let check = |current: &str, planned: &str| {
+            check_planned_version(
+                "my_package",
+                &Version::parse(current).unwrap(),
+                &Version::parse(planned).unwrap(),
+                &breaking,
+            )
+        };
+        check("0.3.1", "0.4.0").unwrap();
+        check("0.0.1", "0.0.2").unwrap();
+        check("1.2.3", "2.0.0").unwrap();
+        let error = check("0.3.1", "0.3.2").unwrap_err().to_string();
+        assert!(
+            error.starts_with("the API changes of package `my_package` require a breaking version bump, but the planned version is 0.3.2"),
+            "{error}"
+        );
+        check("1.2.3", "1.3.0").unwrap_err();
+    }
+
+    #[test]
+    fn api_additions_are_accepted_with_planned_minor_bump() {
+        let additions = SemverCheck::MinorRequired("added fn".to_string());
+        let check = |current: &str, planned: &str| {
+            check_planned_version(
+                "my_package",
+                &Version::parse(current).unwrap(),
+                &Version::parse(planned).unwrap(),
+                &additions,
+            )
+        };
+        check("0.3.1", "0.4.0").unwrap();
+        check("1.2.3", "1.3.0").unwrap();
+        check("1.2.3", "1.2.4").unwrap_err();
+    }
+
+    #[test]
+    fn merge_commits_at_include_paths_are_excluded() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let default_branch = repo.original_branch().to_string();
+        let guide = temp.path().join("docs").join("guide.md");
+        repo.checkout_new_branch("feature").unwrap();
+        fs_err::create_dir_all(guide.parent().unwrap()).unwrap();
+        fs_err::write(&guide, "guide").unwrap();
+        repo.add_all_and_commit("docs: add guide").unwrap();
+        let feature_commit = repo.current_commit_hash().unwrap();
+        repo.checkout(&default_branch).unwrap();
+        repo.git(&["merge", "--no-ff", "--no-commit", "feature"])
+            .unwrap();
+        // The merge commit changes the included path too, so it's listed by `git log`.
+        fs_err::write(&guide, "guide v2").unwrap();
+        repo.add_all_and_commit("Merge branch 'feature'").unwrap();
+        let merge_commit = repo.current_commit_hash().unwrap();
+
+        let local_manifest = Utf8Path::new("../../tests/fixtures/typo-in-overrides/Cargo.toml");
+        let metadata = cargo_utils::get_manifest_metadata(local_manifest).unwrap();
+        let package = metadata.workspace_packages()[0].clone();
+        let config = PackageUpdateConfig {
+            changelog_include_paths: vec!["docs/**".to_string()],
+            ..PackageUpdateConfig::default()
+        };
+        let req = UpdateRequest::new(metadata.clone())
+            .unwrap()
+            .with_package_config(package.name.to_string(), config);
+        let project = Project::new(local_manifest, None, &HashSet::new(), &metadata, &req).unwrap();
+        let commit_ids = |req: &UpdateRequest| {
+            let updater = Updater {
+                project: &project,
+                req,
+            };
+            let mut diff = Diff::new(true);
+            updater
+                .add_commits_at_include_paths(&package, &repo, None, &mut diff)
+                .unwrap();
+            diff.commits.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(commit_ids(&req), vec![merge_commit, feature_commit.clone()]);
+        let req = req.with_changelog_exclude_merge_commits(true);
+        assert_eq!(commit_ids(&req), vec![feature_commit]);
+    }
+
+    #[test]
+    fn lock_baseline_is_the_commit_adding_the_package_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::init(temp.path());
+        let cargo_lock = repo.directory().join("Cargo.lock");
+        let write_lock = |packages: &[(&str, &str)]| {
+            let entries: String = packages
+                .iter()
+                .map(|(name, version)| {
+                    format!("[[package]]\nname = \"{name}\"\nversion = \"{version}\"\n\n")
+                })
+                .collect();
+            fs_err::write(&cargo_lock, format!("version = 4\n\n{entries}")).unwrap();
+        };
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(0, 2, 0);
+
+        write_lock(&[("other", "1.0.0")]);
+        repo.add_all_and_commit("add lock").unwrap();
+        assert_eq!(
+            commit_adding_lock_entry(&repo, cargo_lock.as_str(), &package).unwrap(),
+            None
+        );
+
+        write_lock(&[("my_package", "0.1.0"), ("other", "1.0.0")]);
+        repo.add_all_and_commit("add my_package").unwrap();
+        write_lock(&[("my_package", "0.2.0"), ("other", "1.0.0")]);
+        repo.add_all_and_commit("bump my_package").unwrap();
+        let bump_commit = repo.current_commit_hash().unwrap();
+        write_lock(&[("my_package", "0.2.0"), ("other", "1.1.0")]);
+        repo.add_all_and_commit("bump other").unwrap();
+
+        assert_eq!(
+            commit_adding_lock_entry(&repo, cargo_lock.as_str(), &package).unwrap(),
+            Some(bump_commit)
+        );
+    }
+
+    #[test]
+    fn forced_bump_overrides_commits() {
+        let diff = Diff {
+            registry_package_exists: true,
+            commits: vec![Commit::new(
+                crate::NO_COMMIT_ID.to_string(),
+                "fix: myfix".to_string(),
+            )],
+            is_version_published: true,
+            semver_check: SemverCheck::Skipped,
+            registry_version: None,
+        };
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(0, 3, 1);
+        let mut pkg_config = PackageUpdateConfig::default();
+        assert_eq!(
+            next_package_version(&package, &diff, &pkg_config).unwrap(),
+            Version::new(0, 3, 2)
+        );
+        pkg_config.force_bump = Some(ForceBump::Minor);
+        assert_eq!(
+            next_package_version(&package, &diff, &pkg_config).unwrap(),
+            Version::new(0, 4, 0)
+        );
+    }
+
+    #[test]
+    fn forced_bump_is_ignored_without_changes() {
+        let diff = Diff::new(true);
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(1, 2, 3);
+        let pkg_config = PackageUpdateConfig {
+            force_bump: Some(ForceBump::Major),
+            ..Default::default()
+        };
+        assert_eq!(
+            next_package_version(&package, &diff, &pkg_config).unwrap(),
+            Version::new(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn next_version_is_raised_to_min_version() {
+        let diff = Diff {
+            registry_package_exists: true,
+            commits: vec![Commit::new(
+                crate::NO_COMMIT_ID.to_string(),
+                "fix: myfix".to_string(),
+            )],
+            is_version_published: true,
+            semver_check: SemverCheck::Skipped,
+            registry_version: None,
+        };
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(0, 3, 1);
+        let mut pkg_config = PackageUpdateConfig {
+            min_version: Some(Version::new(0, 5, 0)),
+            ..Default::default()
+        };
+        assert_eq!(
+            next_package_version(&package, &diff, &pkg_config).unwrap(),
+            Version::new(0, 5, 0)
+        );
+        pkg_config.min_version = Some(Version::new(0, 3, 0));
+        assert_eq!(
+            next_package_version(&package, &diff, &pkg_config).unwrap(),
+            Version::new(0, 3, 2)
+        );
+
+        // Packages without changes aren't released.
+        pkg_config.min_version = Some(Version::new(0, 5, 0));
+        assert_eq!(
+            next_package_version(&package, &Diff::new(true), &pkg_config).unwrap(),
+            Version::new(0, 3, 1)
+        );
+    }
+
     #[test]
     fn same_version_is_not_added_to_changelog() {
         let commits = vec![
@@ -1119,4 +1827,193 @@ mod tests {
         .unwrap();
         assert_eq!(old, new.0);
     }
+
+    #[test]
+    fn manually_edited_entry_missing_changes_is_protected() {
+        let commits = vec![Commit::new(
+            crate::NO_COMMIT_ID.to_string(),
+            "fix: myfix".to_string(),
+        )];
+        let changelog_req = ChangelogRequest {
+            changelog_config: Some(crate::changelog::default_git_cliff_config()),
+            protect_manual_edits: true,
+            ..Default::default()
+        };
+        let old = r"## [1.1.0] - 1970-01-01
+
+This release contains a hand-written summary.
+
+### Fixed
+- my awesomefix
+";
+        let err = get_changelog(
+            &commits,
+            &Version::new(1, 1, 0),
+            Some(changelog_req),
+            Some(old),
+            None,
+            None,
+            &fake_package::FakePackage::new("my_package").into(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("looks manually edited"), "{err}");
+        assert!(err.contains("- myfix"), "{err}");
+    }
+
+    #[test]
+    fn manually_edited_entry_with_all_changes_is_kept() {
+        let commits = vec![Commit::new(
+            crate::NO_COMMIT_ID.to_string(),
+            "fix: myfix".to_string(),
+        )];
+        let changelog_req = ChangelogRequest {
+            changelog_config: Some(crate::changelog::default_git_cliff_config()),
+            protect_manual_edits: true,
+            ..Default::default()
+        };
+        let old = r"## [1.1.0] - 1970-01-01
+
+This release contains a hand-written summary.
+
+### Fixed
+- myfix
+";
+        let new = get_changelog(
+            &commits,
+            &Version::new(1, 1, 0),
+            Some(changelog_req),
+            Some(old),
+            None,
+            None,
+            &fake_package::FakePackage::new("my_package").into(),
+        )
+        .unwrap();
+        assert_eq!(old, new.0);
+    }
+
+    #[test]
+    fn excluded_paths_are_ignored() {
+        let git_config = GitConfig {
+            exclude_paths: vec!["**/*.md".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(!is_path_included(
+            Utf8Path::new("crates/my_package/README.md"),
+            &git_config
+        ));
+        assert!(is_path_included(
+            Utf8Path::new("crates/my_package/src/lib.rs"),
+            &git_config
+        ));
+    }
+
+    #[test]
+    fn only_included_paths_are_considered() {
+        let git_config = GitConfig {
+            include_paths: vec!["crates/my_package/src/**".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(is_path_included(
+            Utf8Path::new("crates/my_package/src/lib.rs"),
+            &git_config
+        ));
+        assert!(!is_path_included(
+            Utf8Path::new("crates/my_package/README.md"),
+            &git_config
+        ));
+    }
+
+    #[test]
+    fn unconventional_commits_are_reported() {
+        let commits = vec![
+            Commit::new("abc123".to_string(), "fix: myfix".to_string()),
+            Commit::new("def456".to_string(), "simple update\n\nbody".to_string()),
+        ];
+        let err = ensure_conventional_commits("my_package", &commits).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("package `my_package`"), "{err}");
+        assert!(err.contains("- def456: simple update"), "{err}");
+        assert!(!err.contains("abc123"), "{err}");
+    }
+
+    #[test]
+    fn conventional_commits_are_accepted() {
+        let commits = vec![
+            Commit::new("abc123".to_string(), "fix: myfix".to_string()),
+            Commit::new("def456".to_string(), "feat!: new api".to_string()),
+        ];
+        ensure_conventional_commits("my_package", &commits).unwrap();
+    }
+
+    #[test]
+    fn unpublished_versions_are_summarized() {
+        let mut bumped: Package = fake_package::FakePackage::new("bumped").into();
+        bumped.version = Version::new(0, 2, 0);
+        let mut bumped_diff = Diff::new(true);
+        bumped_diff.set_version_unpublished(Version::new(0, 1, 0));
+        let published: Package = fake_package::FakePackage::new("published").into();
+        let published_diff = Diff::new(true);
+
+        assert_eq!(
+            unpublished_versions_summary(&[(&published, published_diff.clone())]),
+            None
+        );
+        let summary =
+            unpublished_versions_summary(&[(&bumped, bumped_diff), (&published, published_diff)])
+                .unwrap();
+        expect_test::expect![[r#"
+            the local version of these packages is greater than the one in the registry, so release-plz doesn't bump them:
+            - bumped: 0.2.0 (registry: 0.1.0)
+            Run `release-plz release` to publish them."#]]
+        .assert_eq(&summary);
+    }
+
+    #[test]
+    fn semver_check_is_skipped_for_pre_1_0_versions() {
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(0, 3, 0);
+        assert!(!should_check_semver(&package, true, true));
+    }
+
+    #[test]
+    fn crates_io_is_detected_from_publish_field_and_registry() {
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        assert!(publishes_to_crates_io(&package, None));
+        assert!(!publishes_to_crates_io(&package, Some("my-registry")));
+        package.publish = Some(vec!["my-registry".to_string()]);
+        assert!(!publishes_to_crates_io(&package, None));
+        package.publish = Some(vec!["my-registry".to_string(), "crates-io".to_string()]);
+        assert!(publishes_to_crates_io(&package, None));
+    }
+
+    #[test]
+    fn release_link_depends_on_link_type() {
+        let repo_url = RepoUrl::new("https://github.com/owner/repo").unwrap();
+        let link = |link_type| release_link(&repo_url, link_type, "v1.0.0", "v1.1.0");
+        assert_eq!(
+            link(ChangelogLinkType::Compare).unwrap(),
+            "https://github.com/owner/repo/compare/v1.0.0...v1.1.0"
+        );
+        assert_eq!(
+            link(ChangelogLinkType::Tag).unwrap(),
+            "https://github.com/owner/repo/releases/tag/v1.1.0"
+        );
+        assert_eq!(link(ChangelogLinkType::None), None);
+    }
+
+    #[test]
+    fn default_dependency_update_message_is_unchanged() {
+        let message = dependency_update_message(None, &["a", "b"]).unwrap();
+        assert_eq!(message, "chore: updated the following local packages: a, b");
+    }
+
+    #[test]
+    fn dependency_update_message_is_rendered_from_template() {
+        let message =
+            dependency_update_message(Some("deps: bump {{ packages }}"), &["a", "b"]).unwrap();
+        assert_eq!(message, "deps: bump a, b");
+        let commits = [Commit::new(NO_COMMIT_ID.to_string(), message)];
+        ensure_conventional_commits("my_package", &commits).unwrap();
+    }
 }
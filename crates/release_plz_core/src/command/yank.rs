@@ -0,0 +1,170 @@
+use anyhow::Context as _;
+use cargo_metadata::{Metadata, semver::Version};
+use secrecy::SecretString;
+use tracing::{info, instrument};
+
+use crate::{
+    cargo::run_cargo_with_env,
+    command::release::{cargo_registry_token_env_var, find_registry_token},
+};
+
+#[derive(Debug)]
+pub struct YankRequest {
+    /// Cargo metadata.
+    metadata: Metadata,
+    /// Name of the package to yank.
+    package: String,
+    /// Version of the package to yank.
+    version: Version,
+    /// Registry where the package is published.
+    /// If unspecified, crates.io is used.
+    registry: Option<String>,
+    /// Token used to authenticate to the cargo registry.
+    token: Option<SecretString>,
+    /// If true, un-yank the version.
+    undo: bool,
+}
+
+impl YankRequest {
+    pub fn new(metadata: Metadata, package: impl Into<String>, version: Version) -> Self {
+        Self {
+            metadata,
+            package: package.into(),
+            version,
+            registry: None,
+            token: None,
+            undo: false,
+        }
+    }
+
+    pub fn with_registry(mut self, registry: impl Into<String>) -> Self {
+        self.registry = Some(registry.into());
+        self
+    }
+
+    pub fn with_token(mut self, token: impl Into<SecretString>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn with_undo(mut self, undo: bool) -> Self {
+        self.undo = undo;
+        self
+    }
+
+    /// Description of the outcome of the yank, e.g. `yanked my-crate@1.2.3 from crates.io`.
+    pub fn summary(&self) -> String {
+        let action = if self.undo { "un-yanked" } else { "yanked" };
+        let registry = self.registry.as_deref().unwrap_or("crates.io");
+        format!("{action} {}@{} from {registry}", self.package, self.version)
+    }
+
+    fn cargo_yank_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "yank".to_string(),
+            "--version".to_string(),
+            self.version.to_string(),
+        ];
+        if self.undo {
+            args.push("--undo".to_string());
+        }
+        if let Some(registry) = &self.registry {
+            args.push("--registry".to_string());
+            args.push(registry.clone());
+        }
+        args.push(self.package.clone());
+        args
+    }
+}
+
+/// Yank a version of a package of the workspace from the cargo registry,
+/// or un-yank it if [`YankRequest::with_undo`] is set.
+/// Git tags and git releases aren't modified.
+#[instrument(skip_all)]
+pub fn yank(input: &YankRequest) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        input
+            .metadata
+            .workspace_packages()
+            .iter()
+            .any(|p| p.name == input.package),
+        "package `{}` not found in the workspace",
+        input.package
+    );
+    let registry = input.registry.as_deref();
+    let token = find_registry_token(registry, input.token.as_ref(), registry)?;
+    let envs = match token {
+        Some(token) => vec![(cargo_registry_token_env_var(registry)?, token)],
+        None => vec![],
+    };
+    let args = input.cargo_yank_args();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run_cargo_with_env(&input.metadata.workspace_root, &args, &envs)
+        .context("failed to run cargo yank")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "cargo yank failed for {}@{}: {}",
+        input.package,
+        input.version,
+        output.stderr.trim()
+    );
+    info!("{}", input.summary());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> YankRequest {
+        YankRequest::new(
+            fake_package::metadata::fake_metadata(),
+            "my-crate",
+            Version::new(1, 2, 3),
+        )
+    }
+
+    #[test]
+    fn cargo_yank_args_are_correct() {
+        assert_eq!(
+            request().cargo_yank_args(),
+            ["yank", "--version", "1.2.3", "my-crate"]
+        );
+        assert_eq!(
+            request()
+                .with_undo(true)
+                .with_registry("my-registry")
+                .cargo_yank_args(),
+            [
+                "yank",
+                "--version",
+                "1.2.3",
+                "--undo",
+                "--registry",
+                "my-registry",
+                "my-crate"
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_describes_the_yank() {
+        assert_eq!(request().summary(), "yanked my-crate@1.2.3 from crates.io");
+        assert_eq!(
+            request()
+                .with_undo(true)
+                .with_registry("my-registry")
+                .summary(),
+            "un-yanked my-crate@1.2.3 from my-registry"
+        );
+    }
+
+    #[test]
+    fn unknown_package_is_not_yanked() {
+        let error = yank(&request()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "package `my-crate` not found in the workspace"
+        );
+    }
+}
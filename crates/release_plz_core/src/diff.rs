@@ -1,6 +1,7 @@
 use cargo_metadata::semver::Version;
 use git_cliff_core::{commit::Signature, contributor::RemoteContributor};
 use regex::Regex;
+use serde::Serialize;
 
 use crate::semver_check::SemverCheck;
 
@@ -30,6 +31,27 @@ pub struct Commit {
     pub author: Signature,
     pub committer: Signature,
     pub remote: RemoteContributor,
+    /// Trailers of the commit message, e.g. `Closes: #123`.
+    /// [`Option::None`] if they weren't parsed because the changelog template doesn't use them.
+    pub trailers: Option<Vec<Trailer>>,
+}
+
+/// Commit info exposed in the json output of the `update` command.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct CommitSummary {
+    pub id: String,
+    pub message: String,
+    /// Type of the conventional commit, e.g. `feat`.
+    /// [`Option::None`] if the commit isn't conventional.
+    pub conventional_type: Option<String>,
+    pub breaking: bool,
+}
+
+/// Git trailer, i.e. a `key: value` line at the end of the commit message.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
 }
 
 impl Commit {
@@ -41,6 +63,20 @@ impl Commit {
         }
     }
 
+    pub fn summary(&self) -> CommitSummary {
+        let conv = self
+            .to_cliff_commit()
+            .into_conventional()
+            .ok()
+            .and_then(|c| c.conv);
+        CommitSummary {
+            id: self.id.clone(),
+            message: self.message.clone(),
+            conventional_type: conv.as_ref().map(|conv| conv.type_().to_string()),
+            breaking: conv.is_some_and(|conv| conv.breaking()),
+        }
+    }
+
     pub fn is_conventional(&self) -> bool {
         let cliff = self.to_cliff_commit();
         cliff.into_conventional().is_ok()
@@ -48,17 +84,65 @@ impl Commit {
 
     pub fn to_cliff_commit(&self) -> git_cliff_core::commit::Commit<'_> {
         let remote = self.remote.username.is_some().then(|| self.remote.clone());
+        // Trailers are exposed to the changelog template as `commit.extra.trailers`.
+        let extra = self
+            .trailers
+            .as_ref()
+            .map(|trailers| serde_json::json!({ "trailers": trailers }));
         git_cliff_core::commit::Commit {
             id: self.id.clone(),
             message: self.message.clone(),
             author: self.author.clone(),
             committer: self.committer.clone(),
             remote,
+            extra,
             ..Default::default()
         }
     }
 }
 
+/// Parse the trailers of a commit message.
+///
+/// Like git, consider trailers the lines of the last paragraph of the message,
+/// only if all of them are in the `key: value` format.
+/// Lines starting with whitespace continue the value of the previous trailer.
+pub fn parse_trailers(message: &str) -> Vec<Trailer> {
+    let paragraphs: Vec<&str> = message
+        .trim()
+        .split("\n\n")
+        .map(str::trim_end)
+        .filter(|p| !p.trim().is_empty())
+        .collect();
+    // The subject of the commit can't contain trailers.
+    let Some(last_paragraph) = paragraphs.get(1..).and_then(<[&str]>::last) else {
+        return vec![];
+    };
+    let mut trailers: Vec<Trailer> = vec![];
+    for line in last_paragraph.lines() {
+        if line.starts_with(char::is_whitespace) {
+            let Some(trailer) = trailers.last_mut() else {
+                return vec![];
+            };
+            trailer.value.push(' ');
+            trailer.value.push_str(line.trim());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return vec![];
+        };
+        let is_valid_key =
+            !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !is_valid_key {
+            return vec![];
+        }
+        trailers.push(Trailer {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+        });
+    }
+    trailers
+}
+
 impl Diff {
     pub fn new(registry_package_exists: bool) -> Self {
         Self {
@@ -106,6 +190,22 @@ impl Diff {
             .iter()
             .any(|commit| pattern.is_match(&commit.message))
     }
+
+    /// Return `true` if there are commits and all of them are non-breaking
+    /// conventional commits of one of the given types (e.g. `docs`, `chore`).
+    pub fn only_contains_commit_types(&self, types: &[String]) -> bool {
+        !self.commits.is_empty()
+            && self.commits.iter().all(|commit| {
+                let cliff = commit.to_cliff_commit();
+                cliff
+                    .into_conventional()
+                    .ok()
+                    .and_then(|c| c.conv)
+                    .is_some_and(|conv| {
+                        !conv.breaking() && types.iter().any(|t| t == conv.type_().as_str())
+                    })
+            })
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +221,17 @@ mod tests {
         diff
     }
 
+    #[test]
+    fn commit_summary_contains_conventional_type() {
+        let summary = Commit::new("1e6903d".to_string(), "feat!: new api".to_string()).summary();
+        assert_eq!(summary.conventional_type.as_deref(), Some("feat"));
+        assert!(summary.breaking);
+
+        let summary = Commit::new("1e6903d".to_string(), "new api".to_string()).summary();
+        assert_eq!(summary.conventional_type, None);
+        assert!(!summary.breaking);
+    }
+
     #[test]
     fn test_is_commit_message_matched() {
         let diff = create_diff();
@@ -136,4 +247,64 @@ mod tests {
         let present = diff.any_commit_matches(&pattern);
         assert!(!present);
     }
+
+    #[test]
+    fn diff_with_only_skipped_commit_types_is_detected() {
+        let types = ["docs".to_string(), "chore".to_string()];
+        let mut diff = Diff::new(true);
+        assert!(!diff.only_contains_commit_types(&types));
+
+        diff.add_commits(&[
+            Commit::new("1".to_string(), "docs: fix typo".to_string()),
+            Commit::new("2".to_string(), "chore(ci): update action".to_string()),
+        ]);
+        assert!(diff.only_contains_commit_types(&types));
+
+        diff.add_commits(&[Commit::new(
+            "3".to_string(),
+            "docs!: remove guide".to_string(),
+        )]);
+        assert!(!diff.only_contains_commit_types(&types));
+    }
+
+    #[test]
+    fn diff_with_other_commit_types_is_detected() {
+        let types = ["docs".to_string()];
+        let mut diff = Diff::new(true);
+        diff.add_commits(&[
+            Commit::new("1".to_string(), "docs: fix typo".to_string()),
+            Commit::new("2".to_string(), "fix: bug".to_string()),
+        ]);
+        assert!(!diff.only_contains_commit_types(&types));
+
+        let mut diff = Diff::new(true);
+        diff.add_commits(&[Commit::new("1".to_string(), "update docs".to_string())]);
+        assert!(!diff.only_contains_commit_types(&types));
+    }
+
+    #[test]
+    fn trailers_are_parsed() {
+        let message = "fix: my fix\n\nSome description.\n\nCloses: #123\nReviewed-by: John Doe\n  <john@example.com>\n";
+        let trailers = parse_trailers(message);
+        assert_eq!(
+            trailers,
+            vec![
+                Trailer {
+                    key: "Closes".to_string(),
+                    value: "#123".to_string(),
+                },
+                Trailer {
+                    key: "Reviewed-by".to_string(),
+                    value: "John Doe <john@example.com>".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn messages_without_trailers_have_no_trailers() {
+        assert!(parse_trailers("fix: my fix").is_empty());
+        assert!(parse_trailers("fix: my fix\n\nCloses #123").is_empty());
+        assert!(parse_trailers("fix: my fix\n\nthe description: explained").is_empty());
+    }
 }
@@ -3,11 +3,18 @@
 use std::path::Path;
 
 use anyhow::{Context, anyhow};
-use cargo_metadata::{Package, camino::Utf8PathBuf};
+use cargo_metadata::{
+    Package,
+    camino::{Utf8Path, Utf8PathBuf},
+    semver::Version,
+};
 use cargo_utils::CARGO_TOML;
 use tracing::{info, instrument};
 
-use crate::clone::{Cloner, ClonerSource, Crate};
+use crate::{
+    clone::{Cloner, ClonerSource, Crate},
+    fs_utils::to_utf8_path,
+};
 
 #[derive(Debug)]
 pub struct PackageDownloader {
@@ -79,6 +86,35 @@ impl PackageDownloader {
     }
 }
 
+/// Download the `.crate` file of the given package version from the registry
+/// and return its checksum, as reported by the registry index.
+/// Cargo checks the downloaded file against this checksum, so an error is returned if
+/// they don't match, or if the registry doesn't contain the package version.
+pub async fn published_checksum(
+    package: &str,
+    version: &Version,
+    registry: Option<&str>,
+    cargo_cwd: &Utf8Path,
+) -> anyhow::Result<String> {
+    let source: ClonerSource = match registry {
+        Some(registry) => ClonerSource::registry(registry),
+        None => ClonerSource::crates_io(),
+    };
+    let crate_ = Crate::new(package.to_string(), Some(format!("={version}")));
+    let directory = tempfile::tempdir().context("cannot create temporary directory")?;
+    let directory = to_utf8_path(directory.path())?;
+    Cloner::builder()
+        .with_directory(directory)
+        .with_source(source)
+        .with_cargo_cwd(cargo_cwd.to_path_buf())
+        .build()
+        .context("can't build cloner")?
+        .published_checksum(&crate_)
+        .await
+        .with_context(|| format!("error while downloading {package} {version}"))?
+        .with_context(|| format!("{package} {version} not found in the registry"))
+}
+
 /// Read a package from file system
 pub fn read_package(directory: impl AsRef<Path>) -> anyhow::Result<Package> {
     let manifest_path = directory.as_ref().join(CARGO_TOML);
@@ -96,10 +132,136 @@ pub fn read_package(directory: impl AsRef<Path>) -> anyhow::Result<Package> {
 #[cfg(test)]
 mod tests {
     use fake::Fake;
+    use sha2::{Digest as _, Sha256};
     use tempfile::tempdir;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
 
     use super::*;
 
+    const REGISTRY: &str = "local";
+
+    /// `.crate` file of a `my_crate 0.1.0` package, built with `cargo package`.
+    fn crate_file(dir: &Utf8Path) -> Vec<u8> {
+        fs_err::write(
+            dir.join(CARGO_TOML),
+            "[package]\nname = \"my_crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs_err::create_dir(dir.join("src")).unwrap();
+        fs_err::write(dir.join("src").join("lib.rs"), "").unwrap();
+        let output = std::process::Command::new("cargo")
+            .args(["package", "--allow-dirty", "--no-verify", "--offline"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        fs_err::read(dir.join("target/package/my_crate-0.1.0.crate")).unwrap()
+    }
+
+    /// Serve a sparse registry index containing `my_crate 0.1.0` with the given checksum.
+    /// Returns the directory containing the `.cargo/config.toml` pointing to the registry.
+    async fn sparse_registry(
+        server: &MockServer,
+        crate_file: Vec<u8>,
+        cksum: &str,
+    ) -> tempfile::TempDir {
+        let config = serde_json::json!({ "dl": format!("{}/dl", server.uri()) });
+        Mock::given(method("GET"))
+            .and(path("/index/config.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(config))
+            .mount(server)
+            .await;
+        let entry = serde_json::json!({
+            "name": "my_crate",
+            "vers": "0.1.0",
+            "deps": [],
+            "cksum": cksum,
+            "features": {},
+            "yanked": false,
+        });
+        Mock::given(method("GET"))
+            .and(path("/index/my/_c/my_crate"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(entry.to_string()))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/dl/my_crate/0.1.0/download"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(crate_file))
+            .mount(server)
+            .await;
+        let cargo_cwd = tempdir().unwrap();
+        let cargo_dir = cargo_cwd.path().join(".cargo");
+        fs_err::create_dir(&cargo_dir).unwrap();
+        fs_err::write(
+            cargo_dir.join("config.toml"),
+            format!(
+                "[registries.{REGISTRY}]\nindex = \"sparse+{}/index/\"\n",
+                server.uri()
+            ),
+        )
+        .unwrap();
+        cargo_cwd
+    }
+
+    #[tokio::test]
+    async fn published_checksum_is_read_from_sparse_index() {
+        let package_dir = tempdir().unwrap();
+        let crate_file = crate_file(to_utf8_path(package_dir.path()).unwrap());
+        let checksum = format!("{:x}", Sha256::digest(&crate_file));
+        let server = MockServer::start().await;
+        let cargo_cwd = sparse_registry(&server, crate_file, &checksum).await;
+        let cargo_cwd = to_utf8_path(cargo_cwd.path()).unwrap();
+
+        let published = published_checksum(
+            "my_crate",
+            &Version::new(0, 1, 0),
+            Some(REGISTRY),
+            cargo_cwd,
+        )
+        .await
+        .unwrap();
+        assert_eq!(published, checksum);
+
+        let missing_version = published_checksum(
+            "my_crate",
+            &Version::new(0, 2, 0),
+            Some(REGISTRY),
+            cargo_cwd,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            missing_version.to_string(),
+            "my_crate 0.2.0 not found in the registry"
+        );
+    }
+
+    #[tokio::test]
+    async fn published_checksum_fails_if_crate_file_does_not_match_index() {
+        let package_dir = tempdir().unwrap();
+        let crate_file = crate_file(to_utf8_path(package_dir.path()).unwrap());
+        let wrong_checksum = format!("{:x}", Sha256::digest(b"tampered"));
+        let server = MockServer::start().await;
+        let cargo_cwd = sparse_registry(&server, crate_file, &wrong_checksum).await;
+        let cargo_cwd = to_utf8_path(cargo_cwd.path()).unwrap();
+
+        let error = published_checksum(
+            "my_crate",
+            &Version::new(0, 1, 0),
+            Some(REGISTRY),
+            cargo_cwd,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "error while downloading my_crate 0.1.0: failed to verify the checksum of `my_crate v0.1.0 (registry `local`)`"
+        );
+    }
+
     #[tokio::test]
     #[ignore = "requires network"]
     async fn one_package_is_downloaded() {
@@ -141,4 +303,18 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    #[ignore = "requires network"]
+    async fn published_checksum_of_package_is_returned() {
+        let version = Version::new(0, 8, 5);
+        let cargo_cwd = crate::fs_utils::current_directory().unwrap();
+        let checksum = published_checksum("rand", &version, None, &cargo_cwd)
+            .await
+            .unwrap();
+        assert_eq!(
+            checksum,
+            "34af8d1a0e25924bc5b7c43c079c942339d8f0a8b57c39049bef581b46327404"
+        );
+    }
 }
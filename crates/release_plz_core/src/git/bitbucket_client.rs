@@ -0,0 +1,199 @@
+use anyhow::Context;
+use reqwest::Url;
+use reqwest::header::{HeaderMap, HeaderValue};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::RepoUrl;
+use crate::git::forge::{Author, Commit, GitPr, PrCommit, PrCommitDetails, Remote};
+
+#[derive(Debug, Clone)]
+pub struct Bitbucket {
+    pub remote: Remote,
+}
+
+impl Bitbucket {
+    /// Only Bitbucket Cloud is supported.
+    pub fn new(url: RepoUrl, token: SecretString) -> anyhow::Result<Self> {
+        let base_url = url
+            .bitbucket_api_url()
+            .parse()
+            .context("invalid Bitbucket API URL")?;
+
+        debug!("Bitbucket API URL: {base_url}");
+
+        Ok(Self {
+            remote: Remote {
+                base_url,
+                owner: url.owner,
+                repo: url.name,
+                token,
+            },
+        })
+    }
+
+    pub fn default_headers(&self) -> anyhow::Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let mut auth_header: HeaderValue = format!("Bearer {}", self.remote.token.expose_secret())
+            .parse()
+            .context("invalid Bitbucket token")?;
+        auth_header.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, auth_header);
+
+        Ok(headers)
+    }
+}
+
+/// Paginated response.
+// https://developer.atlassian.com/cloud/bitbucket/rest/intro/#pagination
+#[derive(Deserialize, Debug)]
+pub struct BitbucketPage<T> {
+    pub values: Vec<T>,
+}
+
+// https://developer.atlassian.com/cloud/bitbucket/rest/api-group-pullrequests/#api-repositories-workspace-repo-slug-pullrequests-pull-request-id-get
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketPr {
+    pub id: u64,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub author: Option<BitbucketUser>,
+    pub source: BitbucketPrEndpoint,
+    pub links: BitbucketPrLinks,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketUser {
+    pub nickname: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketPrEndpoint {
+    pub branch: BitbucketBranch,
+    pub commit: Option<BitbucketCommitHash>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketBranch {
+    pub name: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketCommitHash {
+    pub hash: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketPrLinks {
+    pub html: BitbucketLink,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketLink {
+    pub href: Url,
+}
+
+impl From<BitbucketPr> for GitPr {
+    fn from(value: BitbucketPr) -> Self {
+        let body = if value.description.is_empty() {
+            None
+        } else {
+            Some(value.description)
+        };
+        Self {
+            user: Author {
+                // Bitbucket identifies users with UUIDs, not with numbers.
+                id: 0,
+                login: value
+                    .author
+                    .and_then(|author| author.nickname)
+                    .unwrap_or_default(),
+            },
+            number: value.id,
+            html_url: value.links.html.href,
+            head: Commit {
+                ref_field: value.source.branch.name,
+                sha: value
+                    .source
+                    .commit
+                    .map(|commit| commit.hash)
+                    .unwrap_or_default(),
+            },
+            title: value.title,
+            body,
+            // Bitbucket doesn't support labels.
+            labels: vec![],
+        }
+    }
+}
+
+// https://developer.atlassian.com/cloud/bitbucket/rest/api-group-commits/#api-repositories-workspace-repo-slug-commit-commit-get
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketCommit {
+    pub hash: String,
+    pub author: Option<BitbucketCommitAuthor>,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitbucketCommitAuthor {
+    /// Present only if the commit author is linked to a Bitbucket account.
+    pub user: Option<BitbucketUser>,
+}
+
+impl BitbucketCommit {
+    pub fn username(&self) -> Option<String> {
+        self.author
+            .as_ref()
+            .and_then(|author| author.user.as_ref())
+            .and_then(|user| user.nickname.clone())
+    }
+}
+
+impl From<BitbucketCommit> for PrCommit {
+    fn from(value: BitbucketCommit) -> Self {
+        Self {
+            author: value.username().map(|login| Author { id: 0, login }),
+            sha: value.hash,
+            commit: PrCommitDetails {
+                message: value.message,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitbucket_pr_is_converted_to_git_pr() {
+        let pr: BitbucketPr = serde_json::from_value(serde_json::json!({
+            "id": 3,
+            "title": "chore: release",
+            "description": "",
+            "author": { "nickname": "bot" },
+            "source": {
+                "branch": { "name": "release-plz-2024" },
+                "commit": { "hash": "abc" }
+            },
+            "links": { "html": { "href": "https://bitbucket.org/a/b/pull-requests/3" } }
+        }))
+        .unwrap();
+        let pr: GitPr = pr.into();
+        assert_eq!(pr.number, 3);
+        assert_eq!(pr.branch(), "release-plz-2024");
+        assert_eq!(pr.head.sha, "abc");
+        assert_eq!(pr.user.login, "bot");
+        assert_eq!(pr.body, None);
+        assert!(pr.labels.is_empty());
+    }
+}
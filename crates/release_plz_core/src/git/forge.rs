@@ -1,10 +1,12 @@
+use crate::git::bitbucket_client::{Bitbucket, BitbucketCommit, BitbucketPage, BitbucketPr};
 use crate::git::{gitea_client::Gitea, gitlab_client::GitLab};
 use crate::{GitHub, GitReleaseInfo};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::pr::Pr;
 use crate::response_ext::ResponseExt;
 use anyhow::Context;
+use cargo_metadata::camino::Utf8Path;
 use http::StatusCode;
 use itertools::Itertools;
 use reqwest::header::HeaderMap;
@@ -14,13 +16,14 @@ use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 #[derive(Debug, Clone)]
 pub enum GitForge {
     Github(GitHub),
     Gitea(Gitea),
     Gitlab(GitLab),
+    Bitbucket(Bitbucket),
 }
 
 impl GitForge {
@@ -29,6 +32,7 @@ impl GitForge {
             Self::Github(g) => g.default_headers(),
             Self::Gitea(g) => g.default_headers(),
             Self::Gitlab(g) => g.default_headers(),
+            Self::Bitbucket(b) => b.default_headers(),
         }
     }
 }
@@ -38,6 +42,7 @@ pub enum ForgeType {
     Github,
     Gitea,
     Gitlab,
+    Bitbucket,
 }
 
 #[derive(Debug)]
@@ -65,6 +70,21 @@ impl Remote {
 pub struct PrCommit {
     pub author: Option<Author>,
     pub sha: String,
+    #[serde(default)]
+    pub commit: PrCommitDetails,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct PrCommitDetails {
+    #[serde(default)]
+    pub message: String,
+}
+
+impl PrCommit {
+    /// First line of the commit message.
+    pub fn subject(&self) -> &str {
+        self.commit.message.lines().next().unwrap_or_default()
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -77,6 +97,8 @@ pub struct Author {
 #[derive(Deserialize, Clone, Debug)]
 pub struct GitLabMrCommit {
     pub id: String,
+    #[serde(default)]
+    pub message: String,
 }
 
 impl From<GitLabMrCommit> for PrCommit {
@@ -84,6 +106,9 @@ impl From<GitLabMrCommit> for PrCommit {
         Self {
             author: None,
             sha: value.id,
+            commit: PrCommitDetails {
+                message: value.message,
+            },
         }
     }
 }
@@ -120,6 +145,21 @@ impl GitPr {
     pub fn label_names(&self) -> Vec<&str> {
         self.labels.iter().map(|l| l.name.as_str()).collect()
     }
+
+    /// Whether the body of the PR contains the given hidden `marker`.
+    pub fn has_marker(&self, marker: &str) -> bool {
+        self.body
+            .as_deref()
+            .is_some_and(|body| body.contains(marker))
+    }
+}
+
+/// Color and description of a label that release-plz creates.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LabelStyle {
+    /// Color as 6 hex digits, without `#`, e.g. `5319e7`.
+    pub color: Option<String>,
+    pub description: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -291,6 +331,7 @@ impl GitClient {
             GitForge::Github(g) => (ForgeType::Github, g.remote),
             GitForge::Gitea(g) => (ForgeType::Gitea, g.remote),
             GitForge::Gitlab(g) => (ForgeType::Gitlab, g.remote),
+            GitForge::Bitbucket(b) => (ForgeType::Bitbucket, b.remote),
         };
         Ok(Self {
             forge,
@@ -303,14 +344,29 @@ impl GitClient {
         match self.forge {
             ForgeType::Github | ForgeType::Gitlab => "per_page",
             ForgeType::Gitea => "limit",
+            ForgeType::Bitbucket => "pagelen",
         }
     }
 
-    /// Creates a GitHub/Gitea release.
+    /// Creates a GitHub/Gitea/GitLab release.
+    /// Bitbucket doesn't support releases, so the release is skipped.
     pub async fn create_release(&self, release_info: &GitReleaseInfo) -> anyhow::Result<()> {
+        if !release_info.assets.is_empty() && self.forge != ForgeType::Github {
+            warn!(
+                "release assets are only supported on GitHub: not attaching {:?} to release `{}`",
+                release_info.assets, release_info.release_name
+            );
+        }
         match self.forge {
             ForgeType::Github | ForgeType::Gitea => self.create_github_release(release_info).await,
             ForgeType::Gitlab => self.create_gitlab_release(release_info).await,
+            ForgeType::Bitbucket => {
+                warn!(
+                    "Bitbucket doesn't support git releases: skipping release `{}`. The release notes are only available in the changelog and in the git tag message",
+                    release_info.release_name
+                );
+                Ok(())
+            }
         }
         .context("Failed to create release")
     }
@@ -328,7 +384,7 @@ impl GitClient {
             prerelease: &release_info.pre_release,
             make_latest: release_info.latest.map(|l| l.to_string()),
         };
-        self.client
+        let response = self.client
             .post(format!("{}/releases", self.repo_url()))
             .json(&create_release_options)
             .send()
@@ -344,6 +400,46 @@ impl GitClient {
                 }
                 anyhow::anyhow!(e)
             })?;
+        if self.forge == ForgeType::Github && !release_info.assets.is_empty() {
+            let release: GitHubRelease = response
+                .json()
+                .await
+                .context("can't parse created release")?;
+            for asset in &release_info.assets {
+                self.upload_github_release_asset(&release, asset)
+                    .await
+                    .with_context(|| format!("failed to upload release asset {asset}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Docs: https://docs.github.com/en/rest/releases/assets#upload-a-release-asset
+    async fn upload_github_release_asset(
+        &self,
+        release: &GitHubRelease,
+        asset: &Utf8Path,
+    ) -> anyhow::Result<()> {
+        let name = asset
+            .file_name()
+            .with_context(|| format!("invalid asset path {asset}"))?;
+        // The upload url is a hypermedia template, e.g. `https://uploads.github.com/repos/o/r/releases/1/assets{?name,label}`.
+        let upload_url = release
+            .upload_url
+            .split_once('{')
+            .map_or(release.upload_url.as_str(), |(url, _)| url);
+        let mut url = Url::parse(upload_url).context("invalid release upload url")?;
+        url.query_pairs_mut().append_pair("name", name);
+        let content = fs_err::read(asset)?;
+        self.client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(content)
+            .send()
+            .await?
+            .successful_status()
+            .await?;
+        info!("uploaded release asset {name}");
         Ok(())
     }
 
@@ -378,6 +474,65 @@ impl GitClient {
         Ok(())
     }
 
+    /// Update the name and the body of the existing GitHub/Gitea/GitLab release of `release_info.git_tag`.
+    /// The git tag and the other fields of the release are left untouched.
+    /// Returns `false` if the release doesn't exist.
+    pub async fn edit_release(&self, release_info: &GitReleaseInfo) -> anyhow::Result<bool> {
+        let tag: String =
+            url::form_urlencoded::byte_serialize(release_info.git_tag.as_bytes()).collect();
+        let response = match self.forge {
+            ForgeType::Github | ForgeType::Gitea => {
+                let response = self
+                    .client
+                    .get(format!("{}/releases/tags/{tag}", self.repo_url()))
+                    .send()
+                    .await?;
+                if response.status() == StatusCode::NOT_FOUND {
+                    return Ok(false);
+                }
+                let release: GitHubRelease = response
+                    .successful_status()
+                    .await?
+                    .json()
+                    .await
+                    .context("can't parse release")?;
+                self.client
+                    .patch(format!("{}/releases/{}", self.repo_url(), release.id))
+                    .json(&json!({
+                        "name": release_info.release_name,
+                        "body": release_info.release_body,
+                    }))
+                    .send()
+                    .await?
+            }
+            ForgeType::Gitlab => {
+                self.client
+                    .put(format!("{}/releases/{tag}", self.remote.base_url))
+                    .json(&json!({
+                        "name": release_info.release_name,
+                        "description": release_info.release_body,
+                    }))
+                    .send()
+                    .await?
+            }
+            ForgeType::Bitbucket => {
+                warn!(
+                    "Bitbucket doesn't support git releases: can't update release `{}`",
+                    release_info.release_name
+                );
+                return Ok(false);
+            }
+        };
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        response
+            .successful_status()
+            .await
+            .with_context(|| format!("failed to update release of tag {}", release_info.git_tag))?;
+        Ok(true)
+    }
+
     pub fn pulls_url(&self) -> String {
         match self.forge {
             ForgeType::Github | ForgeType::Gitea => {
@@ -386,6 +541,9 @@ impl GitClient {
             ForgeType::Gitlab => {
                 format!("{}/merge_requests", self.repo_url())
             }
+            ForgeType::Bitbucket => {
+                format!("{}/pullrequests", self.repo_url())
+            }
         }
     }
 
@@ -397,6 +555,7 @@ impl GitClient {
         match self.forge {
             ForgeType::Github | ForgeType::Gitea => "open",
             ForgeType::Gitlab => "opened",
+            ForgeType::Bitbucket => "OPEN",
         }
     }
 
@@ -409,12 +568,25 @@ impl GitClient {
                     self.remote.owner_slash_repo()
                 )
             }
-            ForgeType::Gitlab => self.remote.base_url.to_string(),
+            ForgeType::Gitlab | ForgeType::Bitbucket => self.remote.base_url.to_string(),
         }
     }
 
     /// Get all opened Prs which branch starts with the given `branch_prefix`.
     pub async fn opened_prs(&self, branch_prefix: &str) -> anyhow::Result<Vec<GitPr>> {
+        self.opened_prs_matching(|pr| pr.branch().starts_with(branch_prefix))
+            .await
+    }
+
+    /// Get all opened Prs which body contains the given `marker`.
+    pub async fn opened_prs_with_marker(&self, marker: &str) -> anyhow::Result<Vec<GitPr>> {
+        self.opened_prs_matching(|pr| pr.has_marker(marker)).await
+    }
+
+    async fn opened_prs_matching(
+        &self,
+        is_match: impl Fn(&GitPr) -> bool,
+    ) -> anyhow::Result<Vec<GitPr>> {
         let mut page = 1;
         let page_size = 30;
         let mut release_prs: Vec<GitPr> = vec![];
@@ -428,10 +600,8 @@ impl GitClient {
                 .await
                 .context("Failed to retrieve open PRs")?;
             let prs_len = prs.len();
-            let current_release_prs: Vec<GitPr> = prs
-                .into_iter()
-                .filter(|pr| pr.head.ref_field.starts_with(branch_prefix))
-                .collect();
+            let current_release_prs: Vec<GitPr> =
+                prs.into_iter().filter(|pr| is_match(pr)).collect();
             release_prs.extend(current_release_prs);
             if prs_len < page_size {
                 break;
@@ -470,6 +640,16 @@ impl GitClient {
                 let git_prs: Vec<GitPr> = gitlab_mrs.into_iter().map(|mr| mr.into()).collect();
                 Ok(git_prs)
             }
+            ForgeType::Bitbucket => {
+                let bitbucket_prs: BitbucketPage<BitbucketPr> =
+                    resp.json().await.context("failed to parse bitbucket prs")?;
+                let git_prs: Vec<GitPr> = bitbucket_prs
+                    .values
+                    .into_iter()
+                    .map(|pr| pr.into())
+                    .collect();
+                Ok(git_prs)
+            }
         }
     }
 
@@ -480,12 +660,29 @@ impl GitClient {
                 let gitlab_mr: GitLabMr = resp.json().await.context("failed to parse gitlab mr")?;
                 Ok(gitlab_mr.into())
             }
+            ForgeType::Bitbucket => {
+                let bitbucket_pr: BitbucketPr =
+                    resp.json().await.context("failed to parse bitbucket pr")?;
+                Ok(bitbucket_pr.into())
+            }
         }
     }
 
     #[instrument(skip(self))]
     pub async fn close_pr(&self, pr_number: u64) -> anyhow::Result<()> {
         debug!("closing pr #{pr_number}");
+        if self.forge == ForgeType::Bitbucket {
+            // Bitbucket doesn't close PRs by editing their state.
+            self.client
+                .post(format!("{}/{pr_number}/decline", self.pulls_url()))
+                .send()
+                .await?
+                .successful_status()
+                .await
+                .with_context(|| format!("cannot close pr {pr_number}"))?;
+            info!("closed pr #{pr_number}");
+            return Ok(());
+        }
         let edit = PrEdit::new().with_state(self.closed_pr_state());
         self.edit_pr(pr_number, edit)
             .await
@@ -498,6 +695,7 @@ impl GitClient {
         match self.forge {
             ForgeType::Github | ForgeType::Gitea => "closed",
             ForgeType::Gitlab => "close",
+            ForgeType::Bitbucket => "DECLINED",
         }
     }
 
@@ -513,6 +711,18 @@ impl GitClient {
                     .put(format!("{}/merge_requests/{pr_number}", self.repo_url()))
                     .json(&edit_mr)
             }
+            ForgeType::Bitbucket => {
+                let mut edit = serde_json::Map::new();
+                if let Some(title) = pr_edit.title {
+                    edit.insert("title".to_string(), title.into());
+                }
+                if let Some(body) = pr_edit.body {
+                    edit.insert("description".to_string(), body.into());
+                }
+                self.client
+                    .put(format!("{}/{pr_number}", self.pulls_url()))
+                    .json(&edit)
+            }
         };
         debug!("editing pr: {req:?}");
 
@@ -532,7 +742,7 @@ impl GitClient {
                 "title": pr.title,
                 "body": pr.body,
                 "base": pr.base_branch,
-                "head": pr.branch,
+                "head": pr.head(),
                 "draft": pr.draft,
             }),
             // Docs: https://docs.gitlab.com/api/merge_requests/#create-mr
@@ -546,6 +756,21 @@ impl GitClient {
                 // The checkbox can be unchecked in the UI before merging.
                 "remove_source_branch": true
             }),
+            // Docs: https://developer.atlassian.com/cloud/bitbucket/rest/api-group-pullrequests/#api-repositories-workspace-repo-slug-pullrequests-post
+            ForgeType::Bitbucket => {
+                let mut source = json!({ "branch": { "name": pr.branch } });
+                if let Some(head_repo) = &pr.head_repo {
+                    source["repository"] = json!({ "full_name": head_repo });
+                }
+                json!({
+                    "title": pr.title,
+                    "description": pr.body,
+                    "source": source,
+                    "destination": { "branch": { "name": pr.base_branch } },
+                    "draft": pr.draft,
+                    "close_source_branch": true
+                })
+            }
         };
 
         let rep = self
@@ -567,25 +792,75 @@ impl GitClient {
                 let gitlab_mr: GitLabMr = rep.json().await.context("Failed to parse Gitlab MR")?;
                 gitlab_mr.into()
             }
+            ForgeType::Bitbucket => {
+                let bitbucket_pr: BitbucketPr =
+                    rep.json().await.context("Failed to parse Bitbucket PR")?;
+                bitbucket_pr.into()
+            }
         };
 
         info!("opened pr: {}", git_pr.html_url);
-        self.add_labels(&pr.labels, git_pr.number)
+        self.add_labels(&pr.labels, &pr.label_styles, git_pr.number)
             .await
             .context("Failed to add labels")?;
         Ok(git_pr)
     }
 
+    /// Post a comment on the PR.
+    #[instrument(skip(self, body))]
+    pub async fn comment_pr(&self, pr_number: u64, body: &str) -> anyhow::Result<()> {
+        let req = match self.forge {
+            ForgeType::Github | ForgeType::Gitea => self
+                .client
+                .post(format!("{}/{pr_number}/comments", self.issues_url()))
+                .json(&json!({ "body": body })),
+            ForgeType::Gitlab => self
+                .client
+                .post(format!("{}/{pr_number}/notes", self.pulls_url()))
+                .json(&json!({ "body": body })),
+            ForgeType::Bitbucket => self
+                .client
+                .post(format!("{}/{pr_number}/comments", self.pulls_url()))
+                .json(&json!({ "content": { "raw": body } })),
+        };
+        req.send()
+            .await?
+            .successful_status()
+            .await
+            .with_context(|| format!("cannot comment on pr {pr_number}"))?;
+        info!("commented on pr #{pr_number}");
+        Ok(())
+    }
+
+    /// Add the labels to the PR.
+    /// Missing labels are created with the color and description of `label_styles`.
     #[instrument(skip(self))]
-    pub async fn add_labels(&self, labels: &[String], pr_number: u64) -> anyhow::Result<()> {
+    pub async fn add_labels(
+        &self,
+        labels: &[String],
+        label_styles: &BTreeMap<String, LabelStyle>,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
         if labels.is_empty() {
             return Ok(());
         }
 
         match self.forge {
-            ForgeType::Github => self.post_github_labels(labels, pr_number).await,
+            ForgeType::Github => {
+                self.create_github_labels(labels, label_styles).await?;
+                self.post_github_labels(labels, pr_number).await
+            }
             ForgeType::Gitlab => self.post_gitlab_labels(labels, pr_number).await,
-            ForgeType::Gitea => self.post_gitea_labels(labels, pr_number).await,
+            ForgeType::Gitea => {
+                self.post_gitea_labels(labels, label_styles, pr_number)
+                    .await
+            }
+            ForgeType::Bitbucket => {
+                warn!(
+                    "Bitbucket doesn't support labels: PR #{pr_number} won't have labels {labels:?}"
+                );
+                Ok(())
+            }
         }
     }
 
@@ -593,6 +868,43 @@ impl GitClient {
         format!("{}/{}/labels", self.issues_url(), pr_number)
     }
 
+    /// Create the labels with a style, if they don't exist.
+    /// GitHub creates the other labels with the default color when adding them to the PR.
+    async fn create_github_labels(
+        &self,
+        labels: &[String],
+        label_styles: &BTreeMap<String, LabelStyle>,
+    ) -> anyhow::Result<()> {
+        for label in labels {
+            let Some(style) = label_styles.get(label) else {
+                continue;
+            };
+            let mut body = json!({ "name": label });
+            if let Some(color) = &style.color {
+                body["color"] = json!(color);
+            }
+            if let Some(description) = &style.description {
+                body["description"] = json!(description);
+            }
+            let response = self
+                .client
+                .post(format!("{}/labels", self.repo_url()))
+                .json(&body)
+                .send()
+                .await?;
+            // GitHub returns 422 (Unprocessable Entity) if the label already exists.
+            if response.status() == StatusCode::UNPROCESSABLE_ENTITY {
+                debug!("label '{label}' already exists");
+                continue;
+            }
+            response
+                .successful_status()
+                .await
+                .with_context(|| format!("failed to create label '{label}'"))?;
+        }
+        Ok(())
+    }
+
     /// Add all labels to PR
     async fn post_github_labels(&self, labels: &[String], pr_number: u64) -> anyhow::Result<()> {
         self.client
@@ -624,11 +936,18 @@ impl GitClient {
     }
 
     /// Add all labels to PR
-    async fn post_gitea_labels(&self, labels: &[String], pr_number: u64) -> anyhow::Result<()> {
+    async fn post_gitea_labels(
+        &self,
+        labels: &[String],
+        label_styles: &BTreeMap<String, LabelStyle>,
+        pr_number: u64,
+    ) -> anyhow::Result<()> {
         let (labels_to_create, mut label_ids) = self
             .get_labels_info_and_categorize_labels(labels, pr_number)
             .await?;
-        let new_label_ids = self.create_gitea_labels(&labels_to_create).await?;
+        let new_label_ids = self
+            .create_gitea_labels(&labels_to_create, label_styles)
+            .await?;
         label_ids.extend(new_label_ids);
         anyhow::ensure!(
             !label_ids.is_empty(),
@@ -710,27 +1029,44 @@ impl GitClient {
         Ok((labels_to_create, label_ids))
     }
 
-    async fn create_gitea_labels(&self, labels_to_create: &[String]) -> anyhow::Result<Vec<u64>> {
+    async fn create_gitea_labels(
+        &self,
+        labels_to_create: &[String],
+        label_styles: &BTreeMap<String, LabelStyle>,
+    ) -> anyhow::Result<Vec<u64>> {
         let mut label_ids = Vec::new();
 
         for label in labels_to_create {
-            let label_id = self.create_gitea_repository_label(label).await?;
+            let label_id = self
+                .create_gitea_repository_label(label, label_styles.get(label))
+                .await?;
             label_ids.push(label_id);
         }
 
         Ok(label_ids)
     }
 
-    async fn create_gitea_repository_label(&self, label: &str) -> anyhow::Result<u64> {
+    async fn create_gitea_repository_label(
+        &self,
+        label: &str,
+        style: Option<&LabelStyle>,
+    ) -> anyhow::Result<u64> {
         debug!("Forge Gitea creating label: {label}");
+        // Required field - using white (#FFFFFF) as default color
+        let color = style
+            .and_then(|s| s.color.as_deref())
+            .map_or_else(|| "#FFFFFF".to_string(), |c| format!("#{c}"));
+        let mut body = json!({
+            "name": label.trim(),
+            "color": color
+        });
+        if let Some(description) = style.and_then(|s| s.description.as_deref()) {
+            body["description"] = json!(description);
+        }
         let res = self
             .client
             .post(format!("{}/labels", self.repo_url()))
-            .json(&json!({
-                "name": label.trim(),
-                // Required field - using white (#FFFFFF) as default color
-                "color": "#FFFFFF"
-            }))
+            .json(&body)
             .send()
             .await?
             .error_for_status()
@@ -762,9 +1098,15 @@ impl GitClient {
     }
 
     pub async fn pr_commits(&self, pr_number: u64) -> anyhow::Result<Vec<PrCommit>> {
+        let mut url = Url::parse(&format!("{}/{}/commits", self.pulls_url(), pr_number))
+            .context("invalid pr commits URL")?;
+        if self.forge == ForgeType::Bitbucket {
+            // The default page size of Bitbucket is small.
+            url.query_pairs_mut().append_pair(self.per_page(), "100");
+        }
         let resp = self
             .client
-            .get(format!("{}/{}/commits", self.pulls_url(), pr_number))
+            .get(url)
             .send()
             .await?
             .successful_status()
@@ -786,6 +1128,20 @@ impl GitClient {
                     .collect();
                 Ok(pr_commits)
             }
+            ForgeType::Bitbucket => {
+                let bitbucket_commits: BitbucketPage<BitbucketCommit> = resp
+                    .json()
+                    .await
+                    .context("failed to parse bitbucket pr commits")?;
+                // Bitbucket returns the newest commits first.
+                let pr_commits = bitbucket_commits
+                    .values
+                    .into_iter()
+                    .rev()
+                    .map(|commit| commit.into())
+                    .collect();
+                Ok(pr_commits)
+            }
         }
     }
 
@@ -807,6 +1163,9 @@ impl GitClient {
                     commit
                 )
             }
+            ForgeType::Bitbucket => {
+                format!("{}/commit/{}/pullrequests", self.repo_url(), commit)
+            }
         };
 
         let response = self.client.get(url).send().await?;
@@ -843,6 +1202,17 @@ impl GitClient {
                 let git_prs: Vec<GitPr> = gitlab_mrs.into_iter().map(|mr| mr.into()).collect();
                 git_prs
             }
+            ForgeType::Bitbucket => {
+                let bitbucket_prs: BitbucketPage<BitbucketPr> = response
+                    .json()
+                    .await
+                    .context("can't parse associated Bitbucket PRs")?;
+                bitbucket_prs
+                    .values
+                    .into_iter()
+                    .map(|pr| pr.into())
+                    .collect()
+            }
         };
 
         let prs_numbers = prs.iter().map(|pr| pr.number).collect::<Vec<_>>();
@@ -884,14 +1254,16 @@ impl GitClient {
             return Ok(RemoteCommit { username: None });
         }
 
-        let remote_commit: GitHubCommit = response
-            .successful_status()
-            .await?
-            .json()
-            .await
-            .context("can't parse commits")?;
-
-        let username = remote_commit.author.and_then(|author| author.login);
+        let response = response.successful_status().await?;
+        let username = if self.forge == ForgeType::Bitbucket {
+            let remote_commit: BitbucketCommit =
+                response.json().await.context("can't parse commits")?;
+            remote_commit.username()
+        } else {
+            let remote_commit: GitHubCommit =
+                response.json().await.context("can't parse commits")?;
+            remote_commit.author.and_then(|author| author.login)
+        };
         Ok(RemoteCommit { username })
     }
 
@@ -902,6 +1274,7 @@ impl GitClient {
                 format!("git/{commits_path}")
             }
             ForgeType::Github => commits_path.to_string(),
+            ForgeType::Bitbucket => "commit/".to_string(),
             ForgeType::Gitlab => {
                 unimplemented!("Gitlab support for `release-plz release-pr is not implemented yet")
             }
@@ -918,6 +1291,7 @@ impl GitClient {
             }
             ForgeType::Gitlab => self.post_gitlab_branch(branch_name, sha).await,
             ForgeType::Gitea => self.post_gitea_branch(branch_name, sha).await,
+            ForgeType::Bitbucket => self.post_bitbucket_branch(branch_name, sha).await,
         }
     }
 
@@ -984,6 +1358,21 @@ Please push your local commits and run release-plz again.\nResponse body: {body}
         Ok(())
     }
 
+    async fn post_bitbucket_branch(&self, branch_name: &str, sha: &str) -> anyhow::Result<()> {
+        self.client
+            .post(format!("{}/refs/branches", self.repo_url()))
+            .json(&json!({
+                "name": branch_name,
+                "target": { "hash": sha }
+            }))
+            .send()
+            .await?
+            .successful_status()
+            .await
+            .with_context(|| format!("failed to create branch {branch_name} with sha {sha}"))?;
+        Ok(())
+    }
+
     pub async fn patch_github_ref(&self, ref_name: &str, sha: &str) -> anyhow::Result<()> {
         self.client
             .patch(format!("{}/git/refs/{}", self.repo_url(), ref_name))
@@ -1013,6 +1402,11 @@ Please push your local commits and run release-plz again.\nResponse body: {body}
                 self.repo_url(),
                 urlencoding::encode(branch_name)
             ),
+            ForgeType::Bitbucket => format!(
+                "{}/refs/branches/{}",
+                self.repo_url(),
+                urlencoding::encode(branch_name)
+            ),
         };
         self.client
             .delete(url)
@@ -1035,9 +1429,69 @@ Please push your local commits and run release-plz again.\nResponse body: {body}
             ForgeType::Github => self.create_github_tag(tag_name, message, sha).await,
             ForgeType::Gitlab => self.create_gitlab_tag(tag_name, message, sha).await,
             ForgeType::Gitea => self.create_gitea_tag(tag_name, message, sha).await,
+            ForgeType::Bitbucket => self.create_bitbucket_tag(tag_name, message, sha).await,
         }
     }
 
+    /// Create a lightweight tag, i.e. a tag without message.
+    pub async fn create_lightweight_tag(
+        &self,
+        tag_name: &str,
+        sha: &str,
+    ) -> Result<(), anyhow::Error> {
+        match self.forge {
+            ForgeType::Github => {
+                self.post_github_ref(&format!("refs/tags/{tag_name}"), sha)
+                    .await
+            }
+            ForgeType::Gitlab => {
+                self.post_tag(
+                    "repository/tags",
+                    json!({ "tag_name": tag_name, "ref": sha }),
+                    tag_name,
+                    sha,
+                )
+                .await
+            }
+            ForgeType::Gitea => {
+                self.post_tag(
+                    "tags",
+                    json!({ "tag_name": tag_name, "target": sha }),
+                    tag_name,
+                    sha,
+                )
+                .await
+            }
+            ForgeType::Bitbucket => {
+                self.post_tag(
+                    "refs/tags",
+                    json!({ "name": tag_name, "target": { "hash": sha } }),
+                    tag_name,
+                    sha,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn post_tag(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+        tag_name: &str,
+        sha: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.client
+            .post(format!("{}/{endpoint}", self.repo_url()))
+            .json(&body)
+            .send()
+            .await?
+            .successful_status()
+            .await
+            .with_context(|| format!("failed to create git tag '{tag_name}' with ref '{sha}'"))?;
+        Ok(())
+    }
+
     async fn create_github_tag(
         &self,
         tag_name: &str,
@@ -1110,6 +1564,29 @@ Please push your local commits and run release-plz again.\nResponse body: {body}
             .with_context(|| format!("failed to create git tag '{tag_name}' with ref '{sha}'"))?;
         Ok(())
     }
+
+    /// The tag message is used as the tag description,
+    /// which is the only place where Bitbucket can show the release notes.
+    async fn create_bitbucket_tag(
+        &self,
+        tag_name: &str,
+        message: &str,
+        sha: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.client
+            .post(format!("{}/refs/tags", self.repo_url()))
+            .json(&json!({
+                "name": tag_name,
+                "target": { "hash": sha },
+                "message": message
+            }))
+            .send()
+            .await?
+            .successful_status()
+            .await
+            .with_context(|| format!("failed to create git tag '{tag_name}' with ref '{sha}'"))?;
+        Ok(())
+    }
 }
 
 pub fn validate_labels(labels: &[String]) -> anyhow::Result<()> {
@@ -1145,6 +1622,27 @@ pub fn validate_labels(labels: &[String]) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Check that the label colors are made of 6 hex digits, e.g. `5319e7`.
+pub fn validate_label_styles(label_styles: &BTreeMap<String, LabelStyle>) -> anyhow::Result<()> {
+    for (label, style) in label_styles {
+        if let Some(color) = &style.color {
+            let is_hex_color = color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit());
+            anyhow::ensure!(
+                is_hex_color,
+                "Invalid color `{color}` of label `{label}`: use 6 hex digits without `#`, e.g. `5319e7`."
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Release on GitHub or Gitea.
+#[derive(Deserialize, Debug)]
+struct GitHubRelease {
+    id: u64,
+    upload_url: String,
+}
+
 /// Representation of a single commit.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GitHubCommit {
@@ -1181,8 +1679,268 @@ pub fn contributors_from_commits(commits: &[PrCommit], forge: ForgeType) -> Vec<
 
 #[cfg(test)]
 mod tests {
+    use crate::RepoUrl;
+
     use super::*;
 
+    fn github_client(server: &wiremock::MockServer) -> GitClient {
+        let github = GitHub::new("owner".to_string(), "repo".to_string(), "token".into())
+            .with_base_url(server.uri().parse().unwrap());
+        GitClient::new(GitForge::Github(github)).unwrap()
+    }
+
+    /// Client of the given forge, with the API of the mock server.
+    fn forge_client(server: &wiremock::MockServer, forge: ForgeType) -> GitClient {
+        let token: SecretString = "token".into();
+        let git_forge = match forge {
+            ForgeType::Github => return github_client(server),
+            ForgeType::Gitlab => {
+                let url = RepoUrl::new("https://gitlab.com/owner/repo").unwrap();
+                let mut gitlab = GitLab::new(url, token).unwrap();
+                gitlab.remote.base_url = format!("{}/projects/owner%2Frepo", server.uri())
+                    .parse()
+                    .unwrap();
+                GitForge::Gitlab(gitlab)
+            }
+            ForgeType::Gitea => {
+                let url = RepoUrl::new("https://gitea.example.com/owner/repo").unwrap();
+                let mut gitea = Gitea::new(url, token).unwrap();
+                gitea.remote.base_url = format!("{}/", server.uri()).parse().unwrap();
+                GitForge::Gitea(gitea)
+            }
+            ForgeType::Bitbucket => {
+                let url = RepoUrl::new("https://bitbucket.org/owner/repo").unwrap();
+                let mut bitbucket = Bitbucket::new(url, token).unwrap();
+                bitbucket.remote.base_url = format!("{}/repositories/owner/repo", server.uri())
+                    .parse()
+                    .unwrap();
+                GitForge::Bitbucket(bitbucket)
+            }
+        };
+        GitClient::new(git_forge).unwrap()
+    }
+
+    fn release_info() -> GitReleaseInfo {
+        GitReleaseInfo {
+            git_tag: "v1.0.0".to_string(),
+            release_name: "v1.0.0".to_string(),
+            release_body: "fixed changelog".to_string(),
+            latest: None,
+            draft: false,
+            pre_release: false,
+            assets: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn existing_github_release_is_edited() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/repos/owner/repo/releases/tags/v1.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 42,
+                "upload_url": "https://uploads.github.com/repos/owner/repo/releases/42/assets{?name,label}",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path("/repos/owner/repo/releases/42"))
+            .and(matchers::body_json(json!({
+                "name": "v1.0.0",
+                "body": "fixed changelog",
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let edited = github_client(&server)
+            .edit_release(&release_info())
+            .await
+            .unwrap();
+        assert!(edited);
+    }
+
+    #[tokio::test]
+    async fn github_labels_are_created_with_style() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/repos/owner/repo/labels"))
+            .and(matchers::body_json(json!({
+                "name": "release",
+                "color": "5319e7",
+                "description": "Release PR",
+            })))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/repos/owner/repo/issues/3/labels"))
+            .and(matchers::body_json(json!({ "labels": ["release", "bot"] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let style = LabelStyle {
+            color: Some("5319e7".to_string()),
+            description: Some("Release PR".to_string()),
+        };
+        let label_styles = BTreeMap::from([("release".to_string(), style)]);
+        github_client(&server)
+            .add_labels(
+                &["release".to_string(), "bot".to_string()],
+                &label_styles,
+                3,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn pr_is_commented_on_every_forge() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let cases = [
+            (
+                ForgeType::Github,
+                "/repos/owner/repo/issues/3/comments",
+                json!({ "body": "released" }),
+            ),
+            (
+                ForgeType::Gitea,
+                "/repos/owner/repo/issues/3/comments",
+                json!({ "body": "released" }),
+            ),
+            (
+                ForgeType::Gitlab,
+                "/projects/owner%2Frepo/merge_requests/3/notes",
+                json!({ "body": "released" }),
+            ),
+            (
+                ForgeType::Bitbucket,
+                "/repositories/owner/repo/pullrequests/3/comments",
+                json!({ "content": { "raw": "released" } }),
+            ),
+        ];
+        for (forge, path, body) in cases {
+            let server = MockServer::start().await;
+            Mock::given(matchers::method("POST"))
+                .and(matchers::path(path))
+                .and(matchers::body_json(body))
+                .respond_with(ResponseTemplate::new(201))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            forge_client(&server, forge)
+                .comment_pr(3, "released")
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn lightweight_tag_is_created_on_every_forge() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let cases = [
+            (
+                ForgeType::Github,
+                "/repos/owner/repo/git/refs",
+                json!({ "ref": "refs/tags/v1.0.0", "sha": "abc" }),
+            ),
+            (
+                ForgeType::Gitea,
+                "/repos/owner/repo/tags",
+                json!({ "tag_name": "v1.0.0", "target": "abc" }),
+            ),
+            (
+                ForgeType::Gitlab,
+                "/projects/owner%2Frepo/repository/tags",
+                json!({ "tag_name": "v1.0.0", "ref": "abc" }),
+            ),
+            (
+                ForgeType::Bitbucket,
+                "/repositories/owner/repo/refs/tags",
+                json!({ "name": "v1.0.0", "target": { "hash": "abc" } }),
+            ),
+        ];
+        for (forge, path, body) in cases {
+            let server = MockServer::start().await;
+            // The body has no message, so the forge creates a lightweight tag.
+            Mock::given(matchers::method("POST"))
+                .and(matchers::path(path))
+                .and(matchers::body_json(body))
+                .respond_with(ResponseTemplate::new(201))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            forge_client(&server, forge)
+                .create_lightweight_tag("v1.0.0", "abc")
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_pr_comment_returns_error() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let error = github_client(&server)
+            .comment_pr(3, "released")
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "cannot comment on pr 3");
+    }
+
+    #[test]
+    fn label_colors_are_validated() {
+        let styles = |color: &str| {
+            BTreeMap::from([(
+                "release".to_string(),
+                LabelStyle {
+                    color: Some(color.to_string()),
+                    description: None,
+                },
+            )])
+        };
+        validate_label_styles(&styles("5319e7")).unwrap();
+        expect_test::expect!["Invalid color `#5319e7` of label `release`: use 6 hex digits without `#`, e.g. `5319e7`."]
+            .assert_eq(&validate_label_styles(&styles("#5319e7")).unwrap_err().to_string());
+        validate_label_styles(&styles("5319g7")).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn missing_github_release_is_not_edited() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let edited = github_client(&server)
+            .edit_release(&release_info())
+            .await
+            .unwrap();
+        assert!(!edited);
+    }
+
     #[test]
     fn contributors_are_extracted_from_commits() {
         let commits = vec![
@@ -1192,6 +1950,7 @@ mod tests {
                     login: "bob".to_string(),
                 }),
                 sha: "abc".to_string(),
+                commit: PrCommitDetails::default(),
             },
             PrCommit {
                 author: Some(Author {
@@ -1199,6 +1958,7 @@ mod tests {
                     login: "marco".to_string(),
                 }),
                 sha: "abc".to_string(),
+                commit: PrCommitDetails::default(),
             },
             PrCommit {
                 author: Some(Author {
@@ -1206,6 +1966,7 @@ mod tests {
                     login: "release[bot]".to_string(),
                 }),
                 sha: "abc".to_string(),
+                commit: PrCommitDetails::default(),
             },
             PrCommit {
                 author: Some(Author {
@@ -1213,10 +1974,12 @@ mod tests {
                     login: "gitea-actions".to_string(),
                 }),
                 sha: "abc".to_string(),
+                commit: PrCommitDetails::default(),
             },
             PrCommit {
                 author: None,
                 sha: "abc".to_string(),
+                commit: PrCommitDetails::default(),
             },
         ];
         let contributors = contributors_from_commits(&commits, ForgeType::Gitea);
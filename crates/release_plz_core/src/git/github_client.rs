@@ -22,7 +22,14 @@ impl GitHub {
         }
     }
 
-    pub fn with_base_url(self, base_url: Url) -> Self {
+    /// Use a custom API base URL, e.g. `https://github.mycorp.com/api/v3`
+    /// for GitHub Enterprise Server.
+    pub fn with_base_url(self, mut base_url: Url) -> Self {
+        // API paths are appended to the base URL, so it needs a trailing slash.
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
         Self {
             remote: Remote {
                 base_url,
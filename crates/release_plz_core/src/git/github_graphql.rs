@@ -55,7 +55,13 @@ pub async fn commit_changes(
 
 fn get_graphql_endpoint(remote: &Remote) -> Url {
     let mut base_url = remote.base_url.clone();
-    base_url.set_path("graphql");
+    // GitHub Enterprise Server exposes the REST API at `/api/v3`
+    // and the GraphQL API at `/api/graphql`.
+    let graphql_path = match base_url.path().trim_end_matches('/').strip_suffix("/v3") {
+        Some(api_path) => format!("{api_path}/graphql"),
+        None => "graphql".to_string(),
+    };
+    base_url.set_path(&graphql_path);
 
     base_url
 }
@@ -164,12 +170,34 @@ fn mutation() -> String {
 
 #[cfg(test)]
 mod tests {
+    use secrecy::SecretString;
     use tempfile::tempdir;
 
     use super::*;
 
+    use crate::GitHub;
     use crate::copy_dir::create_symlink;
 
+    #[test]
+    fn graphql_endpoint_of_github_com_and_enterprise_server() {
+        let token = SecretString::from("token");
+        let github = GitHub::new("owner".to_string(), "repo".to_string(), token);
+        assert_eq!(
+            get_graphql_endpoint(&github.remote).as_str(),
+            "https://api.github.com/graphql"
+        );
+
+        let ghes = github.with_base_url("https://github.mycorp.com/api/v3".parse().unwrap());
+        assert_eq!(
+            ghes.remote.base_url.as_str(),
+            "https://github.mycorp.com/api/v3/"
+        );
+        assert_eq!(
+            get_graphql_endpoint(&ghes.remote).as_str(),
+            "https://github.mycorp.com/api/graphql"
+        );
+    }
+
     #[tokio::test]
     async fn github_commit_query() {
         let temporary = tempdir().unwrap();
@@ -1,3 +1,4 @@
+pub mod bitbucket_client;
 pub mod forge;
 pub mod gitea_client;
 pub mod github_client;
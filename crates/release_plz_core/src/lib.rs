@@ -23,22 +23,28 @@ mod release_order;
 pub(crate) mod release_regex;
 mod repo_url;
 mod response_ext;
+mod sbom;
 pub mod semver_check;
 mod tera;
 mod tmp_repo;
 mod toml_compare;
+mod user_command;
 mod version;
 
+pub use cargo::IndexProtocol;
 pub use changelog::*;
 pub use command::*;
 pub use download::{PackageDownloader, read_package};
-pub use git::forge::{GitClient, GitForge, GitPr};
+pub use git::bitbucket_client::Bitbucket;
+pub use git::forge::{GitClient, GitForge, GitPr, LabelStyle};
 pub use git::gitea_client::Gitea;
 pub use git::github_client::GitHub;
 pub use git::gitlab_client::GitLab;
 pub use next_ver::*;
 pub use package_compare::*;
 pub use package_path::*;
-pub use pr::{DEFAULT_BRANCH_PREFIX, Pr};
+pub use pr::{DEFAULT_BRANCH_PREFIX, DEFAULT_PR_BODY_FOOTER, Pr, PrBodyFooter};
 pub use project::*;
+pub use release_order::release_order;
 pub use repo_url::*;
+pub use tera::check_template_context;
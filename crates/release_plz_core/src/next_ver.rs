@@ -7,9 +7,10 @@ use crate::tmp_repo::TempRepo;
 use crate::update_request::UpdateRequest;
 use crate::updater::Updater;
 use crate::{
-    PackagesUpdate, Project,
+    ChangelogOutputFormat, PackagesUpdate, Project,
     changelog_parser::{self, ChangelogRelease},
     copy_dir::copy_dir,
+    diff::CommitSummary,
     fs_utils::{Utf8TempDir, strip_prefix, to_utf8_path},
     package_path::manifest_dir,
     registry_packages::{self},
@@ -38,6 +39,8 @@ pub(crate) const NO_COMMIT_ID: &str = "0000000";
 pub struct ReleaseMetadata {
     /// Template for the git tag created by release-plz.
     pub tag_name_template: Option<String>,
+    /// Prefix prepended to the template of the git tag.
+    pub tag_prefix: String,
     /// Template for the git release name created by release-plz.
     pub release_name_template: Option<String>,
 }
@@ -51,13 +54,21 @@ pub struct ChangelogRequest {
     /// When the new release is published. If unspecified, current date is used.
     pub release_date: Option<NaiveDate>,
     pub changelog_config: Option<git_cliff_core::config::Config>,
+    /// If true, fail instead of ignoring new changes when the changelog already contains
+    /// a manually edited entry for the next version.
+    pub protect_manual_edits: bool,
+    /// Format of the changelog file.
+    pub output_format: ChangelogOutputFormat,
+    /// User-defined variables available in the changelog template.
+    pub template_context: BTreeMap<String, String>,
 }
 
 impl ReleaseMetadataBuilder for UpdateRequest {
     fn get_release_metadata(&self, package_name: &str) -> Option<ReleaseMetadata> {
         let config = self.get_package_config(package_name);
         config.generic.release.then(|| ReleaseMetadata {
-            tag_name_template: config.generic.tag_name_template.clone(),
+            tag_name_template: self.get_package_tag_name(package_name),
+            tag_prefix: config.generic.tag_prefix.clone(),
             release_name_template: None,
         })
     }
@@ -104,27 +115,42 @@ fn process_git_only_package(
     is_multi_package: bool,
 ) -> anyhow::Result<Option<(RegistryPackage, GitWorkTree)>> {
     // Get the release tag template, falling back to default based on project structure
+    let package_config = input.get_package_config(&package.name);
     let template = input
         .get_package_tag_name(&package.name)
         .unwrap_or_else(|| default_tag_name_template(is_multi_package));
-
-    let release_regex =
-        release_regex::get_release_regex(&template, &package.name).context("get release regex")?;
-    debug!(
-        "looking for tags matching pattern: {}",
-        release_regex.to_string()
-    );
+    let template = format!("{}{template}", package_config.generic.tag_prefix);
+
+    // Tags created before adopting release-plz might follow a different template.
+    let previous_tag_patterns = package_config.generic.previous_tag_patterns;
+    let release_regexes = std::iter::once(&template)
+        .chain(&previous_tag_patterns)
+        .map(|template| {
+            release_regex::get_release_regex(template, &package.name)
+                .with_context(|| format!("get release regex for template `{template}`"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let patterns = release_regexes
+        .iter()
+        .map(|r| format!("`{r}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    debug!("looking for tags matching patterns: {patterns}");
 
     // Get the temporary worktree and repo that we run cargo package in
     let (mut repo, worktree) = get_temp_worktree_and_repo(unreleased_project_repo, &package.name)
         .context("get worktree and repo for package")?;
 
     let Some((release_tag, version)) = repo
-        .get_release_tag(&release_regex, &package.name)
+        .get_release_tag(
+            &release_regexes,
+            &package.name,
+            package_config.generic.git_only_reachable_tags_only,
+        )
         .context("get release tag")?
     else {
         info!(
-            "No release tag found matching pattern `{release_regex}`. \
+            "No release tag found matching patterns {patterns}. \
              Package {} will be treated as initial release.",
             package.name
         );
@@ -268,9 +294,15 @@ pub async fn next_versions(input: &UpdateRequest) -> anyhow::Result<(PackagesUpd
         .context("failed to determine local project repository")?;
 
     let repo_is_clean_result = repository.repo.is_clean();
-    if !input.allow_dirty() {
+    // In Jujutsu colocated repositories, the changes of the working-copy commit
+    // look uncommitted to git, so we don't consider them dirty.
+    let is_jj_colocated = git_cmd::is_jj_colocated(local_project.root());
+    if !input.allow_dirty() && !is_jj_colocated {
         repo_is_clean_result?;
     } else if repo_is_clean_result.is_err() {
+        if is_jj_colocated {
+            debug!("jujutsu repository detected: ignoring the changes of the working-copy commit");
+        }
         // Stash uncommitted changes so we can freely check out other commits.
         // This function runs inside a temporary repository, so this has no
         // effects on the original repository of the user.
@@ -422,6 +454,8 @@ pub struct UpdateResult {
     pub version: Version,
     /// New changelog.
     pub changelog: Option<String>,
+    /// Format of [`Self::changelog`].
+    pub changelog_output_format: ChangelogOutputFormat,
     pub semver_check: SemverCheck,
     pub new_changelog_entry: Option<String>,
     /// The last released/published version from the registry.
@@ -429,13 +463,22 @@ pub struct UpdateResult {
     /// Used to generate correct version transitions in PR body (e.g., "0.1.0 -> 0.2.0")
     /// instead of just showing "0.2.0" when `previous_version == next_version`.
     pub registry_version: Option<Version>,
+    /// Commits that justify the update.
+    pub commits: Vec<CommitSummary>,
 }
 
 impl UpdateResult {
     pub fn last_changes(&self) -> anyhow::Result<Option<ChangelogRelease>> {
-        match &self.changelog {
-            Some(c) => changelog_parser::last_release_from_str(c),
-            None => Ok(None),
+        match (&self.changelog, self.changelog_output_format) {
+            (Some(c), ChangelogOutputFormat::Markdown) => {
+                changelog_parser::last_release_from_str(c)
+            }
+            // The new entry is rendered as markdown regardless of the format of the changelog.
+            (Some(_), ChangelogOutputFormat::Json) => match self.new_changelog_entry.as_deref() {
+                Some(entry) if !entry.is_empty() => changelog_parser::last_release_from_str(entry),
+                _ => Ok(None),
+            },
+            (None, _) => Ok(None),
         }
     }
 }
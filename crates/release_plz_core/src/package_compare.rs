@@ -4,6 +4,7 @@ use cargo_metadata::{
     camino::{Utf8Path, Utf8PathBuf},
 };
 use cargo_utils::{CARGO_TOML, get_manifest_metadata};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use tracing::{debug, info};
 
 use crate::{cargo::run_cargo, fs_utils};
@@ -14,13 +15,28 @@ use std::{
     path::Path,
 };
 
+/// Build the set of files to ignore when comparing packages.
+/// Patterns are globs relative to the package root, e.g. `schema.json` or `generated/**`.
+pub fn compare_ignore_files(patterns: &[String]) -> anyhow::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).with_context(|| format!("invalid glob `{pattern}`"))?;
+        builder.add(glob);
+    }
+    builder.build().context("cannot build glob set")
+}
+
 /// Check if two packages are equal.
 ///
 /// ## Args
-/// - `ignored_dirs`: Directories of the `local_package` to ignore when comparing packages.
+/// - `ignored_files`: Files of the packages to ignore when comparing packages.
+///   Paths are relative to the package root.
+/// - `features_args`: Cargo arguments to enable features when running `cargo package`.
 pub fn are_packages_equal(
     local_package: &Utf8Path,
     registry_package: &Utf8Path,
+    ignored_files: &GlobSet,
+    features_args: &[String],
 ) -> anyhow::Result<bool> {
     debug!(
         "compare local package {:?} with registry package {:?}",
@@ -38,12 +54,14 @@ pub fn are_packages_equal(
         registry_package.join("Cargo.toml.orig.orig"),
     )?;
 
-    let local_package_files = get_cargo_package_files(local_package).with_context(|| {
-        format!("cannot determine packaged files of local package {local_package:?}")
-    })?;
-    let registry_package_files = get_cargo_package_files(registry_package).with_context(|| {
-        format!("cannot determine packaged files of registry package {registry_package:?}")
-    })?;
+    let local_package_files =
+        get_cargo_package_files(local_package, features_args).with_context(|| {
+            format!("cannot determine packaged files of local package {local_package:?}")
+        })?;
+    let registry_package_files = get_cargo_package_files(registry_package, features_args)
+        .with_context(|| {
+            format!("cannot determine packaged files of registry package {registry_package:?}")
+        })?;
 
     // Rename the file to the original name.
     rename(
@@ -51,14 +69,17 @@ pub fn are_packages_equal(
         registry_package.join("Cargo.toml.orig"),
     )?;
 
-    let local_files = local_package_files
-        .iter()
-        .filter(|file| *file != "Cargo.toml.orig" && *file != ".cargo_vcs_info.json");
+    let local_files = local_package_files.iter().filter(|file| {
+        *file != "Cargo.toml.orig"
+            && *file != ".cargo_vcs_info.json"
+            && !ignored_files.is_match(file)
+    });
 
     let registry_files = registry_package_files.iter().filter(|file| {
         *file != "Cargo.toml.orig"
             && *file != "Cargo.toml.orig.orig"
             && *file != ".cargo_vcs_info.json"
+            && !ignored_files.is_match(file)
     });
 
     if !local_files.clone().eq(registry_files) {
@@ -102,7 +123,10 @@ fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> anyhow::Result<()> {
     fs_err::rename(from, to).with_context(|| format!("cannot rename {from:?} to {to:?}"))
 }
 
-pub fn get_cargo_package_files(package: &Utf8Path) -> anyhow::Result<Vec<Utf8PathBuf>> {
+pub fn get_cargo_package_files(
+    package: &Utf8Path,
+    features_args: &[String],
+) -> anyhow::Result<Vec<Utf8PathBuf>> {
     // If this crate was packaged locally (i.e. is inside target/package), we can list files
     // directly from disk without invoking `cargo package`.
     // At the moment, this only happens in the git_only flow.
@@ -118,16 +142,20 @@ pub fn get_cargo_package_files(package: &Utf8Path) -> anyhow::Result<Vec<Utf8Pat
         debug!("Packaged files: {:?}", list);
         Ok(list)
     } else {
-        let list = get_cargo_package_list(package)
+        let list = get_cargo_package_list(package, features_args)
             .context("cannot get packaged files from cargo package list")?;
         debug!("Cargo Packaged files: {:?}", list);
         Ok(list)
     }
 }
 
-fn get_cargo_package_list(package: &Utf8Path) -> Result<Vec<Utf8PathBuf>, anyhow::Error> {
+fn get_cargo_package_list(
+    package: &Utf8Path,
+    features_args: &[String],
+) -> Result<Vec<Utf8PathBuf>, anyhow::Error> {
     // We use `--allow-dirty` because we have `Cargo.toml.orig.orig`, which is an uncommitted change.
-    let args = ["package", "--list", "--quiet", "--allow-dirty"];
+    let mut args = vec!["package", "--list", "--quiet", "--allow-dirty"];
+    args.extend(features_args.iter().map(String::as_str));
     let output = run_cargo(package, &args).context("cannot run `cargo package`")?;
 
     anyhow::ensure!(
@@ -191,10 +219,12 @@ fn are_cargo_toml_equal(local_package: &Utf8Path, registry_package: &Utf8Path) -
 /// - the README is the same
 /// - the local package doesn't have a `readme` field in the `Cargo.toml`.
 /// - the package doesn't have a README at all.
+/// - the `readme` field of the `Cargo.toml` matches `ignored_files`.
 pub fn is_readme_updated(
     package_name: &str,
     local_package_path: &Utf8Path,
     registry_package_path: &Utf8Path,
+    ignored_files: &GlobSet,
 ) -> anyhow::Result<bool> {
     // Read again manifest metadata because the Cargo.toml might change on every commit.
     let package = match read_package_metadata(package_name, local_package_path) {
@@ -206,6 +236,14 @@ pub fn is_readme_updated(
             return Ok(false);
         }
     };
+    if package
+        .readme
+        .as_ref()
+        .is_some_and(|readme| ignored_files.is_match(readme))
+    {
+        debug!("{package_name}: README is ignored");
+        return Ok(false);
+    }
 
     let local_package_readme_path = local_readme_override(&package, local_package_path);
     let are_readmes_equal = match local_package_readme_path? {
@@ -278,3 +316,23 @@ fn read_package_metadata(
         .context("cannot find package in Cargo.toml")?;
     Ok(package)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_ignore_files_matches_relative_paths() {
+        let ignored =
+            compare_ignore_files(&["schema.json".to_string(), "generated/**".to_string()]).unwrap();
+        assert!(ignored.is_match("schema.json"));
+        assert!(ignored.is_match("generated/api/types.rs"));
+        assert!(!ignored.is_match("src/lib.rs"));
+    }
+
+    #[test]
+    fn compare_ignore_files_rejects_invalid_globs() {
+        let error = compare_ignore_files(&["[invalid".to_string()]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid glob `[invalid`");
+    }
+}
@@ -1,11 +1,17 @@
+use std::collections::BTreeMap;
+
 use crate::{
-    PackagesUpdate, ReleaseInfo,
-    tera::{PACKAGE_VAR, RELEASES_VAR, VERSION_VAR, render_template},
+    LabelStyle, PackagesUpdate, ReleaseInfo,
+    tera::{PACKAGE_VAR, RELEASES_VAR, VERSION_VAR, extend_context, render_template},
 };
+use cargo_metadata::camino::Utf8Path;
 use chrono::SecondsFormat;
 
 pub const DEFAULT_BRANCH_PREFIX: &str = "release-plz-";
 pub const OLD_BRANCH_PREFIX: &str = "release-plz/";
+/// Hidden comment added to the body of the release PR, so that release-plz can find
+/// its PR even if the branch doesn't start with the branch prefix anymore.
+pub const RELEASE_PR_MARKER: &str = "<!-- release-plz-pr -->";
 pub const DEFAULT_PR_BODY_TEMPLATE: &str = r#"
 {% set changes %}
 {%- for release in releases %}
@@ -36,10 +42,36 @@ pub const DEFAULT_PR_BODY_TEMPLATE: &str = r#"
 <details><summary><i><b>Changelog</b></i></summary><p>
 {{ changes }}
 </p></details>
-{% endif %}
----
+{% endif %}"#;
+pub const DEFAULT_PR_BODY_FOOTER: &str = r#"---
 This PR was generated with [release-plz](https://github.com/release-plz/release-plz/)."#;
 
+/// Footer appended to the body of the release PR.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PrBodyFooter {
+    /// [`DEFAULT_PR_BODY_FOOTER`] if the body uses the default template.
+    /// No footer if the body uses a custom template.
+    #[default]
+    Default,
+    /// [`DEFAULT_PR_BODY_FOOTER`], even if the body uses a custom template.
+    Enabled,
+    /// Tera template of the footer.
+    Custom(String),
+    /// No footer.
+    Disabled,
+}
+
+impl PrBodyFooter {
+    fn template(&self, custom_body_template: bool) -> Option<&str> {
+        match self {
+            Self::Default if custom_body_template => None,
+            Self::Default | Self::Enabled => Some(DEFAULT_PR_BODY_FOOTER),
+            Self::Custom(footer) => Some(footer),
+            Self::Disabled => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Pr {
     pub base_branch: String,
@@ -48,9 +80,17 @@ pub struct Pr {
     pub body: String,
     pub draft: bool,
     pub labels: Vec<String>,
+    /// Color and description of the labels created by release-plz.
+    pub label_styles: BTreeMap<String, LabelStyle>,
+    /// Repository containing `branch`, in the `owner/repo` format.
+    /// If [`Option::None`], `branch` is in the repository where the PR is opened.
+    pub head_repo: Option<String>,
 }
 
 impl Pr {
+    /// `template_context` contains the user-defined variables available in the
+    /// title and body templates.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         default_branch: &str,
         packages_to_update: &PackagesUpdate,
@@ -58,6 +98,8 @@ impl Pr {
         branch_prefix: &str,
         title_template: Option<String>,
         body_template: Option<&str>,
+        body_footer: &PrBodyFooter,
+        template_context: &BTreeMap<String, String>,
     ) -> anyhow::Result<Self> {
         let pr = Self {
             branch: release_branch(branch_prefix),
@@ -66,10 +108,18 @@ impl Pr {
                 packages_to_update,
                 project_contains_multiple_pub_packages,
                 title_template,
+                template_context,
+            )?,
+            body: pr_body(
+                packages_to_update,
+                body_template,
+                body_footer,
+                template_context,
             )?,
-            body: pr_body(packages_to_update, body_template)?,
             draft: false,
             labels: vec![],
+            label_styles: BTreeMap::new(),
+            head_repo: None,
         };
         Ok(pr)
     }
@@ -83,6 +133,49 @@ impl Pr {
         self.labels = labels;
         self
     }
+
+    pub fn with_label_styles(mut self, label_styles: BTreeMap<String, LabelStyle>) -> Self {
+        self.label_styles = label_styles;
+        self
+    }
+
+    pub fn with_head_repo(mut self, head_repo: Option<String>) -> Self {
+        self.head_repo = head_repo;
+        self
+    }
+
+    /// Append the hidden `marker` to the body, making room for it if the body
+    /// is too long.
+    pub fn with_marker(mut self, marker: &str) -> Self {
+        let max_body_len = MAX_BODY_LEN - marker.chars().count() - 2;
+        let body: String = self.body.trim_end().chars().take(max_body_len).collect();
+        self.body = format!("{body}\n\n{marker}");
+        self
+    }
+
+    /// Head of the PR in the format expected by the GitHub and Gitea APIs,
+    /// i.e. `owner:branch` if the branch is in a fork.
+    pub fn head(&self) -> String {
+        match self
+            .head_repo
+            .as_deref()
+            .and_then(|repo| repo.split_once('/'))
+        {
+            Some((owner, _)) => format!("{owner}:{}", self.branch),
+            None => self.branch.clone(),
+        }
+    }
+}
+
+/// Marker of the release PR of the workspace in `workspace_dir`, relative to the
+/// repository root. Workspaces in subdirectories have their own marker, so that
+/// repositories with multiple workspaces can have one release PR per workspace.
+pub fn release_pr_marker(workspace_dir: &Utf8Path) -> String {
+    if workspace_dir.as_str().is_empty() {
+        RELEASE_PR_MARKER.to_string()
+    } else {
+        format!("<!-- release-plz-pr: {workspace_dir} -->")
+    }
 }
 
 fn release_branch(prefix: &str) -> String {
@@ -98,6 +191,7 @@ fn pr_title(
     packages_to_update: &PackagesUpdate,
     project_contains_multiple_pub_packages: bool,
     title_template: Option<String>,
+    template_context: &BTreeMap<String, String>,
 ) -> anyhow::Result<String> {
     let updates = packages_to_update.updates();
     let first_version = &updates[0].1.version;
@@ -119,6 +213,7 @@ fn pr_title(
         if are_all_versions_equal() {
             context.insert(VERSION_VAR, first_version.to_string().as_str());
         }
+        extend_context(&mut context, template_context);
 
         render_template(&title_template, &context, "pr_name")?
     } else if updates.len() == 1 && project_contains_multiple_pub_packages {
@@ -145,11 +240,14 @@ const MAX_BODY_LEN: usize = 65536;
 fn pr_body(
     packages_to_update: &PackagesUpdate,
     body_template: Option<&str>,
+    body_footer: &PrBodyFooter,
+    template_context: &BTreeMap<String, String>,
 ) -> anyhow::Result<String> {
+    let footer_template = body_footer.template(body_template.is_some());
     let body_template = body_template.unwrap_or(DEFAULT_PR_BODY_TEMPLATE);
 
     let mut releases = packages_to_update.releases();
-    let first_render = render_pr_body(&releases, body_template)?;
+    let first_render = render_pr_body(&releases, body_template, footer_template, template_context)?;
 
     if first_render.chars().count() > MAX_BODY_LEN {
         tracing::info!(
@@ -161,17 +259,29 @@ fn pr_body(
             release.title = None;
         });
 
-        render_pr_body(&releases, body_template)
+        render_pr_body(&releases, body_template, footer_template, template_context)
     } else {
         Ok(first_render)
     }
 }
 
-fn render_pr_body(releases: &[ReleaseInfo], body_template: &str) -> anyhow::Result<String> {
+fn render_pr_body(
+    releases: &[ReleaseInfo],
+    body_template: &str,
+    footer_template: Option<&str>,
+    template_context: &BTreeMap<String, String>,
+) -> anyhow::Result<String> {
     let mut context = tera::Context::new();
     context.insert(RELEASES_VAR, releases);
+    extend_context(&mut context, template_context);
 
-    let rendered_body = render_template(body_template, &context, "pr_body")?;
+    let mut rendered_body = render_template(body_template, &context, "pr_body")?;
+    // The footer is rendered separately, so that it's appended after the output
+    // of custom body templates.
+    if let Some(footer_template) = footer_template {
+        let rendered_footer = render_template(footer_template, &context, "pr_body_footer")?;
+        rendered_body = format!("{rendered_body}\n{rendered_footer}");
+    }
     Ok(trim_pr_body(rendered_body))
 }
 
@@ -212,4 +322,157 @@ mod tests {
         assert!(body.contains("* `my-package`: 0.1.0 -> 0.1.1 (✓ API compatible changes)"));
         assert!(body.contains("- fixed a bug"));
     }
+
+    fn packages_update() -> PackagesUpdate {
+        let package = fake_package::FakePackage::new("my-package").into();
+        let update = crate::UpdateResult {
+            version: cargo_metadata::semver::Version::new(0, 1, 1),
+            changelog: None,
+            changelog_output_format: crate::ChangelogOutputFormat::Markdown,
+            semver_check: crate::semver_check::SemverCheck::Skipped,
+            new_changelog_entry: None,
+            registry_version: None,
+            commits: vec![],
+        };
+        PackagesUpdate::new(vec![(package, update)])
+    }
+
+    #[test]
+    fn default_pr_body_ends_with_default_footer() {
+        let body = pr_body(
+            &packages_update(),
+            None,
+            &PrBodyFooter::Default,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(body.ends_with(&format!("\n{DEFAULT_PR_BODY_FOOTER}")));
+    }
+
+    #[test]
+    fn custom_pr_body_has_no_default_footer() {
+        let body = pr_body(
+            &packages_update(),
+            Some("custom body"),
+            &PrBodyFooter::Default,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(body, "custom body");
+    }
+
+    #[test]
+    fn enabled_footer_is_appended_to_custom_pr_body() {
+        let body = pr_body(
+            &packages_update(),
+            Some("custom body"),
+            &PrBodyFooter::Enabled,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(body, format!("custom body\n{DEFAULT_PR_BODY_FOOTER}"));
+    }
+
+    #[test]
+    fn custom_footer_is_appended_to_custom_pr_body() {
+        let footer = PrBodyFooter::Custom("released {{ releases | length }} package".to_string());
+        let body = pr_body(
+            &packages_update(),
+            Some("custom body"),
+            &footer,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(body, "custom body\nreleased 1 package");
+    }
+
+    #[test]
+    fn footer_can_be_disabled() {
+        let body = pr_body(
+            &packages_update(),
+            None,
+            &PrBodyFooter::Disabled,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(!body.contains("release-plz"));
+    }
+
+    #[test]
+    fn pr_head_contains_fork_owner() {
+        let pr = Pr::new(
+            "main",
+            &packages_update(),
+            false,
+            DEFAULT_BRANCH_PREFIX,
+            None,
+            None,
+            &PrBodyFooter::Default,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(pr.head(), pr.branch);
+
+        let pr = pr.with_head_repo(Some("bot/proj".to_string()));
+        assert_eq!(pr.head(), format!("bot:{}", pr.branch));
+    }
+
+    #[test]
+    fn marker_is_appended_to_pr_body() {
+        let pr = Pr::new(
+            "main",
+            &packages_update(),
+            false,
+            DEFAULT_BRANCH_PREFIX,
+            None,
+            Some("custom body\n"),
+            &PrBodyFooter::Default,
+            &BTreeMap::new(),
+        )
+        .unwrap()
+        .with_marker(RELEASE_PR_MARKER);
+        assert_eq!(pr.body, "custom body\n\n<!-- release-plz-pr -->");
+
+        let long_body = "a".repeat(MAX_BODY_LEN);
+        let pr = Pr {
+            body: long_body,
+            ..pr
+        }
+        .with_marker(RELEASE_PR_MARKER);
+        assert_eq!(pr.body.chars().count(), MAX_BODY_LEN);
+        assert!(pr.body.ends_with(RELEASE_PR_MARKER));
+    }
+
+    #[test]
+    fn workspaces_in_subdirectories_have_their_own_marker() {
+        assert_eq!(release_pr_marker(Utf8Path::new("")), RELEASE_PR_MARKER);
+        assert_eq!(
+            release_pr_marker(Utf8Path::new("crates/tools")),
+            "<!-- release-plz-pr: crates/tools -->"
+        );
+    }
+
+    #[test]
+    fn template_context_is_available_in_pr_templates() {
+        let template_context = BTreeMap::from([
+            ("team".to_string(), "core".to_string()),
+            (RELEASES_VAR.to_string(), "ignored".to_string()),
+        ]);
+        let body = pr_body(
+            &packages_update(),
+            Some("{{ team }}: {{ releases | length }} release"),
+            &PrBodyFooter::Disabled,
+            &template_context,
+        )
+        .unwrap();
+        assert_eq!(body, "core: 1 release");
+        let title = pr_title(
+            &packages_update(),
+            false,
+            Some("chore({{ team }}): release".to_string()),
+            &template_context,
+        )
+        .unwrap();
+        assert_eq!(title, "chore(core): release");
+    }
 }
@@ -164,6 +164,10 @@ impl Project {
 
         let template = template
             .unwrap_or_else(|| default_tag_name_template(self.contains_multiple_pub_packages));
+        let template = match (field, release_metadata) {
+            (TemplateField::GitTagName, Some(m)) => format!("{}{template}", m.tag_prefix),
+            _ => template,
+        };
 
         let context = tera_context(package_name, version);
         crate::tera::render_template(&template, &context, template_name)
@@ -329,6 +333,7 @@ mod tests {
     struct ReleaseMetadataBuilderStub {
         release: bool,
         tag_name: Option<String>,
+        tag_prefix: String,
         release_name: Option<String>,
     }
 
@@ -337,6 +342,7 @@ mod tests {
             Self {
                 release,
                 tag_name,
+                tag_prefix: String::new(),
                 release_name,
             }
         }
@@ -346,6 +352,7 @@ mod tests {
         fn get_release_metadata(&self, _package_name: &str) -> Option<ReleaseMetadata> {
             self.release.then(|| ReleaseMetadata {
                 tag_name_template: self.tag_name.clone(),
+                tag_prefix: self.tag_prefix.clone(),
                 release_name_template: self.release_name.clone(),
             })
         }
@@ -459,4 +466,30 @@ mod tests {
             "release-prefix-typo_test-middle-0.1.0-postfix"
         );
     }
+
+    #[test]
+    fn project_tag_prefix_is_prepended_to_tag_only() {
+        let local_manifest = Utf8Path::new("../../tests/fixtures/typo-in-overrides/Cargo.toml");
+        let metadata = get_manifest_metadata(local_manifest).unwrap();
+        let release_metadata_builder = ReleaseMetadataBuilderStub {
+            tag_prefix: "myproduct-".to_string(),
+            ..ReleaseMetadataBuilderStub::new(true, None, None)
+        };
+        let project = Project::new(
+            local_manifest,
+            None,
+            &HashSet::default(),
+            &metadata,
+            &release_metadata_builder,
+        )
+        .expect("Should ok");
+        assert_eq!(
+            project.git_tag("typo_test", "0.1.0").unwrap(),
+            "myproduct-v0.1.0"
+        );
+        assert_eq!(
+            project.release_name("typo_test", "0.1.0").unwrap(),
+            "v0.1.0"
+        );
+    }
 }
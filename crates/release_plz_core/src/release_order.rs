@@ -3,6 +3,13 @@ use tracing::debug;
 
 /// Return packages in an order they can be released.
 /// In the result, the packages are placed after all their dependencies.
+///
+/// Only the dependencies between the given packages are considered.
+/// Dev-dependencies are ignored, unless they are enabled by a feature of the package,
+/// because `cargo publish` needs them to be published.
+///
+/// This function only reads the given packages: it doesn't access git or the cargo registry.
+///
 /// Return an error if a circular dependency is detected.
 pub fn release_order<'a>(packages: &'a [&Package]) -> anyhow::Result<Vec<&'a Package>> {
     let mut order = vec![];
@@ -53,6 +60,13 @@ fn release_order_inner<'a>(
     Ok(())
 }
 
+/// Return true if `dependency` must be released before `pkg`.
+pub(crate) fn depends_on(pkg: &Package, dependency: &str) -> bool {
+    pkg.dependencies
+        .iter()
+        .any(|d| d.name == dependency && should_dep_be_released_before(d, pkg))
+}
+
 /// Return true if the package is part of a packages array.
 /// This function exists because `package.contains(pkg)` is expensive,
 /// because it compares the whole package struct.
@@ -133,6 +147,14 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn dev_dependencies_are_not_required_before_release() {
+        let pkg = pkg("a", &[dep("b"), dev_dep("c")]);
+        assert!(depends_on(&pkg, "b"));
+        assert!(!depends_on(&pkg, "c"));
+        assert!(!depends_on(&pkg, "d"));
+    }
+
     // Diagrams created with https://asciiflow.com/
 
     /// A─►B
@@ -186,6 +208,34 @@ mod tests {
             .assert_eq(&release_order(&pkgs).unwrap_err().to_string());
     }
 
+    /// ┌─►B──┐
+    /// │     ▼
+    /// A     D
+    /// │     ▲
+    /// └─►C──┘
+    #[test]
+    fn diamond_is_ordered() {
+        let pkgs = [
+            &pkg("a", &[dep("b"), dep("c")]),
+            &pkg("b", &[dep("d")]),
+            &pkg("c", &[dep("d")]),
+            &pkg("d", &[]),
+        ];
+        assert_eq!(order(&pkgs), ["d", "b", "c", "a"]);
+    }
+
+    /// A─►B─►C─►D
+    #[test]
+    fn chain_is_ordered() {
+        let pkgs = [
+            &pkg("a", &[dep("b")]),
+            &pkg("b", &[dep("c")]),
+            &pkg("c", &[dep("d")]),
+            &pkg("d", &[]),
+        ];
+        assert_eq!(order(&pkgs), ["d", "c", "b", "a"]);
+    }
+
     /// A────►C
     /// │     ▲
     /// └─►B──┘
@@ -33,6 +33,36 @@ impl RepoUrl {
         self.host.contains("github")
     }
 
+    pub fn is_on_bitbucket(&self) -> bool {
+        self.host.contains("bitbucket")
+    }
+
+    /// Returns `true` if the repository is hosted on github.com,
+    /// `false` if it's hosted on another host, e.g. a GitHub Enterprise Server.
+    pub fn is_github_com(&self) -> bool {
+        matches!(self.host.as_str(), "github.com" | "www.github.com")
+    }
+
+    /// URL of the GitHub REST API.
+    /// - For github.com, it's `https://api.github.com/`.
+    /// - For GitHub Enterprise Server, it's `https://<host>/api/v3/`.
+    pub fn github_api_url(&self) -> String {
+        if self.is_github_com() {
+            return "https://api.github.com/".to_string();
+        }
+        let v3 = "api/v3/";
+        let scheme = if self.scheme == "ssh" {
+            "https"
+        } else {
+            self.scheme.as_str()
+        };
+        if let Some(port) = self.port {
+            format!("{scheme}://{}:{port}/{v3}", self.host)
+        } else {
+            format!("{scheme}://{}/{v3}", self.host)
+        }
+    }
+
     pub fn full_host(&self) -> String {
         format!("https://{}/{}/{}", self.host, self.owner, self.name)
     }
@@ -50,7 +80,13 @@ impl RepoUrl {
 
     pub fn git_pr_link(&self) -> String {
         let host = self.full_host();
-        let pull_path = if self.is_on_github() { "pull" } else { "pulls" };
+        let pull_path = if self.is_on_github() {
+            "pull"
+        } else if self.is_on_bitbucket() {
+            "pull-requests"
+        } else {
+            "pulls"
+        };
         format!("{host}/{pull_path}")
     }
 
@@ -77,6 +113,14 @@ impl RepoUrl {
             format!("{scheme}://{}/{v4}/{prj_path}", self.host)
         }
     }
+
+    /// URL of the Bitbucket Cloud REST API for this repository.
+    pub fn bitbucket_api_url(&self) -> String {
+        format!(
+            "https://api.bitbucket.org/2.0/repositories/{}/{}",
+            self.owner, self.name
+        )
+    }
 }
 
 fn new_url(git_host_url: &str) -> anyhow::Result<RepoUrl> {
@@ -147,4 +191,40 @@ mod tests {
             http_repo.gitlab_api_url()
         );
     }
+
+    #[test]
+    fn bitbucket_api_url() {
+        let repo = RepoUrl::new("git@bitbucket.org:myworkspace/myrepo.git").unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/myworkspace/myrepo",
+            repo.bitbucket_api_url()
+        );
+        assert_eq!(
+            "https://bitbucket.org/myworkspace/myrepo/pull-requests",
+            repo.git_pr_link()
+        );
+    }
+
+    #[test]
+    fn github_api_url_of_github_com() {
+        let repo = RepoUrl::new(GITHUB_REPO_URL).unwrap();
+        assert!(repo.is_github_com());
+        assert_eq!("https://api.github.com/", repo.github_api_url());
+    }
+
+    #[test]
+    fn github_api_url_of_enterprise_server() {
+        let ssh_repo = RepoUrl::new("git@github.mycorp.com:owner/repo.git").unwrap();
+        assert!(!ssh_repo.is_github_com());
+        assert_eq!(
+            "https://github.mycorp.com/api/v3/",
+            ssh_repo.github_api_url()
+        );
+
+        let http_repo = RepoUrl::new("https://github.mycorp.com:8443/owner/repo").unwrap();
+        assert_eq!(
+            "https://github.mycorp.com:8443/api/v3/",
+            http_repo.github_api_url()
+        );
+    }
 }
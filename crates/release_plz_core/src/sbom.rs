@@ -0,0 +1,157 @@
+use std::process::Command;
+
+use anyhow::Context;
+use cargo_metadata::{
+    DependencyKind, Metadata, Package,
+    camino::{Utf8Path, Utf8PathBuf},
+};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::PackagePath as _;
+
+fn is_cargo_cyclonedx_installed() -> bool {
+    Command::new("cargo-cyclonedx")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Name of the SBOM file of the package, e.g. `my_crate-1.2.3.cdx.json`.
+pub fn sbom_file_name(package: &Package) -> String {
+    format!("{}-{}.cdx.json", package.name, package.version)
+}
+
+/// Write the `CycloneDX` SBOM of the package in `output_dir`.
+///
+/// If [cargo-cyclonedx](https://github.com/CycloneDX/cyclonedx-rust-cargo) is installed, use it.
+/// Otherwise, write a minimal SBOM containing the resolved dependencies of the package.
+pub fn write_sbom(package: &Package, output_dir: &Utf8Path) -> anyhow::Result<Utf8PathBuf> {
+    fs_err::create_dir_all(output_dir)?;
+    let sbom_path = output_dir.join(sbom_file_name(package));
+    if is_cargo_cyclonedx_installed() {
+        run_cargo_cyclonedx(package, &sbom_path)?;
+    } else {
+        warn!(
+            "{}: cargo-cyclonedx is not installed. Generating a minimal SBOM with the resolved dependencies of the package",
+            package.name
+        );
+        let metadata = cargo_utils::cargo_metadata_command()
+            .manifest_path(&package.manifest_path)
+            .exec()
+            .with_context(|| format!("cannot resolve the dependencies of {}", package.name))?;
+        let dependencies = resolved_dependencies(package, &metadata)?;
+        let sbom = serde_json::to_string_pretty(&minimal_sbom(package, &dependencies))?;
+        fs_err::write(&sbom_path, sbom)?;
+    }
+    info!("{}: SBOM written to {sbom_path}", package.name);
+    Ok(sbom_path)
+}
+
+fn run_cargo_cyclonedx(package: &Package, sbom_path: &Utf8Path) -> anyhow::Result<()> {
+    let package_path = package.package_path()?;
+    // cargo-cyclonedx writes `<filename>.json` next to the manifest.
+    let file_stem = format!("{}-{}.cdx", package.name, package.version);
+    let output = Command::new("cargo-cyclonedx")
+        .arg("cyclonedx")
+        .arg("--manifest-path")
+        .arg(&package.manifest_path)
+        .args(["--format", "json", "--override-filename", &file_stem])
+        .output()
+        .with_context(|| format!("error while running cargo-cyclonedx on {}", package.name))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "cargo-cyclonedx failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let generated_sbom = package_path.join(format!("{file_stem}.json"));
+    fs_err::rename(&generated_sbom, sbom_path)
+        .with_context(|| format!("cannot move SBOM generated by cargo-cyclonedx to {sbom_path}"))?;
+    Ok(())
+}
+
+fn purl(name: &str, version: &str) -> String {
+    format!("pkg:cargo/{name}@{version}")
+}
+
+/// Normal and build dependencies of the package, as resolved by `cargo metadata`.
+fn resolved_dependencies<'a>(
+    package: &Package,
+    metadata: &'a Metadata,
+) -> anyhow::Result<Vec<&'a Package>> {
+    let resolve = metadata
+        .resolve
+        .as_ref()
+        .context("cargo metadata didn't return the dependency graph")?;
+    let node = resolve
+        .nodes
+        .iter()
+        .find(|node| node.id == package.id)
+        .with_context(|| format!("cannot find {} in the dependency graph", package.name))?;
+    let dependencies = node
+        .deps
+        .iter()
+        .filter(|dep| {
+            dep.dep_kinds
+                .iter()
+                .any(|info| matches!(info.kind, DependencyKind::Normal | DependencyKind::Build))
+        })
+        .map(|dep| &metadata[&dep.pkg])
+        .collect();
+    Ok(dependencies)
+}
+
+/// `CycloneDX` SBOM listing the given dependencies of the package.
+/// Versions of the dependencies are the resolved ones, e.g. the ones in `Cargo.lock`.
+fn minimal_sbom(package: &Package, dependencies: &[&Package]) -> serde_json::Value {
+    let version = package.version.to_string();
+    let components: Vec<serde_json::Value> = dependencies
+        .iter()
+        .map(|dep| {
+            let dep_version = dep.version.to_string();
+            json!({
+                "type": "library",
+                "name": dep.name,
+                "version": dep_version,
+                "purl": purl(&dep.name, &dep_version),
+            })
+        })
+        .collect();
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "component": {
+                "type": "library",
+                "name": package.name,
+                "version": version,
+                "purl": purl(&package.name, &version),
+            }
+        },
+        "components": components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fake_package::FakePackage;
+
+    use super::*;
+
+    #[test]
+    fn minimal_sbom_contains_resolved_dependency_versions() {
+        let package: Package = FakePackage::new("my_crate").into();
+        let serde: Package = FakePackage::new("serde").into();
+        let sbom = minimal_sbom(&package, &[&serde]);
+        assert_eq!(sbom["bomFormat"], "CycloneDX");
+        assert_eq!(sbom["metadata"]["component"]["name"], "my_crate");
+        let components = sbom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0]["name"], "serde");
+        assert_eq!(components[0]["version"], "0.1.0");
+        assert_eq!(components[0]["purl"], "pkg:cargo/serde@0.1.0");
+        assert_eq!(sbom_file_name(&package), "my_crate-0.1.0.cdx.json");
+    }
+}
@@ -1,7 +1,10 @@
 use std::process::Command;
 
 use anyhow::Context;
-use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{
+    camino::{Utf8Path, Utf8PathBuf},
+    semver::Version,
+};
 use cargo_utils::CARGO_TOML;
 
 fn target_dir(path: &Utf8Path) -> Utf8PathBuf {
@@ -27,6 +30,9 @@ pub enum SemverCheck {
     Compatible,
     /// Semver check done. Incompatibilities found.
     Incompatible(String),
+    /// Semver check done against the planned [`ReleaseType`].
+    /// The new API requires a minor version, but a patch was planned.
+    MinorRequired(String),
     /// Semver check skipped. This is the expected state for binaries.
     Skipped,
 }
@@ -36,14 +42,70 @@ impl SemverCheck {
         match self {
             Self::Compatible => " (✓ API compatible changes)",
             Self::Incompatible(_) => " (⚠️ API breaking changes)",
+            Self::MinorRequired(_) => " (⚠️ API additions)",
             Self::Skipped => "",
         }
     }
 }
 
+/// Release type passed to the `--release-type` flag of cargo-semver-checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseType {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl ReleaseType {
+    /// Release type of the bump from `current` to `next`.
+    /// Following Cargo's semver rules, a minor bump of a `0.x` version and a patch bump
+    /// of a `0.0.x` version are breaking, so they are classified as [`ReleaseType::Major`].
+    pub fn from_versions(current: &Version, next: &Version) -> Self {
+        if next.major > current.major {
+            Self::Major
+        } else if next.minor > current.minor {
+            if current.major == 0 {
+                Self::Major
+            } else {
+                Self::Minor
+            }
+        } else if current.major == 0 && current.minor == 0 && next.patch > current.patch {
+            Self::Major
+        } else {
+            Self::Patch
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+        }
+    }
+}
+
+/// Value of the `--release-type` flag of cargo-semver-checks for the planned release type.
+/// A major release accepts every change, so cargo-semver-checks would report breaking changes
+/// as compatible. In that case, the flag isn't passed, so that breaking changes are still
+/// reported.
+fn release_type_arg(release_type: Option<ReleaseType>) -> Option<&'static str> {
+    release_type
+        .filter(|release_type| *release_type != ReleaseType::Major)
+        .map(ReleaseType::as_str)
+}
+
+/// Run cargo-semver-checks.
+/// `features_args` are cargo arguments to enable features, e.g. `--features a,b`.
+/// If `release_type` is set, cargo-semver-checks checks whether the planned release type
+/// is adequate, and additions that require a minor version are reported.
+/// Breaking changes are reported as [`SemverCheck::Incompatible`] even if a major release
+/// is planned.
 pub fn run_semver_check(
     local_package: &Utf8Path,
     registry_package: &Utf8Path,
+    features_args: &[String],
+    release_type: Option<ReleaseType>,
 ) -> anyhow::Result<SemverCheck> {
     let local_cargo_lock = cargo_lock(local_package);
     let registry_cargo_lock = cargo_lock(registry_package);
@@ -55,12 +117,18 @@ pub fn run_semver_check(
     let local_package_contained_target = local_target_dir.exists();
     let registry_package_contained_target = registry_target_dir.exists();
 
-    let output = Command::new("cargo-semver-checks")
+    let mut command = Command::new("cargo-semver-checks");
+    command
         .args(["semver-checks", "check-release"])
         .arg("--manifest-path")
         .arg(local_package.join(CARGO_TOML))
         .arg("--baseline-root")
         .arg(registry_package.join(CARGO_TOML))
+        .args(features_args);
+    if let Some(release_type) = release_type_arg(release_type) {
+        command.args(["--release-type", release_type]);
+    }
+    let output = command
         .output()
         .with_context(|| format!("error while running cargo-semver-checks on {local_package:?}"))?;
 
@@ -80,18 +148,59 @@ pub fn run_semver_check(
     }
 
     if output.status.success() {
-        Ok(SemverCheck::Compatible)
+        return Ok(SemverCheck::Compatible);
+    }
+    let stderr = String::from_utf8(output.stderr)?;
+    let is_major_required = stderr.contains("semver requires new major version");
+    let is_minor_required =
+        release_type.is_some() && stderr.contains("semver requires new minor version");
+    if !is_major_required && !is_minor_required {
+        return Ok(SemverCheck::Compatible);
+    }
+    let stdout = strip_ansi_escapes::strip(output.stdout);
+    let stdout = String::from_utf8(stdout)?.trim().to_string();
+    if stdout.is_empty() {
+        anyhow::bail!("unknown source of semver incompatibility");
+    }
+    if is_major_required {
+        Ok(SemverCheck::Incompatible(stdout))
     } else {
-        let stderr = String::from_utf8(output.stderr)?;
-        if stderr.contains("semver requires new major version") {
-            let stdout = strip_ansi_escapes::strip(output.stdout);
-            let stdout = String::from_utf8(stdout)?.trim().to_string();
-            if stdout.is_empty() {
-                anyhow::bail!("unknown source of semver incompatibility");
-            }
-            Ok(SemverCheck::Incompatible(stdout))
-        } else {
-            Ok(SemverCheck::Compatible)
-        }
+        Ok(SemverCheck::MinorRequired(stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_type_is_derived_from_versions() {
+        let current = Version::new(0, 3, 1);
+        let release_type = |next| ReleaseType::from_versions(&current, &next);
+        assert_eq!(release_type(Version::new(1, 0, 0)), ReleaseType::Major);
+        assert_eq!(release_type(Version::new(0, 4, 0)), ReleaseType::Major);
+        assert_eq!(release_type(Version::new(0, 3, 2)), ReleaseType::Patch);
+    }
+
+    #[test]
+    fn release_type_follows_cargo_semver_rules() {
+        let release_type = |current: &str, next: &str| {
+            ReleaseType::from_versions(
+                &Version::parse(current).unwrap(),
+                &Version::parse(next).unwrap(),
+            )
+        };
+        assert_eq!(release_type("1.2.3", "1.3.0"), ReleaseType::Minor);
+        assert_eq!(release_type("1.2.3", "1.2.4"), ReleaseType::Patch);
+        assert_eq!(release_type("0.0.1", "0.0.2"), ReleaseType::Major);
+        assert_eq!(release_type("0.0.1", "0.1.0"), ReleaseType::Major);
+    }
+
+    #[test]
+    fn release_type_is_not_passed_for_major_releases() {
+        assert_eq!(release_type_arg(Some(ReleaseType::Major)), None);
+        assert_eq!(release_type_arg(Some(ReleaseType::Minor)), Some("minor"));
+        assert_eq!(release_type_arg(Some(ReleaseType::Patch)), Some("patch"));
+        assert_eq!(release_type_arg(None), None);
     }
 }
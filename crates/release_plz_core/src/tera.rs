@@ -1,12 +1,39 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 
-use crate::Remote;
+use cargo_metadata::semver::BuildMetadata;
+
+use crate::{RELEASE_LINK, Remote};
 
 pub const PACKAGE_VAR: &str = "package";
 pub const VERSION_VAR: &str = "version";
 pub const CHANGELOG_VAR: &str = "changelog";
 pub const REMOTE_VAR: &str = "remote";
 pub const RELEASES_VAR: &str = "releases";
+pub const PACKAGES_VAR: &str = "packages";
+pub const DATE_VAR: &str = "date";
+pub const TIME_VAR: &str = "time";
+pub const PREV_VERSION_VAR: &str = "prev_version";
+pub const TAG_VAR: &str = "tag";
+pub const DRY_RUN_VAR: &str = "dry_run";
+pub const COMMIT_VAR: &str = "commit";
+
+/// Variables set by release-plz, which the user-defined template context can't override.
+const RESERVED_VARS: &[&str] = &[
+    PACKAGE_VAR,
+    VERSION_VAR,
+    CHANGELOG_VAR,
+    REMOTE_VAR,
+    RELEASES_VAR,
+    PACKAGES_VAR,
+    DATE_VAR,
+    PREV_VERSION_VAR,
+    TAG_VAR,
+    DRY_RUN_VAR,
+    COMMIT_VAR,
+    RELEASE_LINK,
+];
 
 pub fn tera_var(var_name: &str) -> String {
     format!("{{{{ {var_name} }}}}")
@@ -27,16 +54,43 @@ pub fn default_tag_name_template(is_multi_package: bool) -> String {
     }
 }
 
+/// Tera template of the git tag name used when the `single_tag` option is enabled.
+/// The tag covers all the packages of the workspace, so it doesn't contain the package name.
+pub fn single_tag_name_template() -> String {
+    default_tag_name_template(false)
+}
+
+/// Default Tera template of the git tag of the combined git release,
+/// e.g. `release-2024-05-20-153000`.
+/// It contains the time, so that releasing twice on the same day creates two tags.
+pub fn default_combined_tag_name_template() -> String {
+    format!("release-{}-{}", tera_var(DATE_VAR), tera_var(TIME_VAR))
+}
+
+/// Render the git tag name of the combined git release.
+/// `date` is the release date in the `YYYY-MM-DD` format,
+/// `time` is the UTC release time in the `HHMMSS` format.
+pub fn combined_tag_name(template: Option<&str>, date: &str, time: &str) -> anyhow::Result<String> {
+    let mut context = tera::Context::new();
+    context.insert(DATE_VAR, date);
+    context.insert(TIME_VAR, time);
+    let default_template = default_combined_tag_name_template();
+    let template = template.unwrap_or(&default_template);
+    render_template(template, &context, "combined_git_release_tag_name")
+}
+
 pub fn release_body_from_template(
     package_name: &str,
     version: &str,
     changelog: &str,
     remote: &Remote,
     body_template: Option<&str>,
+    template_context: &BTreeMap<String, String>,
 ) -> anyhow::Result<String> {
     let mut context = tera_context(package_name, version);
     context.insert(CHANGELOG_VAR, changelog);
     context.insert(REMOTE_VAR, remote);
+    extend_context(&mut context, template_context);
 
     let default_body_template = tera_var(CHANGELOG_VAR);
     let body_template = body_template.unwrap_or(&default_body_template);
@@ -44,6 +98,25 @@ pub fn release_body_from_template(
     render_template(body_template, &context, "release_body")
 }
 
+/// Render the build metadata appended to the version of a package, e.g. `20240520.abc1234`.
+/// `date` is the release date in the `YYYYMMDD` format and `commit` is the hash of the
+/// current commit.
+pub fn version_metadata(
+    template: &str,
+    package_name: &str,
+    version: &str,
+    date: &str,
+    commit: &str,
+) -> anyhow::Result<BuildMetadata> {
+    let mut context = tera_context(package_name, version);
+    context.insert(DATE_VAR, date);
+    context.insert(COMMIT_VAR, commit);
+    let metadata = render_template(template, &context, "version_metadata")?;
+    BuildMetadata::new(metadata.trim()).with_context(|| {
+        format!("`{metadata}` isn't valid build metadata: use only alphanumerics, hyphens and dots")
+    })
+}
+
 pub fn render_template(
     template: &str,
     context: &tera::Context,
@@ -52,12 +125,35 @@ pub fn render_template(
     let mut tera = tera::Tera::default();
 
     tera.add_raw_template(template_name, template)
-        .context("failed to add release_body raw template")?;
+        .with_context(|| format!("invalid {template_name} template"))?;
 
     tera.render(template_name, context)
         .with_context(|| format!("failed to render {template_name}"))
 }
 
+/// Return an error if the user-defined template context contains a variable set by release-plz.
+pub fn check_template_context(template_context: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    if let Some(key) = template_context
+        .keys()
+        .find(|key| RESERVED_VARS.contains(&key.as_str()))
+    {
+        anyhow::bail!(
+            "`{key}` can't be used in the template context because it's a variable set by release-plz"
+        );
+    }
+    Ok(())
+}
+
+/// Add the user-defined variables to `context`, without overriding the variables
+/// set by release-plz.
+pub fn extend_context(context: &mut tera::Context, template_context: &BTreeMap<String, String>) {
+    for (key, value) in template_context {
+        if !context.contains_key(key) {
+            context.insert(key.clone(), value);
+        }
+    }
+}
+
 pub fn tera_context(package_name: &str, version: &str) -> tera::Context {
     let mut context = tera::Context::new();
     context.insert(PACKAGE_VAR, package_name);
@@ -77,11 +173,67 @@ mod tests {
             link: "link".to_string(),
             contributors: vec![],
         };
-        let body =
-            release_body_from_template("my_package", "0.1.0", "my changes", &remote, None).unwrap();
+        let body = release_body_from_template(
+            "my_package",
+            "0.1.0",
+            "my changes",
+            &remote,
+            None,
+            &BTreeMap::new(),
+        )
+        .unwrap();
         assert_eq!(body, "my changes");
     }
 
+    #[test]
+    fn undefined_variable_error_points_to_the_variable() {
+        let context = tera_context("my_package", "0.1.0");
+        let error = render_template("release\n{{ pakage }}", &context, "pr_name").unwrap_err();
+        let error = format!("{error:#}");
+        assert!(error.starts_with("failed to render pr_name: "), "{error}");
+        assert!(
+            error.contains("Variable `pakage` is not defined"),
+            "{error}"
+        );
+        assert!(
+            error.contains("Available variables: package, version"),
+            "{error}"
+        );
+        assert!(error.contains("pr_name:2:4"), "{error}");
+    }
+
+    #[test]
+    fn syntax_error_mentions_the_template_name() {
+        let context = tera_context("my_package", "0.1.0");
+        let error = render_template("{{ package | foo }}", &context, "pr_body").unwrap_err();
+        let error = format!("{error:#}");
+        assert!(error.starts_with("invalid pr_body template: "), "{error}");
+        assert!(error.contains("Unknown filter `foo`"), "{error}");
+    }
+
+    #[test]
+    fn version_metadata_is_rendered() {
+        let metadata = version_metadata(
+            "{{ date }}.{{ commit | truncate(length=7, end=\"\") }}",
+            "my_package",
+            "0.1.0",
+            "20240520",
+            "abc1234def",
+        )
+        .unwrap();
+        assert_eq!(metadata.as_str(), "20240520.abc1234");
+    }
+
+    #[test]
+    fn invalid_version_metadata_is_rejected() {
+        let err = version_metadata("{{ package }}", "my_package", "0.1.0", "20240520", "abc")
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("isn't valid build metadata"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn default_tag_template_single_package() {
         let template = default_tag_name_template(false);
@@ -110,6 +262,18 @@ mod tests {
         assert_eq!(result, "v0.5.0");
     }
 
+    #[test]
+    fn combined_tag_name_is_rendered() {
+        assert_eq!(
+            combined_tag_name(None, "2024-05-20", "153000").unwrap(),
+            "release-2024-05-20-153000"
+        );
+        assert_eq!(
+            combined_tag_name(Some("v{{ date }}-prod"), "2024-05-20", "153000").unwrap(),
+            "v2024-05-20-prod"
+        );
+    }
+
     #[test]
     fn template_renders_custom_format() {
         let template = "release-{{ package }}-{{ version }}-prod";
@@ -0,0 +1,139 @@
+//! Environment variables and template variables available to the commands
+//! configured by the user, such as the `verify_command`.
+
+use std::process::Command;
+
+use anyhow::Context as _;
+use cargo_metadata::{Package, semver::Version};
+
+use crate::tera::{
+    CHANGELOG_VAR, DRY_RUN_VAR, PREV_VERSION_VAR, TAG_VAR, render_template, tera_context,
+};
+
+/// Name of the released package.
+pub const PACKAGE_ENV: &str = "RELEASE_PLZ_PACKAGE";
+/// Version of the released package.
+pub const VERSION_ENV: &str = "RELEASE_PLZ_VERSION";
+/// Version of the previous release of the package. Empty if the package was never released.
+pub const PREV_VERSION_ENV: &str = "RELEASE_PLZ_PREV_VERSION";
+/// Git tag of the release.
+pub const TAG_ENV: &str = "RELEASE_PLZ_TAG";
+/// `true` if release-plz is running in dry-run mode, `false` otherwise.
+pub const DRY_RUN_ENV: &str = "RELEASE_PLZ_DRY_RUN";
+/// Changelog entry of the release.
+pub const CHANGELOG_ENV: &str = "RELEASE_PLZ_CHANGELOG";
+
+/// Information about the release passed to the commands configured by the user.
+pub struct UserCommandContext<'a> {
+    pub package: &'a Package,
+    pub prev_version: Option<&'a Version>,
+    pub tag: &'a str,
+    pub dry_run: bool,
+    pub changelog: &'a str,
+}
+
+impl UserCommandContext<'_> {
+    /// Environment variables set when running the command.
+    pub fn env_vars(&self) -> [(&'static str, String); 6] {
+        [
+            (PACKAGE_ENV, self.package.name.to_string()),
+            (VERSION_ENV, self.package.version.to_string()),
+            (PREV_VERSION_ENV, self.prev_version_str()),
+            (TAG_ENV, self.tag.to_string()),
+            (DRY_RUN_ENV, self.dry_run.to_string()),
+            (CHANGELOG_ENV, self.changelog.to_string()),
+        ]
+    }
+
+    fn tera_context(&self) -> tera::Context {
+        let mut context = tera_context(&self.package.name, &self.package.version.to_string());
+        context.insert(PREV_VERSION_VAR, &self.prev_version_str());
+        context.insert(TAG_VAR, self.tag);
+        context.insert(DRY_RUN_VAR, &self.dry_run);
+        context.insert(CHANGELOG_VAR, self.changelog);
+        context
+    }
+
+    fn prev_version_str(&self) -> String {
+        self.prev_version
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    /// Build the command to run.
+    /// Every element of `command` is rendered as a Tera template.
+    pub fn command(&self, command: &[String], field: &str) -> anyhow::Result<Command> {
+        let context = self.tera_context();
+        let rendered = command
+            .iter()
+            .map(|arg| render_template(arg, &context, field))
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        let (program, args) = rendered
+            .split_first()
+            .with_context(|| format!("{field} is empty"))?;
+        let mut command = Command::new(program);
+        command.args(args).envs(self.env_vars());
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PREV_VERSION: Version = Version::new(0, 1, 0);
+
+    fn context(package: &Package) -> UserCommandContext<'_> {
+        UserCommandContext {
+            package,
+            prev_version: Some(&PREV_VERSION),
+            tag: "my_package-v0.2.0",
+            dry_run: true,
+            changelog: "### Fixed\n- fix bug",
+        }
+    }
+
+    fn my_package() -> Package {
+        let mut package: Package = fake_package::FakePackage::new("my_package").into();
+        package.version = Version::new(0, 2, 0);
+        package
+    }
+
+    #[test]
+    fn env_vars_are_set() {
+        let package = my_package();
+        let output = context(&package)
+            .command(&["env".to_string()], "verify_command")
+            .unwrap()
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        for expected in [
+            "RELEASE_PLZ_PACKAGE=my_package",
+            "RELEASE_PLZ_VERSION=0.2.0",
+            "RELEASE_PLZ_PREV_VERSION=0.1.0",
+            "RELEASE_PLZ_TAG=my_package-v0.2.0",
+            "RELEASE_PLZ_DRY_RUN=true",
+            "RELEASE_PLZ_CHANGELOG=### Fixed\n- fix bug",
+        ] {
+            assert!(stdout.contains(expected), "{expected} not in {stdout}");
+        }
+    }
+
+    #[test]
+    fn command_arguments_are_rendered() {
+        let package = my_package();
+        let command = context(&package)
+            .command(
+                &[
+                    "echo".to_string(),
+                    "{{ package }}@{{ prev_version }}..{{ version }}".to_string(),
+                    "{{ tag }}".to_string(),
+                ],
+                "verify_command",
+            )
+            .unwrap();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["my_package@0.1.0..0.2.0", "my_package-v0.2.0"]);
+    }
+}
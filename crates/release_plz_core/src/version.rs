@@ -16,6 +16,9 @@ impl NextVersionFromDiff for Version {
         } else if matches!(diff.semver_check, SemverCheck::Incompatible(_)) {
             let increment = VersionIncrement::breaking(self);
             increment.bump(self)
+        } else if matches!(diff.semver_check, SemverCheck::MinorRequired(_)) {
+            let next = version_updater.increment(self, diff.commits.iter().map(|c| &c.message));
+            next.max(VersionIncrement::Minor.bump(self))
         } else {
             version_updater.increment(self, diff.commits.iter().map(|c| &c.message))
         }
@@ -62,6 +65,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn next_version_is_minor_when_semver_check_requires_it() {
+        let diff = Diff {
+            registry_package_exists: true,
+            commits: vec![Commit::new(
+                NO_COMMIT_ID.to_string(),
+                "fix: my change".to_string(),
+            )],
+            is_version_published: true,
+            semver_check: SemverCheck::MinorRequired("new function".to_string()),
+            registry_version: None,
+        };
+        let version = Version::new(1, 2, 3);
+        assert_eq!(
+            version.next_from_diff(&diff, VersionUpdater::default()),
+            Version::new(1, 3, 0)
+        );
+    }
+
     #[test]
     fn next_version_doesnt_bump_0_x_minor_version_for_features() {
         let diff = Diff {
@@ -100,4 +122,26 @@ mod tests {
             Version::new(0, 3, 0)
         );
     }
+
+    #[test]
+    fn next_version_is_breaking_for_fix_with_breaking_change_footer() {
+        let diff = Diff {
+            registry_package_exists: true,
+            commits: vec![Commit::new(
+                NO_COMMIT_ID.to_string(),
+                "fix: my change\n\nBREAKING CHANGE: removed function".to_string(),
+            )],
+            is_version_published: true,
+            semver_check: SemverCheck::Skipped,
+            registry_version: None,
+        };
+        assert_eq!(
+            Version::new(1, 2, 3).next_from_diff(&diff, VersionUpdater::default()),
+            Version::new(2, 0, 0)
+        );
+        assert_eq!(
+            Version::new(0, 2, 3).next_from_diff(&diff, VersionUpdater::default()),
+            Version::new(0, 3, 0)
+        );
+    }
 }
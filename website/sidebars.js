@@ -25,10 +25,17 @@ const sidebars = {
         "usage/update",
         "usage/release-pr",
         "usage/release",
+        "usage/changelog",
         "usage/init",
         "usage/set-version",
+        "usage/yank",
+        "usage/release-notes",
+        "usage/config",
+        "usage/check",
+        "usage/check-updates",
         "usage/shell-completion",
         "usage/generate-schema",
+        "usage/exit-codes",
       ],
     },
     {